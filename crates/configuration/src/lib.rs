@@ -37,6 +37,9 @@ fn get_with_ownership(config: serde_json::Value, key: &str) -> Option<serde_json
     }
 }
 
+/// Key of a profile naming the profile it inherits values from
+pub const INHERITS_KEY: &str = "inherits";
+
 pub fn get_profile(
     raw_config: serde_json::Value,
     tool: &str,
@@ -46,17 +49,104 @@ pub fn get_profile(
     let tool_config = get_with_ownership(raw_config, tool)
         .unwrap_or(serde_json::Value::Object(serde_json::Map::new()));
 
-    match get_with_ownership(tool_config, profile_name) {
-        Some(profile_value) => Ok(profile_value),
+    match tool_config.get(profile_name) {
+        Some(_) => resolve_profile(&tool_config, profile_name, &mut vec![]),
         None if profile_name == "default" => Ok(serde_json::Value::Object(Default::default())),
         None => Err(anyhow!("Profile [{}] not found in config", profile_name)),
     }
 }
 
+/// Returns the profile with values of the profiles it `inherits` from merged in.
+/// Values set in the profile itself take precedence over the inherited ones
+fn resolve_profile(
+    tool_config: &serde_json::Value,
+    profile_name: &str,
+    visited: &mut Vec<String>,
+) -> Result<serde_json::Value> {
+    if visited.iter().any(|name| name == profile_name) {
+        visited.push(profile_name.to_string());
+        return Err(anyhow!(
+            "Inheritance cycle detected in profiles: {}",
+            visited.join(" -> ")
+        ));
+    }
+    visited.push(profile_name.to_string());
+
+    let mut profile = tool_config
+        .get(profile_name)
+        .cloned()
+        .ok_or_else(|| anyhow!("Profile [{}] not found in config", profile_name))?;
+    let parent = match profile.as_object_mut() {
+        Some(map) => map.remove(INHERITS_KEY),
+        None => None,
+    };
+
+    match parent {
+        Some(serde_json::Value::String(parent_name)) => {
+            if tool_config.get(&parent_name).is_none() {
+                return Err(anyhow!(
+                    "Profile [{parent_name}] inherited by [{profile_name}] not found in config"
+                ));
+            }
+            let parent = resolve_profile(tool_config, &parent_name, visited)?;
+            Ok(merge_profiles(parent, profile))
+        }
+        Some(_) => Err(anyhow!(
+            "Field `{INHERITS_KEY}` of profile [{profile_name}] must be a string"
+        )),
+        None => Ok(profile),
+    }
+}
+
+/// Merges `child` into `parent`, nested tables are merged recursively
+fn merge_profiles(parent: serde_json::Value, child: serde_json::Value) -> serde_json::Value {
+    match (parent, child) {
+        (serde_json::Value::Object(mut parent), serde_json::Value::Object(child)) => {
+            for (key, value) in child {
+                let merged = match parent.remove(&key) {
+                    Some(parent_value) => merge_profiles(parent_value, value),
+                    None => value,
+                };
+                parent.insert(key, merged);
+            }
+            serde_json::Value::Object(parent)
+        }
+        (_, child) => child,
+    }
+}
+
 pub fn load_global_config<T: GlobalConfig + Default>(
     path: &Option<Utf8PathBuf>,
     profile: &Option<String>,
 ) -> Result<T> {
+    match load_raw_global_config_profile::<T>(path, profile)? {
+        Some(profile) => T::from_raw(resolve_env_variables(profile)?),
+        None => Ok(T::default()),
+    }
+}
+
+/// Loads the config like `load_global_config`, applying `overrides` to the raw profile
+/// before environmental variables are resolved, so that they apply to the overridden values too.
+/// A missing config file is treated as an empty profile
+pub fn load_global_config_with_overrides<T: GlobalConfig>(
+    path: &Option<Utf8PathBuf>,
+    profile: &Option<String>,
+    overrides: impl FnOnce(serde_json::Value) -> Result<serde_json::Value>,
+) -> Result<T> {
+    let profile = load_raw_global_config_profile::<T>(path, profile)?
+        .unwrap_or(serde_json::Value::Object(serde_json::Map::new()));
+    T::from_raw(resolve_env_variables(overrides(profile)?)?)
+}
+
+/// Loads the profile from snfoundry.toml config file without resolving environmental variables
+/// # Arguments
+/// * `path` - Path the config file is searched upwards from; current directory is used if not provided
+/// * `profile` - Name of the profile; `default` profile is used if not provided
+/// Returns `None` if there is no config file
+pub fn load_raw_global_config_profile<T: GlobalConfig>(
+    path: &Option<Utf8PathBuf>,
+    profile: &Option<String>,
+) -> Result<Option<serde_json::Value>> {
     let config_path = path
         .as_ref()
         .and_then(|p| search_config_upwards_relative_to(p).ok())
@@ -72,10 +162,9 @@ pub fn load_global_config<T: GlobalConfig + Default>(
             let raw_config_json = serde_json::to_value(raw_config_toml)
                 .context("Conversion from TOML value to JSON value should not fail.")?;
 
-            let profile = get_profile(raw_config_json, T::tool_name(), profile)?;
-            T::from_raw(resolve_env_variables(profile)?)
+            get_profile(raw_config_json, T::tool_name(), profile).map(Some)
         }
-        None => Ok(T::default()),
+        None => Ok(None),
     }
 }
 /// Loads config for a specific package from the `Scarb.toml` file
@@ -115,7 +204,10 @@ fn resolve_env_variables(config: serde_json::Value) -> Result<serde_json::Value>
                 .collect::<Result<Vec<serde_json::Value>>>()?;
             Ok(serde_json::Value::Array(val))
         }
-        serde_json::Value::String(val) if val.starts_with('$') => resolve_env_variable(&val),
+        // `${VARIABLE}` occurrences inside values are interpolated by the tools themselves
+        serde_json::Value::String(val) if val.starts_with('$') && !val.starts_with("${") => {
+            resolve_env_variable(&val)
+        }
         val => Ok(val),
     }
 }
@@ -276,6 +368,70 @@ mod tests {
         assert_eq!(config.url, String::from("http://127.0.0.1:5055/rpc"));
     }
 
+    #[test]
+    fn load_config_with_overrides() {
+        let tempdir = copy_config_to_tempdir("tests/data/stubtool_snfoundry.toml", None).unwrap();
+        let config = load_global_config_with_overrides::<StubConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &None,
+            |mut raw| {
+                raw["account"] = serde_json::Value::String(String::from("user2"));
+                Ok(raw)
+            },
+        )
+        .unwrap();
+        assert_eq!(config.account, String::from("user2"));
+        assert_eq!(config.url, String::from("http://127.0.0.1:5055/rpc"));
+    }
+
+    #[test]
+    fn load_config_inherited_profile() {
+        let tempdir = copy_config_to_tempdir("tests/data/stubtool_snfoundry.toml", None).unwrap();
+        let config = load_global_config::<StubConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &Some(String::from("inheriting-twice")),
+        )
+        .unwrap();
+        assert_eq!(config.account, String::from("user5"));
+        assert_eq!(config.url, String::from("http://127.0.0.1:5056/rpc"));
+
+        let config = load_global_config::<StubConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &Some(String::from("inheriting")),
+        )
+        .unwrap();
+        assert_eq!(config.account, String::from("user5"));
+        assert_eq!(config.url, String::from("http://127.0.0.1:5050/rpc"));
+    }
+
+    #[test]
+    fn load_config_inheritance_cycle() {
+        let tempdir = copy_config_to_tempdir("tests/data/stubtool_snfoundry.toml", None).unwrap();
+        let err = load_global_config::<StubConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &Some(String::from("cycle1")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Inheritance cycle detected in profiles: cycle1 -> cycle2 -> cycle1"
+        );
+    }
+
+    #[test]
+    fn load_config_inherited_profile_not_found() {
+        let tempdir = copy_config_to_tempdir("tests/data/stubtool_snfoundry.toml", None).unwrap();
+        let err = load_global_config::<StubConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &Some(String::from("missing-parent")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Profile [nonexistent] inherited by [missing-parent] not found in config"
+        );
+    }
+
     #[test]
     fn load_config_not_found() {
         let tempdir = tempdir().expect("Failed to create a temporary directory");
@@ -369,4 +525,16 @@ mod tests {
         assert_eq!(config.nested.list_example, vec![true, false]);
         assert_eq!(config.nested.url_nested, 321.312);
     }
+
+    #[test]
+    fn resolve_env_vars_skips_braced_variables() {
+        let tempdir = copy_config_to_tempdir("tests/data/stubtool_snfoundry.toml", None).unwrap();
+
+        let config = load_global_config::<StubConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &Some(String::from("with-braced-envs")),
+        )
+        .unwrap();
+        assert_eq!(config.url, String::from("${NODE_HOST_123132}/rpc"));
+    }
 }
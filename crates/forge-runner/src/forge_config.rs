@@ -3,6 +3,7 @@ use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::Contr
 use std::collections::HashMap;
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq)]
 pub struct ForgeConfig {
@@ -20,6 +21,8 @@ pub struct TestRunnerConfig {
     pub cache_dir: Utf8PathBuf,
     pub contracts_data: ContractsData,
     pub environment_variables: HashMap<String, String>,
+    /// Wall-clock limit of a single test case run, including all runs of a fuzz test
+    pub test_timeout: Option<Duration>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -17,9 +17,11 @@ use package_tests::with_config_resolved::{
 use profiler_api::run_profiler;
 use smol_str::SmolStr;
 use std::sync::Arc;
+use std::time::Duration;
 use test_case_summary::{AnyTestCaseSummary, Fuzzing};
 use tokio::sync::mpsc::{channel, Sender};
-use tokio::task::JoinHandle;
+use tokio::sync::Notify;
+use tokio::task::{AbortHandle, JoinHandle};
 use universal_sierra_compiler_api::AssembledProgramWithDebugInfo;
 
 pub mod build_trace_data;
@@ -108,7 +110,27 @@ pub fn run_for_test_case(
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
 ) -> JoinHandle<Result<AnyTestCaseSummary>> {
-    if args.is_empty() {
+    let name = case.name.clone();
+    let is_fuzz_test = !args.is_empty();
+    let test_timeout = forge_config.test_runner_config.test_timeout;
+    let started = Arc::new(Notify::new());
+
+    // Aborting the fuzzing task drops its channel, so fuzz test runs which did not start yet are skipped
+    let mut fuzzing_abort_handle = None;
+    let handle = if is_fuzz_test {
+        let fuzzing = run_with_fuzzing(
+            args,
+            case,
+            casm_program,
+            forge_config.test_runner_config.clone(),
+            maybe_versioned_program_path,
+            send,
+            started.clone(),
+        );
+        fuzzing_abort_handle = Some(fuzzing.abort_handle());
+        tokio::task::spawn(async move { Ok(AnyTestCaseSummary::Fuzzing(fuzzing.await??)) })
+    } else {
+        let started = started.clone();
         tokio::task::spawn(async move {
             let res = run_test(
                 case,
@@ -116,24 +138,55 @@ pub fn run_for_test_case(
                 forge_config.test_runner_config.clone(),
                 maybe_versioned_program_path,
                 send,
+                started,
             )
             .await??;
             Ok(AnyTestCaseSummary::Single(res))
         })
-    } else {
-        tokio::task::spawn(async move {
-            let res = run_with_fuzzing(
-                args,
-                case,
-                casm_program,
-                forge_config.test_runner_config.clone(),
-                maybe_versioned_program_path,
-                send,
-            )
-            .await??;
-            Ok(AnyTestCaseSummary::Fuzzing(res))
-        })
+    };
+
+    match test_timeout {
+        Some(timeout) => tokio::task::spawn(run_with_timeout(
+            handle,
+            fuzzing_abort_handle,
+            started,
+            name,
+            timeout,
+        )),
+        None => handle,
+    }
+}
+
+/// Awaits the test case run, giving up once it runs longer than `timeout`.
+/// The timeout is measured from the moment the test starts executing, not from when it is queued.
+///
+/// Test execution happens on a `spawn_blocking` thread, which can not be interrupted. Only the awaiting tasks
+/// are aborted, so no further fuzz test runs start, but the thread executing the timed out run keeps running
+/// until it finishes or the runtime is shut down
+async fn run_with_timeout(
+    mut handle: JoinHandle<Result<AnyTestCaseSummary>>,
+    fuzzing_abort_handle: Option<AbortHandle>,
+    started: Arc<Notify>,
+    name: String,
+    timeout: Duration,
+) -> Result<AnyTestCaseSummary> {
+    tokio::select! {
+        result = &mut handle => return result?,
+        () = started.notified() => {}
     }
+
+    if let Ok(result) = tokio::time::timeout(timeout, &mut handle).await {
+        return result?;
+    }
+    handle.abort();
+
+    Ok(match fuzzing_abort_handle {
+        Some(fuzzing_abort_handle) => {
+            fuzzing_abort_handle.abort();
+            AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { name, timeout })
+        }
+        None => AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { name, timeout }),
+    })
 }
 
 fn run_with_fuzzing(
@@ -143,6 +196,7 @@ fn run_with_fuzzing(
     test_runner_config: Arc<TestRunnerConfig>,
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
+    started: Arc<Notify>,
 ) -> JoinHandle<Result<TestCaseSummary<Fuzzing>>> {
     tokio::task::spawn(async move {
         if send.is_closed() {
@@ -185,6 +239,7 @@ fn run_with_fuzzing(
                 maybe_versioned_program_path.clone(),
                 send.clone(),
                 fuzzing_send.clone(),
+                started.clone(),
             ));
         }
 
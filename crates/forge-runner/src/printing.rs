@@ -92,6 +92,11 @@ where
 }
 
 fn result_message(any_test_result: &AnyTestCaseSummary) -> String {
+    if let AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { timeout, .. })
+    | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { timeout, .. }) = any_test_result
+    {
+        return format!("\n\nTest exceeded the timeout of {timeout:?} and was aborted");
+    }
     if let Some(msg) = any_test_result.msg() {
         if any_test_result.is_passed() {
             return format!("\n\nSuccess data:{msg}");
@@ -113,5 +118,8 @@ fn result_header(any_test_result: &AnyTestCaseSummary) -> String {
     if any_test_result.is_ignored() {
         return format!("[{}]", style("IGNORE").yellow());
     }
+    if any_test_result.is_timed_out() {
+        return format!("[{}]", style("TIMEOUT").red());
+    }
     unreachable!()
 }
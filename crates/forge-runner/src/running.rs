@@ -48,6 +48,7 @@ use cheatnet::state::{BlockInfoReader, CallTrace, CheatnetState, ExtendedStateRe
 use runtime::starknet::context::{build_context, set_max_steps};
 use runtime::{ExtendedRuntime, StarknetRuntime};
 use tokio::sync::mpsc::Sender;
+use tokio::sync::Notify;
 use tokio::task::JoinHandle;
 use universal_sierra_compiler_api::{
     AssembledCairoProgramWithSerde, AssembledProgramWithDebugInfo, CasmCodeOffset,
@@ -60,6 +61,7 @@ pub fn run_test(
     test_runner_config: Arc<TestRunnerConfig>,
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
+    started: Arc<Notify>,
 ) -> JoinHandle<Result<TestCaseSummary<Single>>> {
     tokio::task::spawn_blocking(move || {
         // Due to the inability of spawn_blocking to be abruptly cancelled,
@@ -68,6 +70,7 @@ pub fn run_test(
         if send.is_closed() {
             return Ok(TestCaseSummary::Skipped {});
         }
+        started.notify_one();
         let run_result = run_test_case(
             vec![],
             &case,
@@ -100,6 +103,7 @@ pub(crate) fn run_fuzz_test(
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
     fuzzing_send: Sender<()>,
+    started: Arc<Notify>,
 ) -> JoinHandle<Result<TestCaseSummary<Single>>> {
     tokio::task::spawn_blocking(move || {
         // Due to the inability of spawn_blocking to be abruptly cancelled,
@@ -108,6 +112,7 @@ pub(crate) fn run_fuzz_test(
         if send.is_closed() | fuzzing_send.is_closed() {
             return Ok(TestCaseSummary::Skipped {});
         }
+        started.notify_one();
 
         let run_result = run_test_case(
             args.clone(),
@@ -14,6 +14,7 @@ use shared::utils::build_readable_text;
 use std::cell::RefCell;
 use std::option::Option;
 use std::rc::Rc;
+use std::time::Duration;
 use trace_data::CallTrace as ProfilerCallTrace;
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -116,6 +117,13 @@ pub enum TestCaseSummary<T: TestType> {
         /// Name of the test case
         name: String,
     },
+    /// Test case run exceeded `--test-timeout` and its result was not awaited
+    TimedOut {
+        /// Name of the test case
+        name: String,
+        /// Timeout which was exceeded
+        timeout: Duration,
+    },
     /// Test case skipped due to exit first or execution interrupted, test result is ignored.
     Skipped {},
 }
@@ -133,7 +141,8 @@ impl<T: TestType> TestCaseSummary<T> {
         match self {
             TestCaseSummary::Failed { name, .. }
             | TestCaseSummary::Passed { name, .. }
-            | TestCaseSummary::Ignored { name, .. } => Some(name),
+            | TestCaseSummary::Ignored { name, .. }
+            | TestCaseSummary::TimedOut { name, .. } => Some(name),
             TestCaseSummary::Skipped { .. } => None,
         }
     }
@@ -200,6 +209,10 @@ impl TestCaseSummary<Fuzzing> {
                 },
             },
             TestCaseSummary::Ignored { name } => TestCaseSummary::Ignored { name: name.clone() },
+            TestCaseSummary::TimedOut { name, timeout } => TestCaseSummary::TimedOut {
+                name: name.clone(),
+                timeout,
+            },
             TestCaseSummary::Skipped {} => TestCaseSummary::Skipped {},
         }
     }
@@ -385,4 +398,13 @@ impl AnyTestCaseSummary {
                 | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Ignored { .. })
         )
     }
+
+    #[must_use]
+    pub fn is_timed_out(&self) -> bool {
+        matches!(
+            self,
+            AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { .. })
+                | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { .. })
+        )
+    }
 }
@@ -8,6 +8,7 @@ use rand::{thread_rng, RngCore};
 use std::env;
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::fn_params_excessive_bools)]
@@ -19,6 +20,7 @@ pub fn combine_configs(
     save_trace_data: bool,
     build_profile: bool,
     max_n_steps: Option<u32>,
+    test_timeout: Option<Duration>,
     contracts_data: ContractsData,
     cache_dir: Utf8PathBuf,
     versioned_programs_dir: Utf8PathBuf,
@@ -43,6 +45,7 @@ pub fn combine_configs(
             cache_dir,
             contracts_data,
             environment_variables: env::vars().collect(),
+            test_timeout,
         }),
         output_config: Arc::new(OutputConfig {
             detailed_resources: detailed_resources || forge_config_from_scarb.detailed_resources,
@@ -66,6 +69,7 @@ mod tests {
             false,
             false,
             None,
+            None,
             Default::default(),
             Default::default(),
             Default::default(),
@@ -79,6 +83,7 @@ mod tests {
             false,
             false,
             None,
+            None,
             Default::default(),
             Default::default(),
             Default::default(),
@@ -103,6 +108,7 @@ mod tests {
             false,
             false,
             None,
+            None,
             Default::default(),
             Default::default(),
             Default::default(),
@@ -120,6 +126,7 @@ mod tests {
                     cache_dir: Default::default(),
                     contracts_data: Default::default(),
                     environment_variables: config.test_runner_config.environment_variables.clone(),
+                    test_timeout: None,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: false,
@@ -151,6 +158,7 @@ mod tests {
             false,
             false,
             None,
+            None,
             Default::default(),
             Default::default(),
             Default::default(),
@@ -168,6 +176,7 @@ mod tests {
                     cache_dir: Default::default(),
                     contracts_data: Default::default(),
                     environment_variables: config.test_runner_config.environment_variables.clone(),
+                    test_timeout: None,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: true,
@@ -198,6 +207,7 @@ mod tests {
             true,
             true,
             Some(1_000_000),
+            Some(Duration::from_secs(30)),
             Default::default(),
             Default::default(),
             Default::default(),
@@ -216,6 +226,7 @@ mod tests {
                     cache_dir: Default::default(),
                     contracts_data: Default::default(),
                     environment_variables: config.test_runner_config.environment_variables.clone(),
+                    test_timeout: Some(Duration::from_secs(30)),
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: true,
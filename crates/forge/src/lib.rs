@@ -1,10 +1,10 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use forge_runner::CACHE_DIR;
 use run_tests::workspace::run_for_workspace;
 use scarb_api::{metadata::MetadataCommandExt, ScarbCommand};
 use scarb_ui::args::PackagesFilter;
-use std::{fs, num::NonZeroU32, thread::available_parallelism};
+use std::{fs, num::NonZeroU32, thread::available_parallelism, time::Duration};
 use tokio::runtime::Builder;
 use universal_sierra_compiler_api::UniversalSierraCompilerCommand;
 
@@ -104,7 +104,7 @@ pub struct TestArgs {
     fuzzer_seed: Option<u64>,
 
     /// Run only tests marked with `#[ignore]` attribute
-    #[arg(long = "ignored")]
+    #[arg(long = "ignored", visible_alias = "ignored-only")]
     only_ignored: bool,
     /// Run all tests regardless of `#[ignore]` attribute
     #[arg(long, conflicts_with = "only_ignored")]
@@ -133,6 +133,29 @@ pub struct TestArgs {
     /// Number of maximum steps during a single test. For fuzz tests this value is applied to each subtest separately.
     #[arg(long)]
     max_n_steps: Option<u32>,
+
+    /// Wall-clock limit of a single test, e.g. `30s`, `5m` or `1h`; seconds if no unit is given.
+    /// Tests running longer are reported as timed out and the remaining tests continue to run.
+    /// For fuzz tests the limit applies to all runs together
+    #[arg(long, value_parser = parse_test_timeout)]
+    test_timeout: Option<Duration>,
+}
+
+fn parse_test_timeout(value: &str) -> Result<Duration> {
+    let (amount, multiplier) = if let Some(amount) = value.strip_suffix('h') {
+        (amount, 3600)
+    } else if let Some(amount) = value.strip_suffix('m') {
+        (amount, 60)
+    } else {
+        (value.strip_suffix('s').unwrap_or(value), 1)
+    };
+    let amount: u64 = amount.parse().map_err(|_| {
+        anyhow!("Failed to parse test timeout = {value}, expected e.g. `30s`, `5m` or `1h`")
+    })?;
+    if amount == 0 {
+        bail!("Test timeout must be greater than 0");
+    }
+    Ok(Duration::from_secs(amount * multiplier))
 }
 
 pub enum ExitStatus {
@@ -174,7 +197,45 @@ pub fn main_execution() -> Result<ExitStatus> {
                 .enable_all()
                 .build()?;
 
-            rt.block_on(run_for_workspace(args))
+            let result = rt.block_on(run_for_workspace(args));
+            // Tests which exceeded `--test-timeout` can't be interrupted and may still be running,
+            // so the runtime does not wait for them
+            rt.shutdown_background();
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test_timeout_with_units() {
+        assert_eq!(parse_test_timeout("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_test_timeout("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_test_timeout("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_test_timeout("1h").unwrap(), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn parse_test_timeout_zero() {
+        let err = parse_test_timeout("0s").unwrap_err();
+
+        assert_eq!(err.to_string(), "Test timeout must be greater than 0");
+    }
+
+    #[test]
+    fn parse_test_timeout_invalid() {
+        for value in ["", "abc", "10x", "-5s", "1.5m"] {
+            let err = parse_test_timeout(value).unwrap_err();
+
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "Failed to parse test timeout = {value}, expected e.g. `30s`, `5m` or `1h`"
+                )
+            );
         }
     }
 }
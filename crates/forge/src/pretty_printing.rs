@@ -31,15 +31,26 @@ pub(crate) fn print_test_summary(summaries: &[TestTargetSummary], filtered: usiz
     let failed: usize = summaries.iter().map(TestTargetSummary::count_failed).sum();
     let skipped: usize = summaries.iter().map(TestTargetSummary::count_skipped).sum();
     let ignored: usize = summaries.iter().map(TestTargetSummary::count_ignored).sum();
+    let timed_out: usize = summaries
+        .iter()
+        .map(TestTargetSummary::count_timed_out)
+        .sum();
+    // Only shown when `--test-timeout` was hit, so the summary is unchanged otherwise
+    let timed_out = if timed_out > 0 {
+        format!(", {timed_out} timed out")
+    } else {
+        String::new()
+    };
 
     println!(
-        "{}: {} passed, {} failed, {} skipped, {} ignored, {} filtered out",
+        "{}: {} passed, {} failed, {} skipped, {} ignored, {} filtered out{}",
         style("Tests").bold(),
         passed,
         failed,
         skipped,
         ignored,
         filtered,
+        timed_out,
     );
 }
 
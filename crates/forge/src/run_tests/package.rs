@@ -68,6 +68,7 @@ impl RunForPackageArgs {
             args.save_trace_data,
             args.build_profile,
             args.max_n_steps,
+            args.test_timeout,
             contracts_data,
             cache_dir.clone(),
             versioned_programs_dir,
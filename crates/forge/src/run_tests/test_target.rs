@@ -89,7 +89,9 @@ pub async fn run_for_test_target(
         print_test_result(&result, forge_config.output_config.detailed_resources);
         maybe_save_execution_data(&result, forge_config.output_config.execution_data_to_save)?;
 
-        if result.is_failed() && forge_config.test_runner_config.exit_first {
+        if (result.is_failed() || result.is_timed_out())
+            && forge_config.test_runner_config.exit_first
+        {
             interrupted = true;
             rec.close();
         }
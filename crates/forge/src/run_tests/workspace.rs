@@ -91,8 +91,11 @@ fn extract_failed_tests(
         .filter(|test_case_summary| {
             matches!(
                 test_case_summary,
-                AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed { .. })
-                    | AnyTestCaseSummary::Single(TestCaseSummary::Failed { .. })
+                AnyTestCaseSummary::Fuzzing(
+                    TestCaseSummary::Failed { .. } | TestCaseSummary::TimedOut { .. }
+                ) | AnyTestCaseSummary::Single(
+                    TestCaseSummary::Failed { .. } | TestCaseSummary::TimedOut { .. }
+                )
             )
         })
 }
@@ -68,6 +68,7 @@ pub fn run_test_case(test: &TestCase) -> Vec<TestTargetSummary> {
                         .join(CACHE_DIR),
                     contracts_data: ContractsData::try_from(test.contracts().unwrap()).unwrap(),
                     environment_variables: test.env().clone(),
+                    test_timeout: None,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: false,
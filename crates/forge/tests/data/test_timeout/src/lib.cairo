@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn passing() {
+        assert(1 + 1 == 2, 'who knows?');
+    }
+
+    #[test]
+    // never finishes before the timeout
+    fn infinite_loop() {
+        let mut i: u64 = 1;
+
+        while i != 0 {
+            i = i + 1;
+        }
+    }
+}
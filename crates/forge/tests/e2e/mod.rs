@@ -13,6 +13,7 @@ mod fuzzing;
 mod io_operations;
 mod running;
 mod steps;
+mod test_timeout;
 mod trace_print;
 mod trace_resources;
 mod workspaces;
@@ -268,6 +268,22 @@ fn with_non_matching_filter() {
     );
 }
 
+#[test]
+fn with_ignored_only_flag() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).arg("--ignored-only").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [PASS] simple_package::tests::ignored_test [..]
+        [FAIL] tests::ext_function_test::ignored_test
+        Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 11 filtered out
+        "},
+    );
+}
+
 #[test]
 fn with_ignored_flag() {
     let temp = setup_package("simple_package");
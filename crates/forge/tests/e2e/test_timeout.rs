@@ -0,0 +1,37 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stdout_contains;
+
+#[test]
+fn reports_timed_out_test() {
+    let temp = setup_package("test_timeout");
+
+    // Steps limit is raised so that the loop can't fail before the timeout is exceeded
+    let output = test_runner(&temp)
+        .args(["--test-timeout", "1s", "--max-n-steps", "4294967295"])
+        .assert()
+        .code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc!(
+            r"
+                [..]Compiling[..]
+                [..]Finished[..]
+
+
+                Collected 2 test(s) from test_timeout package
+                Running 2 test(s) from src/
+                [PASS] test_timeout::tests::passing [..]
+                [TIMEOUT] test_timeout::tests::infinite_loop
+
+                Test exceeded the timeout of 1s and was aborted
+
+                Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out, 1 timed out
+
+                Failures:
+                    test_timeout::tests::infinite_loop
+            "
+        ),
+    );
+}
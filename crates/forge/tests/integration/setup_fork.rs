@@ -152,6 +152,7 @@ fn fork_aliased_decorator() {
                             .join(CACHE_DIR),
                         contracts_data: ContractsData::try_from(test.contracts().unwrap()).unwrap(),
                         environment_variables: test.env().clone(),
+                        test_timeout: None,
                     }),
                     output_config: Arc::new(OutputConfig {
                         detailed_resources: false,
@@ -25,6 +25,9 @@ struct StarknetContract {
     id: String,
     package_name: String,
     contract_name: String,
+    /// Fully-qualified path of the contract module, e.g. `package::module::Contract`
+    #[serde(default)]
+    module_path: Option<String>,
     artifacts: StarknetContractArtifactPaths,
 }
 
@@ -113,6 +116,38 @@ pub fn get_contracts_artifacts_and_source_sierra_paths(
     metadata: &Metadata,
     package: &PackageId,
     profile: Option<&str>,
+) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
+    load_package_contracts(metadata, package, profile, |contract| {
+        contract.contract_name.clone()
+    })
+}
+
+/// Get the map with `StarknetContractArtifacts` for the given package, keyed by fully-qualified paths
+/// of the contract modules, e.g. `package::module::Contract`, so contracts with the same name can be told apart.
+/// Contract names are used instead if Scarb does not provide the paths
+pub fn get_contracts_artifacts_by_module_path(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+) -> Result<HashMap<String, StarknetContractArtifacts>> {
+    let map = load_package_contracts(metadata, package, profile, |contract| {
+        contract
+            .module_path
+            .clone()
+            .unwrap_or_else(|| contract.contract_name.clone())
+    })?;
+
+    Ok(map
+        .into_iter()
+        .map(|(name, (artifacts, _))| (name, artifacts))
+        .collect())
+}
+
+fn load_package_contracts(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+    contract_key: fn(&StarknetContract) -> String,
 ) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
     let target_name = target_name_for_package(metadata, package)?;
     let target_dir = target_dir_for_workspace(metadata);
@@ -123,7 +158,9 @@ pub fn get_contracts_artifacts_and_source_sierra_paths(
     )?;
 
     let map = match maybe_contracts_path {
-        Some(contracts_path) => load_contracts_artifacts_and_source_sierra_paths(&contracts_path)?,
+        Some(contracts_path) => {
+            load_contracts_artifacts_and_source_sierra_paths(&contracts_path, contract_key)?
+        }
         None => HashMap::default(),
     };
 
@@ -132,6 +169,7 @@ pub fn get_contracts_artifacts_and_source_sierra_paths(
 
 fn load_contracts_artifacts_and_source_sierra_paths(
     contracts_path: &Utf8PathBuf,
+    contract_key: fn(&StarknetContract) -> String,
 ) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
     let base_path = contracts_path
         .parent()
@@ -140,7 +178,7 @@ fn load_contracts_artifacts_and_source_sierra_paths(
     let mut map = HashMap::new();
 
     for ref contract in artifacts.contracts {
-        let name = contract.contract_name.clone();
+        let name = contract_key(contract);
         let contract_artifacts =
             StarknetContractArtifacts::from_scarb_contract_artifact(contract, base_path)?;
 
@@ -509,6 +547,37 @@ mod tests {
         assert!(!contract.0.casm.is_empty());
     }
 
+    #[test]
+    fn get_contracts_by_module_path() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let package = metadata.packages.first().unwrap();
+        let contracts =
+            get_contracts_artifacts_by_module_path(&metadata, &package.id, None).unwrap();
+
+        let mut names: Vec<_> = contracts.keys().cloned().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "basic_package::ERC20".to_string(),
+                "basic_package::HelloStarknet".to_string()
+            ]
+        );
+    }
+
     #[test]
     fn get_name_for_package() {
         let temp = setup_package("basic_package");
@@ -1,2 +1,4 @@
 pub const EXPECTED_RPC_VERSION: &str = "0.7.0";
+/// RPC versions that are known to produce responses which cannot be deserialized
+pub const INCOMPATIBLE_RPC_VERSIONS: &str = "<0.6.0";
 pub const RPC_URL_VERSION: &str = "v0_7";
@@ -1,9 +1,9 @@
 use crate::consts::EXPECTED_RPC_VERSION;
 use crate::print::print_as_warning;
-use crate::rpc::{get_rpc_version, is_expected_version};
-use anyhow::{anyhow, Result};
-use starknet::providers::jsonrpc::HttpTransport;
-use starknet::providers::JsonRpcClient;
+use crate::rpc::{get_rpc_version, is_expected_version, is_incompatible_version};
+use anyhow::{anyhow, bail, Result};
+use semver::Version;
+use starknet::providers::Provider;
 use std::fmt::Display;
 
 pub mod command;
@@ -13,8 +13,8 @@ pub mod rpc;
 pub mod test_utils;
 pub mod utils;
 
-pub async fn verify_and_warn_if_incompatible_rpc_version(
-    client: &JsonRpcClient<HttpTransport>,
+pub async fn verify_and_warn_if_incompatible_rpc_version<P: Provider + Sync>(
+    client: &P,
     url: impl Display,
 ) -> Result<()> {
     let node_spec_version = get_rpc_version(client).await?;
@@ -26,3 +26,28 @@ pub async fn verify_and_warn_if_incompatible_rpc_version(
 
     Ok(())
 }
+
+/// Verifies RPC version of the node, or `forced_version` if provided, in which case the node is not queried.
+/// Fails for versions known to be incompatible and warns about other unexpected ones
+pub async fn verify_rpc_version<P: Provider + Sync>(
+    client: &P,
+    url: impl Display,
+    forced_version: Option<&Version>,
+) -> Result<Version> {
+    let node_spec_version = match forced_version {
+        Some(version) => version.clone(),
+        None => get_rpc_version(client).await?,
+    };
+    if is_incompatible_version(&node_spec_version) {
+        bail!(
+            "RPC node with the url {url} uses unsupported version {node_spec_version}. Expected version: {EXPECTED_RPC_VERSION}"
+        );
+    }
+    if !is_expected_version(&node_spec_version) {
+        print_as_warning(&anyhow!(
+            "RPC node with the url {url} uses incompatible version {node_spec_version}. Expected version: {EXPECTED_RPC_VERSION}"
+        ));
+    }
+
+    Ok(node_spec_version)
+}
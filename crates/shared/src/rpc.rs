@@ -1,4 +1,4 @@
-use crate::consts::EXPECTED_RPC_VERSION;
+use crate::consts::{EXPECTED_RPC_VERSION, INCOMPATIBLE_RPC_VERSIONS};
 use anyhow::{Context, Result};
 use semver::{Version, VersionReq};
 use starknet::providers::jsonrpc::HttpTransport;
@@ -7,11 +7,15 @@ use std::str::FromStr;
 use url::Url;
 
 pub fn create_rpc_client(url: &str) -> Result<JsonRpcClient<HttpTransport>> {
-    let parsed_url = Url::parse(url).with_context(|| format!("Failed to parse URL: {url}"))?;
-    let client = JsonRpcClient::new(HttpTransport::new(parsed_url));
+    let client = JsonRpcClient::new(create_http_transport(url)?);
     Ok(client)
 }
 
+pub fn create_http_transport(url: &str) -> Result<HttpTransport> {
+    let parsed_url = Url::parse(url).with_context(|| format!("Failed to parse URL: {url}"))?;
+    Ok(HttpTransport::new(parsed_url))
+}
+
 #[must_use]
 pub fn is_expected_version(version: &Version) -> bool {
     VersionReq::from_str(EXPECTED_RPC_VERSION)
@@ -19,7 +23,14 @@ pub fn is_expected_version(version: &Version) -> bool {
         .matches(version)
 }
 
-pub async fn get_rpc_version(client: &JsonRpcClient<HttpTransport>) -> Result<Version> {
+#[must_use]
+pub fn is_incompatible_version(version: &Version) -> bool {
+    VersionReq::from_str(INCOMPATIBLE_RPC_VERSIONS)
+        .expect("Failed to parse the incompatible RPC versions")
+        .matches(version)
+}
+
+pub async fn get_rpc_version<P: Provider + Sync>(client: &P) -> Result<Version> {
     client
         .spec_version()
         .await
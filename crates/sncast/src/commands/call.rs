@@ -0,0 +1,30 @@
+use crate::helpers::traced_transport::TracedTransport;
+use crate::response::errors::StarknetCommandError;
+use crate::response::structs::{CallResponse, Felt};
+use anyhow::Result;
+use starknet::core::types::{BlockId, FieldElement, FunctionCall};
+use starknet::providers::{JsonRpcClient, Provider};
+
+#[allow(clippy::ptr_arg)]
+pub async fn call(
+    contract_address: FieldElement,
+    entry_point_selector: FieldElement,
+    calldata: Vec<FieldElement>,
+    provider: &JsonRpcClient<TracedTransport>,
+    block_id: &BlockId,
+) -> Result<CallResponse, StarknetCommandError> {
+    let function_call = FunctionCall {
+        contract_address,
+        entry_point_selector,
+        calldata,
+    };
+    let res = provider
+        .call(function_call, block_id)
+        .await
+        .map(|v| v.into_iter().map(Felt).collect());
+
+    match res {
+        Ok(response) => Ok(CallResponse { response }),
+        Err(error) => Err(StarknetCommandError::ProviderError(error.into())),
+    }
+}
@@ -0,0 +1,446 @@
+use crate::helpers::constants::MAX_CONTRACT_CLASS_SIZE;
+use crate::helpers::signer::CastSigner;
+use crate::helpers::traced_transport::TracedTransport;
+use crate::response::errors::{
+    handle_starknet_command_error, SNCastProviderError, SNCastStarknetError, StarknetCommandError,
+};
+use crate::response::structs::{
+    DeclareMultipleResponse, DeclareResponse, DeclareStatus, DeclaredContractEntry, Felt,
+};
+use crate::{apply_optional, check_max_fee_ratio, handle_wait_for_tx, ErrorData, WaitForTx};
+use anyhow::{anyhow, Context, Result};
+use scarb_api::StarknetContractArtifacts;
+use starknet::accounts::AccountError::Provider;
+use starknet::accounts::{ConnectedAccount, Declaration};
+use starknet::core::types::{BlockId, BlockTag, FieldElement, FlattenedSierraClass, StarknetError};
+use starknet::providers::{Provider, ProviderError};
+use starknet::{
+    accounts::{Account, SingleOwnerAccount},
+    core::types::contract::{CompiledClass, SierraClass},
+    providers::jsonrpc::JsonRpcClient,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn declare(
+    contract_name: &str,
+    max_fee: Option<FieldElement>,
+    max_fee_abort_ratio: Option<u64>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    nonce: Option<FieldElement>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    expected_class_hash: Option<FieldElement>,
+    wait_config: WaitForTx,
+) -> Result<DeclareResponse, StarknetCommandError> {
+    let (flattened_class, casm_class_hash) = load_contract_classes(contract_name, artifacts)?;
+
+    if let Some(expected_class_hash) = expected_class_hash {
+        let class_hash = flattened_class.class_hash();
+        if class_hash != expected_class_hash {
+            return Err(anyhow!(
+                "Class hash of the built artifact = {class_hash:#x} does not match the expected class hash = {expected_class_hash:#x}"
+            )
+            .into());
+        }
+    }
+
+    send_declaration(
+        flattened_class,
+        casm_class_hash,
+        max_fee,
+        max_fee_abort_ratio,
+        account,
+        nonce,
+        wait_config,
+    )
+    .await
+}
+
+/// Declares every contract from `contract_names` using artifacts of a single package build.
+/// Contracts whose classes are already declared are skipped. If a declaration fails,
+/// the remaining contracts are not attempted and the entries collected so far are returned
+pub async fn declare_multiple(
+    contract_names: &[String],
+    max_fee: Option<FieldElement>,
+    max_fee_abort_ratio: Option<u64>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    nonce: Option<FieldElement>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    wait_config: WaitForTx,
+) -> Result<DeclareMultipleResponse, StarknetCommandError> {
+    // Nonce is tracked locally, so consecutive declarations do not depend on the node
+    // already seeing the previous ones
+    let mut nonce = match nonce {
+        Some(nonce) => nonce,
+        None => account
+            .get_nonce()
+            .await
+            .map_err(|error| StarknetCommandError::ProviderError(error.into()))?,
+    };
+
+    let mut contracts = vec![];
+    for contract_name in contract_names {
+        let mut class_hash = None;
+        let result = async {
+            let (flattened_class, casm_class_hash) =
+                load_contract_classes(contract_name, artifacts)?;
+            class_hash = Some(flattened_class.class_hash());
+
+            if is_class_declared(account.provider(), flattened_class.class_hash()).await? {
+                return Ok::<_, StarknetCommandError>(None);
+            }
+            send_declaration(
+                flattened_class,
+                casm_class_hash,
+                max_fee,
+                max_fee_abort_ratio,
+                account,
+                Some(nonce),
+                wait_config,
+            )
+            .await
+            .map(Some)
+        }
+        .await;
+
+        let entry = match result {
+            Ok(Some(response)) => {
+                nonce += FieldElement::ONE;
+                DeclaredContractEntry {
+                    contract_name: contract_name.clone(),
+                    status: DeclareStatus::Declared,
+                    class_hash: Some(response.class_hash),
+                    transaction_hash: Some(response.transaction_hash),
+                    error: None,
+                }
+            }
+            Ok(None) => DeclaredContractEntry {
+                contract_name: contract_name.clone(),
+                status: DeclareStatus::AlreadyDeclared,
+                class_hash: class_hash.map(Felt),
+                transaction_hash: None,
+                error: None,
+            },
+            // Following declarations would use a nonce the failed one did not consume,
+            // so the remaining contracts are not attempted
+            Err(error) => {
+                contracts.push(DeclaredContractEntry {
+                    contract_name: contract_name.clone(),
+                    status: DeclareStatus::Failed,
+                    class_hash: class_hash.map(Felt),
+                    transaction_hash: None,
+                    error: Some(format!("{:#}", handle_starknet_command_error(error))),
+                });
+                break;
+            }
+        };
+        contracts.push(entry);
+    }
+
+    Ok(DeclareMultipleResponse { contracts })
+}
+
+/// Computes the class hash of the contract from its artifacts, without declaring it
+pub fn get_class_hash(
+    contract_name: &str,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+) -> Result<FieldElement, StarknetCommandError> {
+    let (flattened_class, _) = load_contract_classes(contract_name, artifacts)?;
+    Ok(flattened_class.class_hash())
+}
+
+/// Sizes of the contract class: Sierra and CASM classes in bytes of their JSON representation,
+/// which providers limit, and the number of felts of the CASM bytecode
+pub struct ContractSizes {
+    pub sierra_size: usize,
+    pub casm_size: usize,
+    pub bytecode_length: usize,
+}
+
+/// Computes sizes of the contract class from its artifacts, without declaring it
+pub fn get_contract_sizes(
+    contract_name: &str,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+) -> Result<ContractSizes, StarknetCommandError> {
+    let (flattened_class, _) = load_contract_classes(contract_name, artifacts)?;
+    let contract_artifacts = find_contract_artifacts(contract_name, artifacts)?;
+
+    let casm: serde_json::Value =
+        serde_json::from_str(&contract_artifacts.casm).context("Failed to parse casm artifact")?;
+    let bytecode_length = casm["bytecode"]
+        .as_array()
+        .context("Failed to read bytecode of casm artifact")?
+        .len();
+
+    Ok(ContractSizes {
+        sierra_size: class_size(&flattened_class)?,
+        casm_size: serde_json::to_vec(&casm)
+            .context("Failed to serialize casm artifact")?
+            .len(),
+        bytecode_length,
+    })
+}
+
+/// Size of the class as sent to the network in bytes
+fn class_size(flattened_class: &FlattenedSierraClass) -> Result<usize> {
+    Ok(serde_json::to_vec(flattened_class)
+        .context("Failed to serialize contract class")?
+        .len())
+}
+
+/// Names the contracts of the package are referred to by: the contract name if it is unique in the package,
+/// the fully-qualified module path otherwise
+#[must_use]
+pub fn contract_names(artifacts: &HashMap<String, StarknetContractArtifacts>) -> Vec<String> {
+    let mut names: Vec<String> = artifacts
+        .keys()
+        .map(|path| {
+            let name = short_contract_name(path);
+            let is_ambiguous = artifacts
+                .keys()
+                .filter(|other| short_contract_name(other) == name)
+                .count()
+                > 1;
+            if is_ambiguous {
+                path.clone()
+            } else {
+                name.to_string()
+            }
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn short_contract_name(module_path: &str) -> &str {
+    module_path
+        .rsplit_once("::")
+        .map_or(module_path, |(_, name)| name)
+}
+
+/// Finds artifacts of the contract, which are keyed by fully-qualified module paths.
+/// A name with `::` has to match the path exactly, otherwise the contract name has to be unique in the package
+fn find_contract_artifacts<'a>(
+    contract_name: &str,
+    artifacts: &'a HashMap<String, StarknetContractArtifacts>,
+) -> Result<&'a StarknetContractArtifacts, StarknetCommandError> {
+    let not_found = || {
+        StarknetCommandError::ContractArtifactsNotFound(ErrorData::new(contract_name.to_string()))
+    };
+    if contract_name.contains("::") {
+        return artifacts.get(contract_name).ok_or_else(not_found);
+    }
+
+    let mut candidates: Vec<&str> = artifacts
+        .keys()
+        .map(String::as_str)
+        .filter(|path| short_contract_name(path) == contract_name)
+        .collect();
+    candidates.sort_unstable();
+    match candidates.as_slice() {
+        [path] => Ok(&artifacts[*path]),
+        [] => Err(not_found()),
+        _ => Err(anyhow!(
+            "Contract name = {contract_name} is ambiguous, use one of the fully-qualified names: {}",
+            candidates.join(", ")
+        )
+        .into()),
+    }
+}
+
+fn load_contract_classes(
+    contract_name: &str,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+) -> Result<(FlattenedSierraClass, FieldElement), StarknetCommandError> {
+    let contract_artifacts = find_contract_artifacts(contract_name, artifacts)?;
+
+    let contract_definition: SierraClass = serde_json::from_str(&contract_artifacts.sierra)
+        .context("Failed to parse sierra artifact")?;
+    let casm_contract_definition: CompiledClass =
+        serde_json::from_str(&contract_artifacts.casm).context("Failed to parse casm artifact")?;
+
+    let casm_class_hash = casm_contract_definition
+        .class_hash()
+        .map_err(anyhow::Error::from)?;
+
+    let flattened_class = contract_definition.flatten().map_err(anyhow::Error::from)?;
+
+    Ok((flattened_class, casm_class_hash))
+}
+
+async fn is_class_declared(
+    provider: &JsonRpcClient<TracedTransport>,
+    class_hash: FieldElement,
+) -> Result<bool, StarknetCommandError> {
+    match provider
+        .get_class(BlockId::Tag(BlockTag::Pending), class_hash)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => Ok(false),
+        Err(error) => Err(StarknetCommandError::ProviderError(error.into())),
+    }
+}
+
+async fn send_declaration(
+    flattened_class: FlattenedSierraClass,
+    casm_class_hash: FieldElement,
+    max_fee: Option<FieldElement>,
+    max_fee_abort_ratio: Option<u64>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    nonce: Option<FieldElement>,
+    wait_config: WaitForTx,
+) -> Result<DeclareResponse, StarknetCommandError> {
+    let class_size = class_size(&flattened_class)?;
+    let declaration = account.declare(Arc::new(flattened_class), casm_class_hash);
+
+    let declaration = apply_optional(declaration, max_fee, Declaration::max_fee);
+    let declaration = apply_optional(declaration, nonce, Declaration::nonce);
+
+    if let (Some(max_fee), Some(ratio)) = (max_fee, max_fee_abort_ratio) {
+        let estimated_fee = match declaration.estimate_fee().await {
+            Ok(estimate) => estimate.overall_fee,
+            Err(Provider(error)) => return Err(handle_declare_provider_error(error, class_size)),
+            _ => return Err(anyhow!("Unknown RPC error").into()),
+        };
+        check_max_fee_ratio(max_fee, estimated_fee, ratio)?;
+    }
+
+    let declared = declaration.send().await;
+    match declared {
+        Ok(result) => handle_wait_for_tx(
+            account.provider(),
+            result.transaction_hash,
+            DeclareResponse {
+                class_hash: Felt(result.class_hash),
+                transaction_hash: Felt(result.transaction_hash),
+            },
+            wait_config,
+        )
+        .await
+        .map_err(StarknetCommandError::from),
+        Err(Provider(error)) => Err(handle_declare_provider_error(error, class_size)),
+        _ => Err(anyhow!("Unknown RPC error").into()),
+    }
+}
+
+/// Replaces the bare "class size is too large" provider error with one stating the size of the class
+fn handle_declare_provider_error(error: ProviderError, class_size: usize) -> StarknetCommandError {
+    match SNCastProviderError::from(error) {
+        SNCastProviderError::StarknetError(SNCastStarknetError::ContractClassSizeIsTooLarge) => {
+            class_size_too_large_error(class_size).into()
+        }
+        error => StarknetCommandError::ProviderError(error),
+    }
+}
+
+fn class_size_too_large_error(class_size: usize) -> anyhow::Error {
+    anyhow!(
+        "Contract class size = {class_size} bytes is too large to be declared, the limit enforced by Starknet is {MAX_CONTRACT_CLASS_SIZE} bytes. \
+        Reduce the size of the contract, e.g. by splitting it into several contracts or moving logic to library calls. \
+        Some providers enforce lower limits than the network, in such case try a different RPC endpoint with `--url`"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contract_names, find_contract_artifacts, handle_declare_provider_error};
+    use crate::response::errors::StarknetCommandError;
+    use scarb_api::StarknetContractArtifacts;
+    use starknet::core::types::StarknetError;
+    use starknet::providers::ProviderError;
+    use std::collections::HashMap;
+
+    fn artifacts(names: &[&str]) -> HashMap<String, StarknetContractArtifacts> {
+        names
+            .iter()
+            .map(|name| {
+                (
+                    (*name).to_string(),
+                    StarknetContractArtifacts {
+                        sierra: (*name).to_string(),
+                        casm: String::new(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_find_by_name_or_qualified_path() {
+        let artifacts = artifacts(&[
+            "package::first::Token",
+            "package::second::Token",
+            "package::vault::Vault",
+        ]);
+
+        let found = find_contract_artifacts("package::second::Token", &artifacts).unwrap();
+        assert_eq!(found.sierra, "package::second::Token");
+
+        let found = find_contract_artifacts("Vault", &artifacts).unwrap();
+        assert_eq!(found.sierra, "package::vault::Vault");
+
+        let found = find_contract_artifacts("package::vault::Vault", &artifacts).unwrap();
+        assert_eq!(found.sierra, "package::vault::Vault");
+    }
+
+    #[test]
+    fn test_qualified_path_with_wrong_module() {
+        let artifacts = artifacts(&["package::vault::Vault"]);
+
+        for name in ["package::other::Vault", "vault::Vault"] {
+            let err = find_contract_artifacts(name, &artifacts).unwrap_err();
+            assert!(matches!(
+                err,
+                StarknetCommandError::ContractArtifactsNotFound(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_contract_names() {
+        let artifacts = artifacts(&[
+            "package::first::Token",
+            "package::second::Token",
+            "package::vault::Vault",
+        ]);
+
+        assert_eq!(
+            contract_names(&artifacts),
+            vec![
+                "Vault".to_string(),
+                "package::first::Token".to_string(),
+                "package::second::Token".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_name() {
+        let artifacts = artifacts(&["package::first::Token", "package::second::Token"]);
+
+        let err = find_contract_artifacts("Token", &artifacts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Contract name = Token is ambiguous, use one of the fully-qualified names: package::first::Token, package::second::Token"
+        );
+    }
+
+    #[test]
+    fn test_class_size_too_large_error() {
+        let err = handle_declare_provider_error(
+            ProviderError::StarknetError(StarknetError::ContractClassSizeIsTooLarge),
+            5_000_000,
+        );
+        assert!(err.to_string().starts_with(
+            "Contract class size = 5000000 bytes is too large to be declared, the limit enforced by Starknet is 4089446 bytes"
+        ));
+
+        let err = handle_declare_provider_error(
+            ProviderError::StarknetError(StarknetError::ClassAlreadyDeclared),
+            5_000_000,
+        );
+        assert!(matches!(err, StarknetCommandError::ProviderError(_)));
+    }
+}
@@ -0,0 +1,189 @@
+use crate::helpers::paymaster::{execute_sponsored, Paymaster};
+use crate::helpers::signer::CastSigner;
+use crate::helpers::traced_transport::TracedTransport;
+use crate::response::errors::StarknetCommandError;
+use crate::response::structs::{DeployResponse, Felt};
+use crate::{
+    check_max_fee_ratio, extract_or_generate_salt, get_class_hash_by_address, handle_wait_for_tx,
+    udc_uniqueness, WaitForTx,
+};
+use anyhow::{anyhow, bail, Result};
+use starknet::accounts::AccountError::Provider;
+use starknet::accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
+use starknet::contract::ContractFactory;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{get_selector_from_name, get_udc_deployed_address};
+use starknet::providers::JsonRpcClient;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn deploy(
+    class_hash: FieldElement,
+    constructor_calldata: Vec<FieldElement>,
+    salt: Option<FieldElement>,
+    unique: bool,
+    udc_address: FieldElement,
+    max_fee: Option<FieldElement>,
+    max_fee_abort_ratio: Option<u64>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    nonce: Option<FieldElement>,
+    wait_config: WaitForTx,
+) -> Result<DeployResponse, StarknetCommandError> {
+    let salt = extract_or_generate_salt(salt);
+    let factory = ContractFactory::new_with_udc(class_hash, account, udc_address);
+    let execution = factory.deploy(constructor_calldata.clone(), salt, unique);
+
+    // TODO(#1396): use apply_optional here when `Deployment` in starknet-rs is public
+    //  otherwise we cannot pass the necessary reference to a function
+    let execution = if let Some(max_fee) = max_fee {
+        execution.max_fee(max_fee)
+    } else {
+        execution
+    };
+
+    let execution = if let Some(nonce) = nonce {
+        execution.nonce(nonce)
+    } else {
+        execution
+    };
+
+    if let (Some(max_fee), Some(ratio)) = (max_fee, max_fee_abort_ratio) {
+        let estimated_fee = match execution.estimate_fee().await {
+            Ok(estimate) => estimate.overall_fee,
+            Err(Provider(error)) => return Err(StarknetCommandError::ProviderError(error.into())),
+            _ => return Err(anyhow!("Unknown RPC error").into()),
+        };
+        check_max_fee_ratio(max_fee, estimated_fee, ratio)?;
+    }
+
+    let result = execution.send().await;
+    match result {
+        Ok(result) => handle_wait_for_tx(
+            account.provider(),
+            result.transaction_hash,
+            DeployResponse {
+                contract_address: Felt(get_udc_deployed_address(
+                    salt,
+                    class_hash,
+                    &udc_uniqueness(unique, account.address(), udc_address),
+                    &constructor_calldata,
+                )),
+                transaction_hash: Felt(result.transaction_hash),
+            },
+            wait_config,
+        )
+        .await
+        .map_err(StarknetCommandError::from),
+        Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
+        _ => Err(anyhow!("Unknown RPC error").into()),
+    }
+}
+
+/// Deploys the contract through the paymaster, which pays the fee of the transaction
+#[allow(clippy::too_many_arguments)]
+pub async fn deploy_sponsored(
+    class_hash: FieldElement,
+    constructor_calldata: Vec<FieldElement>,
+    salt: Option<FieldElement>,
+    unique: bool,
+    udc_address: FieldElement,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    paymaster: &Paymaster,
+    signer: &CastSigner,
+    wait_config: WaitForTx,
+) -> Result<DeployResponse, StarknetCommandError> {
+    let salt = extract_or_generate_salt(salt);
+    let contract_address = get_udc_deployed_address(
+        salt,
+        class_hash,
+        &udc_uniqueness(unique, account.address(), udc_address),
+        &constructor_calldata,
+    );
+    let mut calldata = vec![
+        class_hash,
+        salt,
+        FieldElement::from(u8::from(unique)),
+        FieldElement::from(constructor_calldata.len()),
+    ];
+    calldata.extend(constructor_calldata);
+    let call = Call {
+        to: udc_address,
+        selector: get_selector_from_name("deployContract").map_err(anyhow::Error::from)?,
+        calldata,
+    };
+
+    let transaction_hash = execute_sponsored(
+        paymaster,
+        vec![call],
+        account.address(),
+        account.chain_id(),
+        signer,
+    )
+    .await?;
+
+    handle_wait_for_tx(
+        account.provider(),
+        transaction_hash,
+        DeployResponse {
+            contract_address: Felt(contract_address),
+            transaction_hash: Felt(transaction_hash),
+        },
+        wait_config,
+    )
+    .await
+    .map_err(StarknetCommandError::from)
+}
+
+/// Fails if a contract is already deployed at the address the contract would be deployed to,
+/// so a transaction which would revert is not sent
+pub async fn check_deployment_address_free(
+    class_hash: FieldElement,
+    constructor_calldata: &[FieldElement],
+    salt: FieldElement,
+    unique: bool,
+    udc_address: FieldElement,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+) -> Result<()> {
+    let contract_address = get_udc_deployed_address(
+        salt,
+        class_hash,
+        &udc_uniqueness(unique, account.address(), udc_address),
+        constructor_calldata,
+    );
+
+    match get_class_hash_by_address(account.provider(), contract_address).await? {
+        Some(deployed_class_hash) => bail!(
+            "Contract of class = {deployed_class_hash:#x} is already deployed at address = {contract_address:#x}. Use a different `--salt` or pass `--allow-redeploy` to send the transaction anyway"
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Returns the first salt, counting up from `start_salt`, for which no contract is deployed
+/// at the address the contract would be deployed to. At most `max_attempts` salts are checked
+pub async fn find_free_salt(
+    class_hash: FieldElement,
+    constructor_calldata: &[FieldElement],
+    start_salt: FieldElement,
+    max_attempts: u64,
+    unique: bool,
+    udc_address: FieldElement,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+) -> Result<FieldElement> {
+    let uniqueness = udc_uniqueness(unique, account.address(), udc_address);
+    let mut salt = start_salt;
+    for _ in 0..max_attempts {
+        let contract_address =
+            get_udc_deployed_address(salt, class_hash, &uniqueness, constructor_calldata);
+        if get_class_hash_by_address(account.provider(), contract_address)
+            .await?
+            .is_none()
+        {
+            return Ok(salt);
+        }
+        salt += FieldElement::ONE;
+    }
+
+    bail!(
+        "Failed to find a salt with a free deployment address, checked {max_attempts} salts starting from {start_salt:#x}. Pass a different `--auto-salt-start`"
+    )
+}
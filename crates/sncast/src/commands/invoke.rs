@@ -0,0 +1,229 @@
+use crate::helpers::paymaster::{execute_sponsored, Paymaster};
+use crate::helpers::signer::CastSigner;
+use crate::helpers::traced_transport::TracedTransport;
+use crate::response::errors::StarknetCommandError;
+use crate::response::structs::{Felt, InvokeResponse};
+use crate::{
+    apply_optional, check_max_fee_ratio, get_class_hash_by_address, get_token_balance,
+    handle_rpc_error, handle_wait_for_tx, WaitForTx,
+};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use clap::ValueEnum;
+use primitive_types::U256;
+use starknet::accounts::AccountError::Provider;
+use starknet::accounts::{Account, Call, ConnectedAccount, Execution, SingleOwnerAccount};
+use starknet::core::types::{BlockId, BlockTag, FieldElement, StarknetError};
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::{JsonRpcClient, Provider, ProviderError};
+
+/// ERC-20 call moving the `--value` of an invoke
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValueCall {
+    /// `approve(spender, amount)` letting the invoked contract pull the tokens
+    #[default]
+    Approve,
+    /// `transfer(recipient, amount)` sending the tokens to the invoked contract upfront
+    Transfer,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn invoke(
+    contract_address: FieldElement,
+    function_selector: FieldElement,
+    calldata: Vec<FieldElement>,
+    max_fee: Option<FieldElement>,
+    max_fee_abort_ratio: Option<u64>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    nonce: Option<FieldElement>,
+    wait_config: WaitForTx,
+) -> Result<InvokeResponse, StarknetCommandError> {
+    let call = Call {
+        to: contract_address,
+        selector: function_selector,
+        calldata,
+    };
+
+    execute_calls(
+        account,
+        vec![call],
+        max_fee,
+        max_fee_abort_ratio,
+        nonce,
+        wait_config,
+    )
+    .await
+}
+
+pub async fn execute_calls(
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    calls: Vec<Call>,
+    max_fee: Option<FieldElement>,
+    max_fee_abort_ratio: Option<u64>,
+    nonce: Option<FieldElement>,
+    wait_config: WaitForTx,
+) -> Result<InvokeResponse, StarknetCommandError> {
+    let execution_calls = account.execute(calls);
+
+    let execution = apply_optional(execution_calls, max_fee, Execution::max_fee);
+    let execution = apply_optional(execution, nonce, Execution::nonce);
+
+    if let (Some(max_fee), Some(ratio)) = (max_fee, max_fee_abort_ratio) {
+        let estimated_fee = match execution.estimate_fee().await {
+            Ok(estimate) => estimate.overall_fee,
+            Err(Provider(error)) => return Err(StarknetCommandError::ProviderError(error.into())),
+            _ => return Err(anyhow!("Unknown RPC error").into()),
+        };
+        check_max_fee_ratio(max_fee, estimated_fee, ratio)?;
+    }
+
+    match execution.send().await {
+        Ok(result) => handle_wait_for_tx(
+            account.provider(),
+            result.transaction_hash,
+            InvokeResponse {
+                transaction_hash: Felt(result.transaction_hash),
+            },
+            wait_config,
+        )
+        .await
+        .map_err(StarknetCommandError::from),
+        Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
+        _ => Err(anyhow!("Unknown RPC error").into()),
+    }
+}
+
+/// Executes the calls through the paymaster, which pays the fee of the transaction
+pub async fn execute_calls_sponsored(
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    calls: Vec<Call>,
+    paymaster: &Paymaster,
+    signer: &CastSigner,
+    wait_config: WaitForTx,
+) -> Result<InvokeResponse, StarknetCommandError> {
+    let transaction_hash = execute_sponsored(
+        paymaster,
+        calls,
+        account.address(),
+        account.chain_id(),
+        signer,
+    )
+    .await?;
+
+    handle_wait_for_tx(
+        account.provider(),
+        transaction_hash,
+        InvokeResponse {
+            transaction_hash: Felt(transaction_hash),
+        },
+        wait_config,
+    )
+    .await
+    .map_err(StarknetCommandError::from)
+}
+
+/// Builds the ERC-20 call, prepended to the invoke, which moves `amount` of `token` to `contract_address`.
+/// The amount is passed as `u256`, i.e. its low and high 128 bits
+pub fn value_call(
+    kind: ValueCall,
+    token: FieldElement,
+    contract_address: FieldElement,
+    amount: FieldElement,
+) -> Result<Call> {
+    let function = match kind {
+        ValueCall::Approve => "approve",
+        ValueCall::Transfer => "transfer",
+    };
+    let amount = U256::from_big_endian(&amount.to_bytes_be());
+    Ok(Call {
+        to: token,
+        selector: get_selector_from_name(function)?,
+        calldata: vec![
+            contract_address,
+            FieldElement::from(amount.low_u128()),
+            FieldElement::from((amount >> 128).low_u128()),
+        ],
+    })
+}
+
+/// Checks that the `--value` is non-zero and the account holds enough of `token` to pay it
+pub async fn validate_value(
+    provider: &JsonRpcClient<TracedTransport>,
+    token: FieldElement,
+    account_address: FieldElement,
+    amount: FieldElement,
+) -> Result<()> {
+    ensure!(
+        amount != FieldElement::ZERO,
+        "`--value` must be greater than 0"
+    );
+    let balance = get_token_balance(provider, token, account_address)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to check balance of token = {token:#x}, make sure it is an ERC-20 contract"
+            )
+        })?;
+    let amount = U256::from_big_endian(&amount.to_bytes_be());
+    ensure!(
+        balance >= amount,
+        "Account balance = {balance} of token = {token:#x} is lower than `--value` = {amount}"
+    );
+    Ok(())
+}
+
+/// Checks that the class passed to `--replace-class` is declared and differs from the current class of the contract,
+/// which is returned
+pub async fn validate_replace_class(
+    provider: &JsonRpcClient<TracedTransport>,
+    contract_address: FieldElement,
+    new_class_hash: FieldElement,
+) -> Result<FieldElement> {
+    match provider
+        .get_class(BlockId::Tag(BlockTag::Pending), new_class_hash)
+        .await
+    {
+        Ok(_) => {}
+        Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => bail!(
+            "Class with hash = {new_class_hash:#x} is not declared, declare it before passing it to `--replace-class`"
+        ),
+        Err(error) => return Err(handle_rpc_error(error)),
+    }
+
+    let old_class_hash = get_class_hash_by_address(provider, contract_address)
+        .await?
+        .with_context(|| format!("There is no contract at the address {contract_address:#x}"))?;
+    ensure!(
+        old_class_hash != new_class_hash,
+        "Contract at address {contract_address:#x} already has class = {new_class_hash:#x}"
+    );
+    Ok(old_class_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{value_call, ValueCall};
+    use starknet::core::types::FieldElement;
+    use starknet::core::utils::get_selector_from_name;
+
+    #[test]
+    fn test_value_call() {
+        let token = FieldElement::from(0x10_u32);
+        let contract = FieldElement::from(0x20_u32);
+        let amount = FieldElement::from_hex_be("0x300000000000000000000000000000005").unwrap();
+
+        let call = value_call(ValueCall::Approve, token, contract, amount).unwrap();
+        assert_eq!(call.to, token);
+        assert_eq!(call.selector, get_selector_from_name("approve").unwrap());
+        assert_eq!(
+            call.calldata,
+            vec![
+                contract,
+                FieldElement::from(5_u32),
+                FieldElement::from(3_u32)
+            ]
+        );
+
+        let call = value_call(ValueCall::Transfer, token, contract, amount).unwrap();
+        assert_eq!(call.selector, get_selector_from_name("transfer").unwrap());
+    }
+}
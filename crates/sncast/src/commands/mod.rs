@@ -0,0 +1,4 @@
+pub mod call;
+pub mod declare;
+pub mod deploy;
+pub mod invoke;
@@ -0,0 +1,751 @@
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use camino::Utf8PathBuf;
+use primitive_types::U256;
+use serde_json::{Map, Value};
+use starknet::core::types::contract::{
+    AbiEntry, AbiEvent, AbiFunction, AbiNamedMember, TypedAbiEvent,
+};
+use starknet::core::types::FieldElement;
+use std::fs;
+use std::str::FromStr;
+
+/// Loads contract ABI from a file. Accepts either a plain ABI array
+/// or a contract class JSON with an `abi` field
+pub fn load_abi_from_file(path: &Utf8PathBuf) -> Result<Vec<AbiEntry>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read ABI file = {path}"))?;
+    let value: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse ABI file = {path}"))?;
+
+    let abi = match value {
+        Value::Object(mut contract_class) if contract_class.contains_key("abi") => contract_class
+            .remove("abi")
+            .expect("Presence of `abi` key was checked"),
+        value => value,
+    };
+    let abi = match abi {
+        Value::String(abi) => serde_json::from_str(&abi)
+            .with_context(|| format!("Failed to parse ABI from file = {path}"))?,
+        abi => abi,
+    };
+
+    serde_json::from_value(abi).with_context(|| format!("Failed to parse ABI from file = {path}"))
+}
+
+fn abi_items(abi: &[AbiEntry]) -> Vec<&AbiEntry> {
+    abi.iter()
+        .flat_map(|entry| match entry {
+            AbiEntry::Interface(interface) => interface.items.iter().collect(),
+            entry => vec![entry],
+        })
+        .collect()
+}
+
+pub fn find_function<'a>(abi: &'a [AbiEntry], name: &str) -> Result<&'a AbiFunction> {
+    abi_items(abi)
+        .into_iter()
+        .find_map(|entry| match entry {
+            AbiEntry::Function(function) | AbiEntry::L1Handler(function)
+                if function.name == name =>
+            {
+                Some(function)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("Function = {name} not found in the contract ABI"))
+}
+
+/// Returns the ABI entry of the function or L1 handler with given name
+pub fn find_function_entry<'a>(abi: &'a [AbiEntry], name: &str) -> Result<&'a AbiEntry> {
+    abi_items(abi)
+        .into_iter()
+        .find(|entry| match entry {
+            AbiEntry::Function(function) | AbiEntry::L1Handler(function) => function.name == name,
+            _ => false,
+        })
+        .ok_or_else(|| anyhow!("Function = {name} not found in the contract ABI"))
+}
+
+/// Returns the ABI entry of the event with given name,
+/// which can be either its full path (e.g. `map::Map::Event`) or the last segment of it
+pub fn find_event_entry<'a>(abi: &'a [AbiEntry], name: &str) -> Result<&'a AbiEntry> {
+    abi_items(abi)
+        .into_iter()
+        .find(|entry| {
+            let AbiEntry::Event(event) = entry else {
+                return false;
+            };
+            let event_name = match event {
+                AbiEvent::Typed(TypedAbiEvent::Struct(event)) => &event.name,
+                AbiEvent::Typed(TypedAbiEvent::Enum(event)) => &event.name,
+                AbiEvent::Untyped(event) => &event.name,
+            };
+            event_name == name || event_name.rsplit("::").next() == Some(name)
+        })
+        .ok_or_else(|| anyhow!("Event = {name} not found in the contract ABI"))
+}
+
+/// Parses ABI of a Sierra contract class, which is stored as a JSON string
+pub fn parse_abi(abi: &str) -> Result<Vec<AbiEntry>> {
+    serde_json::from_str(abi).context("Failed to parse contract ABI")
+}
+
+/// Serializes constructor arguments to calldata, based on the constructor inputs from the ABI.
+/// Arguments are either all positional (`value`) or all named (`name=value`). Composite values
+/// (structs, enums, arrays) are passed as JSON, e.g. `{"x": 1, "y": 2}`, `{"Some": 3}` or `[1, 2]`
+pub fn encode_constructor_args(abi: &[AbiEntry], args: &[String]) -> Result<Vec<FieldElement>> {
+    let inputs = abi_items(abi)
+        .into_iter()
+        .find_map(|entry| match entry {
+            AbiEntry::Constructor(constructor) => Some(constructor.inputs.as_slice()),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    encode_args(abi, inputs, args, "constructor")
+}
+
+/// Serializes arguments of the function with given name to calldata, the same way as [`encode_constructor_args`]
+pub fn encode_function_args(
+    abi: &[AbiEntry],
+    function_name: &str,
+    args: &[String],
+) -> Result<Vec<FieldElement>> {
+    let function = find_function(abi, function_name)?;
+
+    encode_args(abi, &function.inputs, args, "function")
+}
+
+fn encode_args(
+    abi: &[AbiEntry],
+    inputs: &[AbiNamedMember],
+    args: &[String],
+    kind: &str,
+) -> Result<Vec<FieldElement>> {
+    let values = match_args_to_inputs(inputs, args, kind)?;
+
+    let mut calldata = vec![];
+    for (input, value) in inputs.iter().zip(values) {
+        encode_value(abi, &input.r#type, &value, &mut calldata)
+            .with_context(|| format!("Failed to encode {kind} argument = {}", input.name))?;
+    }
+    Ok(calldata)
+}
+
+fn match_args_to_inputs(
+    inputs: &[AbiNamedMember],
+    args: &[String],
+    kind: &str,
+) -> Result<Vec<Value>> {
+    let named_args: Vec<Option<(&str, &str)>> = args
+        .iter()
+        .map(|arg| {
+            arg.split_once('=').filter(|(name, _)| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            })
+        })
+        .collect();
+
+    if named_args.iter().all(Option::is_none) {
+        ensure!(
+            args.len() == inputs.len(),
+            "Expected {} {kind} arguments, got {}",
+            inputs.len(),
+            args.len()
+        );
+        return Ok(args.iter().map(|arg| parse_arg(arg)).collect());
+    }
+
+    let named_args = named_args
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .with_context(|| {
+            format!("Arguments of the {kind} have to be either all named or all positional")
+        })?;
+    for (name, _) in &named_args {
+        ensure!(
+            inputs.iter().any(|input| input.name == *name),
+            "The {kind} does not have an argument named = {name}"
+        );
+    }
+    inputs
+        .iter()
+        .map(|input| {
+            named_args
+                .iter()
+                .find(|(name, _)| *name == input.name)
+                .map(|(_, value)| parse_arg(value))
+                .ok_or_else(|| anyhow!("Missing {kind} argument = {}", input.name))
+        })
+        .collect()
+}
+
+fn parse_arg(arg: &str) -> Value {
+    // Numbers are kept as strings, so they are not limited to the JSON number precision
+    match serde_json::from_str(arg) {
+        Ok(value @ (Value::Bool(_) | Value::Array(_) | Value::Object(_))) => value,
+        _ => Value::String(arg.to_string()),
+    }
+}
+
+fn encode_value(
+    abi: &[AbiEntry],
+    type_name: &str,
+    value: &Value,
+    calldata: &mut Vec<FieldElement>,
+) -> Result<()> {
+    if let Some(inner_type) = generic_argument(type_name, "core::array::Array")
+        .or_else(|| generic_argument(type_name, "core::array::Span"))
+    {
+        let Value::Array(items) = value else {
+            bail!("Expected an array for type = {type_name}, got {value}");
+        };
+        calldata.push(FieldElement::from(items.len()));
+        for item in items {
+            encode_value(abi, inner_type, item, calldata)?;
+        }
+        return Ok(());
+    }
+
+    match type_name {
+        "core::felt252"
+        | "core::starknet::contract_address::ContractAddress"
+        | "core::starknet::class_hash::ClassHash" => calldata.push(parse_felt(value)?),
+        "core::bool" => {
+            let value = match value {
+                Value::Bool(value) => *value,
+                value => bail!("Expected a boolean, got {value}"),
+            };
+            calldata.push(FieldElement::from(u8::from(value)));
+        }
+        "core::integer::u8" => calldata.push(parse_unsigned(value, 8)?),
+        "core::integer::u16" => calldata.push(parse_unsigned(value, 16)?),
+        "core::integer::u32" => calldata.push(parse_unsigned(value, 32)?),
+        "core::integer::u64" => calldata.push(parse_unsigned(value, 64)?),
+        "core::integer::u128" => calldata.push(parse_unsigned(value, 128)?),
+        "core::integer::u256" => {
+            let value = parse_u256(value)?;
+            calldata.push(FieldElement::from(value.low_u128()));
+            calldata.push(FieldElement::from((value >> 128).low_u128()));
+        }
+        type_name => encode_user_type(abi, type_name, value, calldata)?,
+    }
+    Ok(())
+}
+
+fn encode_user_type(
+    abi: &[AbiEntry],
+    type_name: &str,
+    value: &Value,
+    calldata: &mut Vec<FieldElement>,
+) -> Result<()> {
+    for entry in abi_items(abi) {
+        match entry {
+            AbiEntry::Struct(abi_struct) if abi_struct.name == type_name => {
+                let Value::Object(fields) = value else {
+                    bail!("Expected an object for struct = {type_name}, got {value}");
+                };
+                for member in &abi_struct.members {
+                    let field = fields.get(&member.name).ok_or_else(|| {
+                        anyhow!("Missing field = {} of struct = {type_name}", member.name)
+                    })?;
+                    encode_value(abi, &member.r#type, field, calldata)?;
+                }
+                return Ok(());
+            }
+            AbiEntry::Enum(abi_enum) if abi_enum.name == type_name => {
+                let (variant_name, variant_value) = enum_variant(value)
+                    .with_context(|| format!("Expected a variant of enum = {type_name}"))?;
+                let (index, variant) = abi_enum
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, variant)| variant.name == variant_name)
+                    .ok_or_else(|| {
+                        anyhow!("Enum = {type_name} does not have a variant named = {variant_name}")
+                    })?;
+                calldata.push(FieldElement::from(index));
+                if variant.r#type != "()" {
+                    encode_value(abi, &variant.r#type, &variant_value, calldata)?;
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+    bail!("Unsupported type = {type_name}. Use `--constructor-calldata` to pass serialized calldata instead")
+}
+
+/// Enum variants are passed either as `{"Variant": value}` or, for unit variants, as `"Variant"`
+fn enum_variant(value: &Value) -> Option<(String, Value)> {
+    match value {
+        Value::String(variant) => Some((variant.clone(), Value::Null)),
+        Value::Object(object) if object.len() == 1 => {
+            let (variant, value) = object.iter().next()?;
+            Some((variant.clone(), value.clone()))
+        }
+        _ => None,
+    }
+}
+
+fn generic_argument<'a>(type_name: &'a str, generic_type: &str) -> Option<&'a str> {
+    type_name
+        .strip_prefix(generic_type)?
+        .strip_prefix("::<")?
+        .strip_suffix('>')
+}
+
+fn value_as_str(value: &Value) -> Result<String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(value) if !value.is_f64() => Ok(value.to_string()),
+        Value::Number(value) => {
+            bail!("Number = {value} is not an integer or is too large, pass it as a string instead")
+        }
+        value => bail!("Expected a number, got {value}"),
+    }
+}
+
+fn parse_felt(value: &Value) -> Result<FieldElement> {
+    let value = value_as_str(value)?;
+    FieldElement::from_str(&value).map_err(|_| anyhow!("Failed to parse {value} as felt252"))
+}
+
+fn parse_unsigned(value: &Value, bits: usize) -> Result<FieldElement> {
+    let parsed = parse_u256(value)?;
+    ensure!(
+        parsed.bits() <= bits,
+        "Value = {} does not fit into u{bits}",
+        value_as_str(value)?
+    );
+    Ok(FieldElement::from(parsed.low_u128()))
+}
+
+fn parse_u256(value: &Value) -> Result<U256> {
+    let value = value_as_str(value)?;
+    match value.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16),
+        None => U256::from_dec_str(&value),
+    }
+    .map_err(|_| anyhow!("Failed to parse {value} as an unsigned integer"))
+}
+
+/// Decodes the value returned by a function into JSON, based on the function outputs from the ABI.
+/// Values are represented the same way as constructor arguments: structs as objects,
+/// enums as `{"Variant": value}` (or `"Variant"` for unit variants), numbers as strings
+pub fn decode_function_outputs(
+    abi: &[AbiEntry],
+    function_name: &str,
+    response: &[FieldElement],
+) -> Result<Value> {
+    let function = find_function(abi, function_name)?;
+
+    let mut felts = response.iter().copied();
+    let mut values = function
+        .outputs
+        .iter()
+        .map(|output| decode_value(abi, &output.r#type, &mut felts))
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(
+        felts.next().is_none(),
+        "Response of function = {function_name} is longer than expected from its ABI"
+    );
+
+    Ok(if values.len() == 1 {
+        values.remove(0)
+    } else {
+        Value::Array(values)
+    })
+}
+
+fn decode_value(
+    abi: &[AbiEntry],
+    type_name: &str,
+    felts: &mut impl Iterator<Item = FieldElement>,
+) -> Result<Value> {
+    let mut next_felt = || {
+        felts
+            .next()
+            .ok_or_else(|| anyhow!("Response is too short to decode type = {type_name}"))
+    };
+
+    if let Some(inner_type) = generic_argument(type_name, "core::array::Array")
+        .or_else(|| generic_argument(type_name, "core::array::Span"))
+    {
+        let len = felt_to_usize(next_felt()?)?;
+        let items = (0..len)
+            .map(|_| decode_value(abi, inner_type, felts))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Value::Array(items));
+    }
+
+    let value = match type_name {
+        "()" => Value::Null,
+        "core::felt252"
+        | "core::starknet::contract_address::ContractAddress"
+        | "core::starknet::class_hash::ClassHash" => Value::String(format!("{:#x}", next_felt()?)),
+        "core::bool" => Value::Bool(next_felt()? != FieldElement::ZERO),
+        "core::integer::u8"
+        | "core::integer::u16"
+        | "core::integer::u32"
+        | "core::integer::u64"
+        | "core::integer::u128" => Value::String(format!("{:#}", next_felt()?)),
+        "core::integer::u256" => {
+            let low = felt_to_u256(next_felt()?);
+            let high = felt_to_u256(next_felt()?);
+            Value::String(((high << 128) + low).to_string())
+        }
+        "core::byte_array::ByteArray" => decode_byte_array(felts)?,
+        type_name => decode_user_type(abi, type_name, felts)?,
+    };
+    Ok(value)
+}
+
+fn decode_user_type(
+    abi: &[AbiEntry],
+    type_name: &str,
+    felts: &mut impl Iterator<Item = FieldElement>,
+) -> Result<Value> {
+    for entry in abi_items(abi) {
+        match entry {
+            AbiEntry::Struct(abi_struct) if abi_struct.name == type_name => {
+                let mut fields = Map::new();
+                for member in &abi_struct.members {
+                    fields.insert(
+                        member.name.clone(),
+                        decode_value(abi, &member.r#type, felts)?,
+                    );
+                }
+                return Ok(Value::Object(fields));
+            }
+            AbiEntry::Enum(abi_enum) if abi_enum.name == type_name => {
+                let index = felts
+                    .next()
+                    .ok_or_else(|| anyhow!("Response is too short to decode type = {type_name}"))?;
+                let variant = abi_enum
+                    .variants
+                    .get(felt_to_usize(index)?)
+                    .ok_or_else(|| {
+                        anyhow!("Enum = {type_name} does not have a variant with index = {index}")
+                    })?;
+                if variant.r#type == "()" {
+                    return Ok(Value::String(variant.name.clone()));
+                }
+                let value = decode_value(abi, &variant.r#type, felts)?;
+                return Ok(Value::Object(Map::from_iter([(
+                    variant.name.clone(),
+                    value,
+                )])));
+            }
+            _ => {}
+        }
+    }
+    bail!("Unsupported type = {type_name}")
+}
+
+fn decode_byte_array(felts: &mut impl Iterator<Item = FieldElement>) -> Result<Value> {
+    let mut next_felt = || {
+        felts
+            .next()
+            .context("Response is too short to decode type = core::byte_array::ByteArray")
+    };
+
+    let mut bytes = vec![];
+    let words_count = felt_to_usize(next_felt()?)?;
+    for _ in 0..words_count {
+        bytes.extend_from_slice(&next_felt()?.to_bytes_be()[1..]);
+    }
+    let pending_word = next_felt()?.to_bytes_be();
+    let pending_word_len = felt_to_usize(next_felt()?)?;
+    ensure!(
+        pending_word_len < 31,
+        "Invalid length of the pending word of ByteArray = {pending_word_len}"
+    );
+    bytes.extend_from_slice(&pending_word[32 - pending_word_len..]);
+
+    Ok(Value::String(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+fn felt_to_usize(felt: FieldElement) -> Result<usize> {
+    usize::try_from(felt).map_err(|_| anyhow!("Value = {felt:#x} is too large to be a length"))
+}
+
+fn felt_to_u256(felt: FieldElement) -> U256 {
+    U256::from_big_endian(&felt.to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helpers::abi::{
+        decode_function_outputs, encode_constructor_args, find_event_entry, find_function,
+        find_function_entry, load_abi_from_file, parse_abi,
+    };
+    use serde_json::json;
+    use starknet::core::types::contract::AbiEntry;
+    use starknet::core::types::FieldElement;
+
+    #[test]
+    fn test_load_abi_and_find_function() {
+        let abi = load_abi_from_file(&"tests/data/files/map_abi.json".into()).unwrap();
+        let function = find_function(&abi, "get").unwrap();
+
+        assert_eq!(function.name, "get");
+        assert_eq!(function.inputs.len(), 1);
+    }
+
+    #[test]
+    fn test_find_function_not_in_abi() {
+        let abi = load_abi_from_file(&"tests/data/files/map_abi.json".into()).unwrap();
+        let err = find_function(&abi, "nonexistent").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Function = nonexistent not found in the contract ABI"));
+    }
+
+    #[test]
+    fn test_load_abi_invalid_file() {
+        let err = load_abi_from_file(&"tests/data/files/state_no_txs.json".into()).unwrap_err();
+
+        assert!(err.to_string().contains("Failed to parse ABI from file"));
+    }
+
+    const CONSTRUCTOR_ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                {"name": "low", "type": "core::integer::u128"},
+                {"name": "high", "type": "core::integer::u128"}
+            ]
+        },
+        {
+            "type": "struct",
+            "name": "example::Point",
+            "members": [
+                {"name": "x", "type": "core::integer::u8"},
+                {"name": "y", "type": "core::felt252"}
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "core::option::Option::<core::felt252>",
+            "variants": [
+                {"name": "Some", "type": "core::felt252"},
+                {"name": "None", "type": "()"}
+            ]
+        },
+        {
+            "type": "constructor",
+            "name": "constructor",
+            "inputs": [
+                {"name": "amount", "type": "core::integer::u256"},
+                {"name": "point", "type": "example::Point"},
+                {"name": "values", "type": "core::array::Array::<core::felt252>"},
+                {"name": "owner", "type": "core::option::Option::<core::felt252>"},
+                {"name": "active", "type": "core::bool"}
+            ]
+        }
+    ]"#;
+
+    fn felts(values: &[u128]) -> Vec<FieldElement> {
+        values
+            .iter()
+            .map(|value| FieldElement::from(*value))
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_constructor_args_positional() {
+        let abi = parse_abi(CONSTRUCTOR_ABI).unwrap();
+        let args = [
+            "0x100000000000000000000000000000005",
+            r#"{"x": 3, "y": "0x4"}"#,
+            "[7, 8]",
+            r#"{"Some": 9}"#,
+            "true",
+        ]
+        .map(String::from);
+
+        let calldata = encode_constructor_args(&abi, &args).unwrap();
+
+        assert_eq!(calldata, felts(&[5, 1, 3, 4, 2, 7, 8, 0, 9, 1]));
+    }
+
+    #[test]
+    fn test_encode_constructor_args_named() {
+        let abi = parse_abi(CONSTRUCTOR_ABI).unwrap();
+        let args = [
+            "active=false",
+            "owner=\"None\"",
+            "values=[]",
+            r#"point={"x": 1, "y": 2}"#,
+            "amount=10",
+        ]
+        .map(String::from);
+
+        let calldata = encode_constructor_args(&abi, &args).unwrap();
+
+        assert_eq!(calldata, felts(&[10, 0, 1, 2, 0, 1, 0]));
+    }
+
+    #[test]
+    fn test_encode_constructor_args_type_mismatch() {
+        let abi = parse_abi(CONSTRUCTOR_ABI).unwrap();
+        let args = ["1", r#"{"x": 256, "y": 0}"#, "[]", "\"None\"", "true"].map(String::from);
+
+        let err = encode_constructor_args(&abi, &args).unwrap_err();
+
+        assert!(format!("{err:#}").contains("Failed to encode constructor argument = point"));
+        assert!(format!("{err:#}").contains("Value = 256 does not fit into u8"));
+    }
+
+    #[test]
+    fn test_encode_constructor_args_wrong_count() {
+        let abi = parse_abi(CONSTRUCTOR_ABI).unwrap();
+        let err = encode_constructor_args(&abi, &["1".to_string()]).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Expected 5 constructor arguments, got 1"));
+    }
+
+    #[test]
+    fn test_encode_function_args() {
+        let abi = parse_abi(
+            r#"[
+                {
+                    "type": "struct",
+                    "name": "example::Point",
+                    "members": [
+                        {"name": "x", "type": "core::integer::u8"},
+                        {"name": "y", "type": "core::felt252"}
+                    ]
+                },
+                {
+                    "type": "function",
+                    "name": "move_to",
+                    "inputs": [
+                        {"name": "point", "type": "example::Point"},
+                        {"name": "speed", "type": "core::felt252"}
+                    ],
+                    "outputs": [],
+                    "state_mutability": "external"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let calldata = encode_function_args(
+            &abi,
+            "move_to",
+            &[r#"{"x": 1, "y": 2}"#.to_string(), "3".to_string()],
+        )
+        .unwrap();
+        assert_eq!(calldata, felts(&[1, 2, 3]));
+
+        let err = encode_function_args(&abi, "move_to", &["1".to_string()]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Expected 2 function arguments, got 1"));
+
+        let err = encode_function_args(&abi, "jump", &[]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Function = jump not found in the contract ABI"));
+    }
+
+    const FUNCTIONS_ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "example::Point",
+            "members": [
+                {"name": "x", "type": "core::integer::u8"},
+                {"name": "y", "type": "core::felt252"}
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "core::option::Option::<example::Point>",
+            "variants": [
+                {"name": "Some", "type": "example::Point"},
+                {"name": "None", "type": "()"}
+            ]
+        },
+        {
+            "type": "function",
+            "name": "get_points",
+            "inputs": [],
+            "outputs": [{"type": "core::array::Array::<core::option::Option::<example::Point>>"}],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "get_balance",
+            "inputs": [],
+            "outputs": [{"type": "core::integer::u256"}],
+            "state_mutability": "view"
+        },
+        {
+            "type": "function",
+            "name": "get_name",
+            "inputs": [],
+            "outputs": [{"type": "core::byte_array::ByteArray"}],
+            "state_mutability": "view"
+        }
+    ]"#;
+
+    #[test]
+    fn test_decode_function_outputs_composite() {
+        let abi = parse_abi(FUNCTIONS_ABI).unwrap();
+
+        let decoded =
+            decode_function_outputs(&abi, "get_points", &felts(&[2, 0, 3, 4, 1])).unwrap();
+
+        assert_eq!(decoded, json!([{"Some": {"x": "3", "y": "0x4"}}, "None"]));
+    }
+
+    #[test]
+    fn test_decode_function_outputs_u256_and_byte_array() {
+        let abi = parse_abi(FUNCTIONS_ABI).unwrap();
+
+        let balance = decode_function_outputs(&abi, "get_balance", &felts(&[5, 1])).unwrap();
+        let name =
+            decode_function_outputs(&abi, "get_name", &felts(&[0, 0x6865_6c6c_6f, 5])).unwrap();
+
+        assert_eq!(balance, json!("340282366920938463463374607431768211461"));
+        assert_eq!(name, json!("hello"));
+    }
+
+    #[test]
+    fn test_decode_function_outputs_wrong_length() {
+        let abi = parse_abi(FUNCTIONS_ABI).unwrap();
+
+        let too_short = decode_function_outputs(&abi, "get_balance", &felts(&[5])).unwrap_err();
+        let too_long =
+            decode_function_outputs(&abi, "get_balance", &felts(&[5, 0, 1])).unwrap_err();
+
+        assert!(too_short
+            .to_string()
+            .contains("Response is too short to decode type = core::integer::u256"));
+        assert!(too_long
+            .to_string()
+            .contains("Response of function = get_balance is longer than expected from its ABI"));
+    }
+
+    #[test]
+    fn test_find_abi_entries() {
+        let abi = load_abi_from_file(&"tests/data/files/map_abi.json".into()).unwrap();
+
+        assert!(matches!(
+            find_function_entry(&abi, "put").unwrap(),
+            AbiEntry::Function(function) if function.name == "put"
+        ));
+        assert!(matches!(
+            find_event_entry(&abi, "Event").unwrap(),
+            AbiEntry::Event(_)
+        ));
+        assert!(find_event_entry(&abi, "map::Map::Event").is_ok());
+        assert!(find_event_entry(&abi, "put").is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::helpers::abi::parse_abi;
+use crate::helpers::constants::DEFAULT_ABI_CACHE_DIR;
+use camino::Utf8PathBuf;
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::FieldElement;
+use std::fs;
+
+/// Maximum total size of the cached ABIs, oldest entries are removed when it is exceeded
+const MAX_ABI_CACHE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Local cache of class ABIs keyed by class hash. Declared classes never change,
+/// so cached entries never have to be invalidated.
+/// Failures of the cache are ignored, in which case the ABI is fetched from the network
+pub struct AbiCache {
+    dir: Utf8PathBuf,
+    max_size: u64,
+}
+
+impl Default for AbiCache {
+    fn default() -> Self {
+        Self::new(Utf8PathBuf::from(
+            shellexpand::tilde(DEFAULT_ABI_CACHE_DIR).to_string(),
+        ))
+    }
+}
+
+impl AbiCache {
+    #[must_use]
+    pub fn new(dir: Utf8PathBuf) -> Self {
+        Self {
+            dir,
+            max_size: MAX_ABI_CACHE_SIZE,
+        }
+    }
+
+    #[must_use]
+    pub fn load(&self, class_hash: FieldElement) -> Option<Vec<AbiEntry>> {
+        let abi = fs::read_to_string(self.entry_path(class_hash)).ok()?;
+        parse_abi(&abi).ok()
+    }
+
+    pub fn store(&self, class_hash: FieldElement, abi: &str) {
+        if fs::create_dir_all(&self.dir).is_ok()
+            && fs::write(self.entry_path(class_hash), abi).is_ok()
+        {
+            self.evict_oldest_entries();
+        }
+    }
+
+    fn entry_path(&self, class_hash: FieldElement) -> Utf8PathBuf {
+        self.dir.join(format!("{class_hash:#x}.json"))
+    }
+
+    fn evict_oldest_entries(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut entries: Vec<_> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+            })
+            .collect();
+        entries.sort();
+
+        let mut size: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        for (_, len, path) in entries {
+            if size <= self.max_size {
+                break;
+            }
+            if fs::remove_file(path).is_ok() {
+                size -= len;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const ABI: &str = r#"[{"type": "function", "name": "get", "inputs": [], "outputs": [], "state_mutability": "view"}]"#;
+
+    fn cache_in(dir: &tempfile::TempDir) -> AbiCache {
+        AbiCache::new(Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap())
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = tempdir().unwrap();
+        let cache = cache_in(&dir);
+
+        assert!(cache.load(FieldElement::ONE).is_none());
+        cache.store(FieldElement::ONE, ABI);
+
+        assert_eq!(cache.load(FieldElement::ONE).unwrap().len(), 1);
+        assert!(cache.load(FieldElement::TWO).is_none());
+    }
+
+    #[test]
+    fn test_oldest_entries_evicted() {
+        let dir = tempdir().unwrap();
+        let mut cache = cache_in(&dir);
+        cache.max_size = ABI.len() as u64;
+
+        cache.store(FieldElement::ONE, ABI);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        cache.store(FieldElement::TWO, ABI);
+
+        assert!(cache.load(FieldElement::ONE).is_none());
+        assert!(cache.load(FieldElement::TWO).is_some());
+    }
+}
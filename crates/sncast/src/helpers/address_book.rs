@@ -0,0 +1,203 @@
+use crate::helpers::scarb_utils::{read_closest_scarb_manifest, read_scarb_manifest};
+use anyhow::{anyhow, bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use starknet::core::types::FieldElement;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::{fmt, fs};
+
+/// Contract address passed as a felt or as `@<name>` reference to an entry of the address book
+#[derive(Clone, Debug, PartialEq)]
+pub enum AddressArg {
+    Value(FieldElement),
+    Name(String),
+}
+
+pub fn parse_address(value: &str) -> Result<AddressArg> {
+    if let Some(name) = value.strip_prefix('@') {
+        if name.is_empty() {
+            bail!("Missing name of the address book entry in `@`");
+        }
+        return Ok(AddressArg::Name(name.to_string()));
+    }
+    FieldElement::from_str(value)
+        .map(AddressArg::Value)
+        .with_context(|| {
+            format!("Failed to parse address = {value}, expected a felt or `@<name>` of an address book entry")
+        })
+}
+
+impl fmt::Display for AddressArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressArg::Value(address) => write!(f, "{address:#x}"),
+            AddressArg::Name(name) => write!(f, "@{name}"),
+        }
+    }
+}
+
+impl AddressArg {
+    /// Returns the address, looking up names in the address book loaded with [`AddressBook::load`].
+    /// The address book is read only if a name is referenced
+    pub fn resolve(&self, address_book_path: Option<&Utf8PathBuf>) -> Result<FieldElement> {
+        match self {
+            AddressArg::Value(address) => Ok(*address),
+            AddressArg::Name(name) => AddressBook::load(address_book_path)?.get(name),
+        }
+    }
+}
+
+/// Named contract addresses
+#[derive(Debug, Default)]
+pub struct AddressBook(BTreeMap<String, FieldElement>);
+
+impl AddressBook {
+    /// Reads the address book from `address_book_path` or, if not passed, from `[tool.sncast.addresses]`
+    /// section of the Scarb manifest (`SCARB_MANIFEST_PATH` or the closest `Scarb.toml`)
+    pub fn load(address_book_path: Option<&Utf8PathBuf>) -> Result<Self> {
+        match address_book_path {
+            Some(path) => Self::from_file(path),
+            None => Self::from_manifest(read_scarb_manifest()?),
+        }
+    }
+
+    /// Reads the address book from a TOML file with `<name> = "<address>"` entries
+    pub fn from_file(path: &Utf8Path) -> Result<Self> {
+        let entries = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read address book = {path}"))?
+            .parse::<toml::Table>()
+            .with_context(|| format!("Failed to parse address book = {path}"))?;
+        Self::from_entries(&entries, &format!("address book = {path}"))
+    }
+
+    /// Reads the address book from `[tool.sncast.addresses]` section of the closest `Scarb.toml`,
+    /// it is empty if there is no such section
+    pub fn from_scarb(dir: &Utf8Path) -> Result<Self> {
+        Self::from_manifest(read_closest_scarb_manifest(dir)?)
+    }
+
+    fn from_manifest(manifest: Option<(Utf8PathBuf, toml::Value)>) -> Result<Self> {
+        let Some((manifest_path, manifest)) = manifest else {
+            return Ok(Self::default());
+        };
+        match manifest
+            .get("tool")
+            .and_then(|tool| tool.get("sncast"))
+            .and_then(|sncast| sncast.get("addresses"))
+        {
+            Some(toml::Value::Table(entries)) => Self::from_entries(
+                entries,
+                &format!("`tool.sncast.addresses` in {manifest_path}"),
+            ),
+            Some(_) => Err(anyhow!(
+                "Field `tool.sncast.addresses` in {manifest_path} must be a table"
+            )),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn from_entries(entries: &toml::Table, source: &str) -> Result<Self> {
+        entries
+            .iter()
+            .map(|(name, value)| {
+                let address = value
+                    .as_str()
+                    .and_then(|value| FieldElement::from_str(value).ok())
+                    .with_context(|| {
+                        format!("Entry `{name}` of {source} must be a string with an address")
+                    })?;
+                Ok((name.clone(), address))
+            })
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    pub fn get(&self, name: &str) -> Result<FieldElement> {
+        if let Some(address) = self.0.get(name) {
+            return Ok(*address);
+        }
+        if self.0.is_empty() {
+            bail!("Unknown address book entry = {name}, the address book is empty");
+        }
+        let known: Vec<&str> = self.0.keys().map(String::as_str).collect();
+        bail!(
+            "Unknown address book entry = {name}, known entries: {}",
+            known.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(
+            parse_address("0x123").unwrap(),
+            AddressArg::Value(FieldElement::from(0x123_u32))
+        );
+        assert_eq!(
+            parse_address("@token").unwrap(),
+            AddressArg::Name("token".to_string())
+        );
+        assert!(parse_address("@").is_err());
+        assert!(parse_address("token").is_err());
+    }
+
+    #[test]
+    fn test_address_book_from_scarb() {
+        let dir = tempdir().unwrap();
+        let dir = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        fs::write(
+            dir.join("Scarb.toml"),
+            indoc::indoc! {r#"
+                [package]
+                name = "example"
+
+                [tool.sncast.addresses]
+                token = "0x123"
+                vault = "0x456"
+            "#},
+        )
+        .unwrap();
+        let nested = dir.join("src");
+        fs::create_dir(&nested).unwrap();
+
+        let address_book = AddressBook::from_scarb(&nested).unwrap();
+
+        assert_eq!(
+            address_book.get("token").unwrap(),
+            FieldElement::from(0x123_u32)
+        );
+        assert_eq!(
+            address_book.get("pool").unwrap_err().to_string(),
+            "Unknown address book entry = pool, known entries: token, vault"
+        );
+    }
+
+    #[test]
+    fn test_empty_address_book() {
+        let address_book = AddressBook::default();
+
+        assert_eq!(
+            address_book.get("token").unwrap_err().to_string(),
+            "Unknown address book entry = token, the address book is empty"
+        );
+    }
+
+    #[test]
+    fn test_invalid_address_book_entry() {
+        let dir = tempdir().unwrap();
+        let path = Utf8PathBuf::try_from(dir.path().join("addresses.toml")).unwrap();
+        fs::write(&path, "token = 123").unwrap();
+
+        let err = AddressBook::from_file(&path).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("Entry `token` of address book = {path} must be a string with an address")
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use crate::helpers::constants::DEFAULT_CALL_CACHE_DIR;
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{BlockId, BlockTag, FieldElement};
+use starknet::core::utils::starknet_keccak;
+use starknet_crypto::poseidon_hash_many;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Results at the `latest` and `pending` blocks change with new transactions and blocks,
+/// so they are reused at most for this long
+const TAGGED_BLOCK_MAX_TTL: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Deserialize)]
+struct CallCacheEntry {
+    /// Unix time in milliseconds the result was stored at
+    stored_at: u128,
+    response: Vec<FieldElement>,
+}
+
+/// Local cache of `call` results keyed by the RPC URL, contract address, selector, calldata and block.
+/// Results are reused within the TTL; results at the `latest` and `pending` blocks are reused for at most
+/// `TAGGED_BLOCK_MAX_TTL`. Failures of the cache are ignored, in which case the call is performed
+pub struct CallCache {
+    dir: Utf8PathBuf,
+    rpc_url: String,
+    ttl: Duration,
+}
+
+impl CallCache {
+    #[must_use]
+    pub fn new(rpc_url: &str, ttl: Duration) -> Self {
+        Self::new_in(
+            Utf8PathBuf::from(shellexpand::tilde(DEFAULT_CALL_CACHE_DIR).to_string()),
+            rpc_url,
+            ttl,
+        )
+    }
+
+    #[must_use]
+    pub fn new_in(dir: Utf8PathBuf, rpc_url: &str, ttl: Duration) -> Self {
+        Self {
+            dir,
+            rpc_url: rpc_url.to_string(),
+            ttl,
+        }
+    }
+
+    #[must_use]
+    pub fn load(
+        &self,
+        contract_address: FieldElement,
+        selector: FieldElement,
+        calldata: &[FieldElement],
+        block_id: &BlockId,
+    ) -> Option<Vec<FieldElement>> {
+        let path = self.entry_path(contract_address, selector, calldata, block_id);
+        let entry: CallCacheEntry = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+
+        if now_millis()?.saturating_sub(entry.stored_at) < self.ttl_for(block_id).as_millis() {
+            tracing::info!(?block_id, "Reusing the result of the call from the cache");
+            Some(entry.response)
+        } else {
+            let _ = fs::remove_file(path);
+            None
+        }
+    }
+
+    pub fn store(
+        &self,
+        contract_address: FieldElement,
+        selector: FieldElement,
+        calldata: &[FieldElement],
+        block_id: &BlockId,
+        response: Vec<FieldElement>,
+    ) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let Some(stored_at) = now_millis() else {
+            return;
+        };
+        let Ok(entry) = serde_json::to_string(&CallCacheEntry {
+            stored_at,
+            response,
+        }) else {
+            return;
+        };
+        if fs::create_dir_all(&self.dir).is_ok() {
+            let _ = fs::write(
+                self.entry_path(contract_address, selector, calldata, block_id),
+                entry,
+            );
+        }
+    }
+
+    /// Time the results at the block are reused for
+    fn ttl_for(&self, block_id: &BlockId) -> Duration {
+        match block_id {
+            BlockId::Tag(_) => self.ttl.min(TAGGED_BLOCK_MAX_TTL),
+            BlockId::Hash(_) | BlockId::Number(_) => self.ttl,
+        }
+    }
+
+    fn entry_path(
+        &self,
+        contract_address: FieldElement,
+        selector: FieldElement,
+        calldata: &[FieldElement],
+        block_id: &BlockId,
+    ) -> Utf8PathBuf {
+        let (block_kind, block) = match block_id {
+            BlockId::Hash(hash) => (FieldElement::ZERO, *hash),
+            BlockId::Number(number) => (FieldElement::ONE, FieldElement::from(*number)),
+            BlockId::Tag(BlockTag::Latest) => (FieldElement::TWO, FieldElement::ZERO),
+            BlockId::Tag(BlockTag::Pending) => (FieldElement::from(3_u8), FieldElement::ZERO),
+        };
+        let key = poseidon_hash_many(&[
+            starknet_keccak(self.rpc_url.as_bytes()),
+            contract_address,
+            selector,
+            poseidon_hash_many(calldata),
+            block_kind,
+            block,
+        ]);
+        self.dir.join(format!("{key:#x}.json"))
+    }
+}
+
+fn now_millis() -> Option<u128> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|now| now.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const URL: &str = "http://127.0.0.1:5055/rpc";
+
+    fn cache_in(dir: &tempfile::TempDir, ttl: Duration) -> CallCache {
+        CallCache::new_in(
+            Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap(),
+            URL,
+            ttl,
+        )
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = tempdir().unwrap();
+        let cache = cache_in(&dir, Duration::from_secs(60));
+        let block_id = BlockId::Number(10);
+
+        assert!(cache
+            .load(FieldElement::ONE, FieldElement::TWO, &[], &block_id)
+            .is_none());
+        cache.store(
+            FieldElement::ONE,
+            FieldElement::TWO,
+            &[],
+            &block_id,
+            vec![FieldElement::from(3_u8)],
+        );
+
+        assert_eq!(
+            cache.load(FieldElement::ONE, FieldElement::TWO, &[], &block_id),
+            Some(vec![FieldElement::from(3_u8)])
+        );
+        assert!(cache
+            .load(
+                FieldElement::ONE,
+                FieldElement::TWO,
+                &[],
+                &BlockId::Number(11)
+            )
+            .is_none());
+        assert!(cache
+            .load(
+                FieldElement::ONE,
+                FieldElement::TWO,
+                &[FieldElement::ONE],
+                &block_id
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_pending_block_cached_within_ttl() {
+        let dir = tempdir().unwrap();
+        let cache = cache_in(&dir, Duration::from_secs(60));
+        let block_id = BlockId::Tag(BlockTag::Pending);
+
+        cache.store(
+            FieldElement::ONE,
+            FieldElement::TWO,
+            &[],
+            &block_id,
+            vec![FieldElement::from(3_u8)],
+        );
+
+        assert_eq!(
+            cache.load(FieldElement::ONE, FieldElement::TWO, &[], &block_id),
+            Some(vec![FieldElement::from(3_u8)])
+        );
+        assert!(cache
+            .load(
+                FieldElement::ONE,
+                FieldElement::TWO,
+                &[],
+                &BlockId::Tag(BlockTag::Latest)
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_tagged_block_ttl_capped() {
+        let dir = tempdir().unwrap();
+        let cache = cache_in(&dir, Duration::from_secs(3600));
+
+        assert_eq!(
+            cache.ttl_for(&BlockId::Tag(BlockTag::Pending)),
+            TAGGED_BLOCK_MAX_TTL
+        );
+        assert_eq!(
+            cache.ttl_for(&BlockId::Tag(BlockTag::Latest)),
+            TAGGED_BLOCK_MAX_TTL
+        );
+        assert_eq!(
+            cache.ttl_for(&BlockId::Number(10)),
+            Duration::from_secs(3600)
+        );
+    }
+
+    #[test]
+    fn test_expired_entry_not_used() {
+        let dir = tempdir().unwrap();
+        let cache = cache_in(&dir, Duration::from_millis(10));
+        let block_id = BlockId::Tag(BlockTag::Latest);
+
+        cache.store(
+            FieldElement::ONE,
+            FieldElement::TWO,
+            &[],
+            &block_id,
+            vec![FieldElement::from(3_u8)],
+        );
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert!(cache
+            .load(FieldElement::ONE, FieldElement::TWO, &[], &block_id)
+            .is_none());
+    }
+}
@@ -1,8 +1,11 @@
+use crate::helpers::keystore::KeystoreFormat;
 use crate::ValidatedWaitParams;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use configuration::GlobalConfig;
 use serde::{Deserialize, Serialize};
+use starknet::core::types::FieldElement;
+use std::env;
 
 #[derive(Default, Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct CastConfig {
@@ -21,11 +24,50 @@ pub struct CastConfig {
 
     pub keystore: Option<Utf8PathBuf>,
 
+    /// Format of the keystore file; detected from its contents by default
+    #[serde(
+        default,
+        rename(serialize = "keystore-format", deserialize = "keystore-format")
+    )]
+    pub keystore_format: KeystoreFormat,
+
+    /// Command used to sign transactions instead of a private key
+    #[serde(rename(serialize = "signer-command", deserialize = "signer-command"))]
+    pub signer_command: Option<String>,
+
     #[serde(
         default,
         rename(serialize = "wait-params", deserialize = "wait-params")
     )]
     pub wait_params: ValidatedWaitParams,
+
+    /// Number of times a request to the node is resent after a timeout or a connection error;
+    /// unrelated to `wait-params`, which govern polling for the status of a sent transaction
+    #[serde(
+        default,
+        rename(
+            serialize = "network-timeout-retries",
+            deserialize = "network-timeout-retries"
+        )
+    )]
+    pub network_timeout_retries: u8,
+
+    /// Maximum length of calldata passed to `call`, `invoke` and `deploy`, guarding against runaway generated calldata
+    #[serde(rename(serialize = "max-calldata-len", deserialize = "max-calldata-len"))]
+    pub max_calldata_len: Option<usize>,
+
+    /// Substrings of profile and account names which indicate a development setup;
+    /// sending transactions to mainnet with such profile or account requires confirmation
+    #[serde(
+        default,
+        rename(serialize = "dev-name-patterns", deserialize = "dev-name-patterns")
+    )]
+    pub dev_name_patterns: Option<Vec<String>>,
+
+    /// Address of the Universal Deployer Contract used by `deploy`, for networks where it is not deployed
+    /// at the default address
+    #[serde(rename(serialize = "udc-address", deserialize = "udc-address"))]
+    pub udc_address: Option<FieldElement>,
 }
 
 impl GlobalConfig for CastConfig {
@@ -35,6 +77,266 @@ impl GlobalConfig for CastConfig {
     }
 
     fn from_raw(config: serde_json::Value) -> Result<Self> {
-        Ok(serde_json::from_value::<CastConfig>(config)?)
+        let mut config = serde_json::from_value::<CastConfig>(config)?;
+
+        config.url = interpolate_env_variables(&config.url).context("Failed to resolve `url`")?;
+        config.accounts_file = interpolate_env_variables(config.accounts_file.as_str())
+            .context("Failed to resolve `accounts-file`")?
+            .into();
+        config.keystore = config
+            .keystore
+            .map(|keystore| interpolate_env_variables(keystore.as_str()).map(Utf8PathBuf::from))
+            .transpose()
+            .context("Failed to resolve `keystore`")?;
+
+        Ok(config)
+    }
+}
+
+/// Overrides entries of the raw config profile with the ones of `--config-inline`, a TOML fragment with keys of
+/// the `[sncast.<profile>]` table. Entries can be separated with `;` instead of new lines
+pub fn apply_inline_config(raw: serde_json::Value, inline: &str) -> Result<serde_json::Value> {
+    let overrides: toml::Table =
+        toml::from_str(&split_inline_entries(inline)).map_err(|error| {
+            let message = error.message().trim_end();
+            match error.span() {
+                Some(span) => {
+                    let (line, column) = line_and_column(inline, span.start);
+                    anyhow!("Failed to parse `--config-inline` as TOML at line {line}, column {column}: {message}")
+                }
+                None => anyhow!("Failed to parse `--config-inline` as TOML: {message}"),
+            }
+        })?;
+
+    let defaults = serde_json::to_value(CastConfig::default())?;
+    let mut raw = match raw {
+        serde_json::Value::Object(entries) => entries,
+        _ => bail!("Config profile should be a table"),
+    };
+    for (key, value) in overrides {
+        let Some(default) = defaults.get(&key) else {
+            bail!("Unknown key = {key} in `--config-inline`");
+        };
+        let value = serde_json::to_value(value)?;
+        match (raw.get(&key).unwrap_or(default), value) {
+            (serde_json::Value::Object(table), serde_json::Value::Object(value)) => {
+                // Nested tables, e.g. `wait-params`, keep the entries which are not overridden
+                let mut table = table.clone();
+                table.extend(value);
+                raw.insert(key, serde_json::Value::Object(table));
+            }
+            (_, value) => {
+                raw.insert(key, value);
+            }
+        }
+    }
+
+    Ok(serde_json::Value::Object(raw))
+}
+
+/// Line and column (both starting at 1) of the byte offset in the input
+fn line_and_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset.min(input.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |line| line.chars().count())
+        + 1;
+    (line, column)
+}
+
+/// Replaces `;` separating the entries with new lines, leaving `;` inside quoted strings intact.
+/// Both are a single byte, so offsets in the result point at the same place in the input
+fn split_inline_entries(inline: &str) -> String {
+    let mut result = String::with_capacity(inline.len());
+    let mut quote = None;
+    let mut escaped = false;
+    for c in inline.chars() {
+        match (c, quote) {
+            (';', None) => {
+                result.push('\n');
+                continue;
+            }
+            ('"' | '\'', None) => quote = Some(c),
+            // Only basic strings (in `"`) support escapes
+            ('\\', Some('"')) if !escaped => {
+                escaped = true;
+                result.push(c);
+                continue;
+            }
+            (c, Some(open)) if c == open && !escaped => quote = None,
+            _ => {}
+        }
+        escaped = false;
+        result.push(c);
+    }
+    result
+}
+
+/// Replaces `${VARIABLE}` occurrences in the value with values of the environment variables
+fn interpolate_env_variables(value: &str) -> Result<String> {
+    let mut result = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let (name, remaining) = rest[start + 2..]
+            .split_once('}')
+            .ok_or_else(|| anyhow!("Unclosed `${{` in value = {value}"))?;
+        let variable =
+            env::var(name).map_err(|_| anyhow!("Environment variable = {name} is not defined"))?;
+        result.push_str(&variable);
+        rest = remaining;
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_inline_config, interpolate_env_variables, split_inline_entries, CastConfig};
+    use camino::Utf8PathBuf;
+    use configuration::{load_global_config, GlobalConfig};
+    use starknet::core::types::FieldElement;
+    use std::env;
+    use std::time::Duration;
+
+    #[test]
+    fn test_interpolate_env_variables() {
+        env::set_var("SNCAST_INTERPOLATION_TEST_ENV", "staging");
+
+        assert_eq!(
+            interpolate_env_variables("~/accounts/${SNCAST_INTERPOLATION_TEST_ENV}.json").unwrap(),
+            "~/accounts/staging.json"
+        );
+        assert_eq!(
+            interpolate_env_variables("http://127.0.0.1:5055/rpc").unwrap(),
+            "http://127.0.0.1:5055/rpc"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_env_variables_undefined() {
+        let err = interpolate_env_variables("${SNCAST_INTERPOLATION_UNDEFINED_ENV}/accounts.json")
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Environment variable = SNCAST_INTERPOLATION_UNDEFINED_ENV is not defined"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_env_variables_unclosed() {
+        let err = interpolate_env_variables("${SNCAST_INTERPOLATION_TEST_ENV").unwrap_err();
+
+        assert!(err.to_string().contains("Unclosed `${` in value"));
+    }
+
+    #[test]
+    fn test_load_config_with_leading_env_variable() {
+        env::set_var("SNCAST_INTERPOLATION_NODE_HOST", "127.0.0.1:5055");
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tempdir.path().join("snfoundry.toml"),
+            "[sncast.default]\nurl = \"http://${SNCAST_INTERPOLATION_NODE_HOST}/rpc\"\naccounts-file = \"${SNCAST_INTERPOLATION_NODE_HOST}/accounts.json\"\n",
+        )
+        .unwrap();
+
+        let config = load_global_config::<CastConfig>(
+            &Some(Utf8PathBuf::try_from(tempdir.path().to_path_buf()).unwrap()),
+            &None,
+        )
+        .unwrap();
+
+        assert_eq!(config.url, "http://127.0.0.1:5055/rpc");
+        assert_eq!(
+            config.accounts_file,
+            Utf8PathBuf::from("127.0.0.1:5055/accounts.json")
+        );
+    }
+
+    #[test]
+    fn test_udc_address_from_raw() {
+        let config = CastConfig::from_raw(serde_json::json!({ "udc-address": "0x123" })).unwrap();
+
+        assert_eq!(config.udc_address, Some(FieldElement::from(0x123_u32)));
+        assert!(CastConfig::from_raw(serde_json::json!({ "udc-address": "0xz" })).is_err());
+    }
+
+    #[test]
+    fn test_network_timeout_retries_from_raw() {
+        let config =
+            CastConfig::from_raw(serde_json::json!({ "network-timeout-retries": 3 })).unwrap();
+        assert_eq!(config.network_timeout_retries, 3);
+
+        let config = CastConfig::from_raw(serde_json::json!({})).unwrap();
+        assert_eq!(config.network_timeout_retries, 0);
+    }
+
+    #[test]
+    fn test_apply_inline_config() {
+        let raw = serde_json::json!({
+            "url": "http://127.0.0.1:5055/rpc",
+            "account": "user1",
+            "wait-params": { "timeout": 100 },
+        });
+
+        let raw = apply_inline_config(
+            raw,
+            r#"account = "user2"; udc-address = "0x123"; wait-params = { retry-interval = 5 }"#,
+        )
+        .unwrap();
+        let config = CastConfig::from_raw(raw).unwrap();
+
+        assert_eq!(config.url, "http://127.0.0.1:5055/rpc");
+        assert_eq!(config.account, "user2");
+        assert_eq!(config.udc_address, Some(FieldElement::from(0x123_u32)));
+        assert_eq!(config.wait_params.get_timeout(), Duration::from_secs(100));
+        assert_eq!(
+            config.wait_params.get_retry_interval(),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_apply_inline_config_interpolated() {
+        env::set_var("SNCAST_INLINE_CONFIG_TEST_URL", "http://127.0.0.1:5055/rpc");
+
+        let raw = apply_inline_config(
+            serde_json::json!({}),
+            r#"url = "${SNCAST_INLINE_CONFIG_TEST_URL}""#,
+        )
+        .unwrap();
+        let config = CastConfig::from_raw(raw).unwrap();
+
+        assert_eq!(config.url, "http://127.0.0.1:5055/rpc");
+    }
+
+    #[test]
+    fn test_apply_inline_config_errors() {
+        let err = apply_inline_config(serde_json::json!({}), r#"acount = "user2""#).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown key = acount in `--config-inline`");
+
+        let err = apply_inline_config(
+            serde_json::json!({}),
+            r#"url = "http://127.0.0.1"; account ="#,
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Failed to parse `--config-inline` as TOML at line 1, column "));
+    }
+
+    #[test]
+    fn test_split_inline_entries() {
+        assert_eq!(
+            split_inline_entries(
+                r#"url = "http://a;b"; account = 'x;y'; signer-command = "echo \";\"""#
+            ),
+            "url = \"http://a;b\"\n account = 'x;y'\n signer-command = \"echo \\\";\\\"\""
+        );
     }
 }
@@ -15,6 +15,10 @@ function = ""
 inputs = []
 "#;
 
+pub const ETH_ADDRESS: FieldElement =
+    felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7");
+pub const STRK_ADDRESS: FieldElement =
+    felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d");
 pub const UDC_ADDRESS: FieldElement =
     felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
 pub const OZ_CLASS_HASH: FieldElement =
@@ -30,15 +34,30 @@ pub const BRAAVOS_BASE_ACCOUNT_CLASS_HASH: FieldElement =
 
 // used in wait_for_tx. Txs will be fetched every 5s with timeout of 300s - so 60 attempts
 #[allow(dead_code)]
-pub const WAIT_TIMEOUT: u16 = 300;
+pub const WAIT_TIMEOUT: u64 = 300;
 #[allow(dead_code)]
-pub const WAIT_RETRY_INTERVAL: u8 = 5;
+pub const WAIT_RETRY_INTERVAL: u64 = 5;
 
 #[allow(dead_code)]
 pub const DEFAULT_ACCOUNTS_FILE: &str = "~/.starknet_accounts/starknet_open_zeppelin_accounts.json";
 
+pub const DEFAULT_ABI_CACHE_DIR: &str = "~/.cache/sncast/abi";
+pub const DEFAULT_CALL_CACHE_DIR: &str = "~/.cache/sncast/calls";
+
 pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "KEYSTORE_PASSWORD";
 pub const CREATE_KEYSTORE_PASSWORD_ENV_VAR: &str = "CREATE_KEYSTORE_PASSWORD";
+pub const CONFIRM_MAINNET_ENV_VAR: &str = "SNCAST_CONFIRM_MAINNET";
+pub const ALWAYS_CONFIRM_ENV_VAR: &str = "SNCAST_ALWAYS_CONFIRM";
+pub const PROFILE_ENV_VAR: &str = "SNCAST_PROFILE";
+
+pub const DEFAULT_MAX_CALLDATA_LEN: usize = 10_000;
+
+pub const AUTO_SALT_MAX_ATTEMPTS: u64 = 100;
+
+// Limit of the serialized contract class size enforced by Starknet sequencers, in bytes
+pub const MAX_CONTRACT_CLASS_SIZE: usize = 4_089_446;
+
+pub const DEFAULT_DEV_NAME_PATTERNS: [&str; 4] = ["dev", "test", "local", "devnet"];
 
 pub const SCRIPT_LIB_ARTIFACT_NAME: &str = "__sncast_script_lib";
 
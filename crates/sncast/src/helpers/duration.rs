@@ -0,0 +1,124 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{de, Deserialize, Deserializer, Serializer};
+use std::time::Duration;
+
+const UNITS: [(&str, u64); 4] = [("ms", 1), ("s", 1_000), ("m", 60_000), ("h", 3_600_000)];
+
+/// Parses a duration passed as a CLI argument or a config value.
+/// Accepts values with a unit suffix (e.g. `500ms`, `2s`, `1m`, `1h`);
+/// values without a suffix are interpreted as seconds
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+
+    let suffix_start = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(suffix_start);
+
+    let multiplier = if unit.is_empty() {
+        1_000
+    } else {
+        UNITS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+            .map(|(_, multiplier)| *multiplier)
+            .ok_or_else(|| {
+                let accepted_units = UNITS
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                anyhow!("Invalid duration unit = {unit}. Accepted units are: {accepted_units}")
+            })?
+    };
+
+    let amount: u64 = amount
+        .trim()
+        .parse()
+        .with_context(|| format!("Failed to parse duration = {value}"))?;
+    let millis = amount
+        .checked_mul(multiplier)
+        .with_context(|| format!("Duration = {value} is too long"))?;
+
+    if millis == 0 {
+        bail!("Duration must be greater than zero");
+    }
+
+    Ok(Duration::from_millis(millis))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawDuration {
+    Seconds(u64),
+    Text(String),
+}
+
+/// Deserializes a duration given either as an integer number of seconds or as a string with a unit suffix
+pub fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match RawDuration::deserialize(deserializer)? {
+        RawDuration::Seconds(seconds) => Ok(Duration::from_secs(seconds)),
+        RawDuration::Text(text) => parse_duration(&text).map_err(de::Error::custom),
+    }
+}
+
+/// Serializes a duration as an integer number of seconds if possible, otherwise as milliseconds with a unit suffix
+pub fn serialize_duration<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if duration.subsec_millis() == 0 {
+        serializer.serialize_u64(duration.as_secs())
+    } else {
+        serializer.serialize_str(&format_duration(*duration))
+    }
+}
+
+/// Formats a duration the same way it is serialized: as a number of seconds, or as milliseconds
+/// with the `ms` suffix when it is not a whole number of seconds
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        duration.as_secs().to_string()
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_duration("1m").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse_duration("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse_duration("300").unwrap(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(300)), "300");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1500ms");
+        assert_eq!(format_duration(Duration::from_millis(500)), "500ms");
+    }
+
+    #[test]
+    fn test_parse_duration_invalid() {
+        assert!(parse_duration("0s")
+            .unwrap_err()
+            .to_string()
+            .contains("Duration must be greater than zero"));
+        assert!(parse_duration("5d")
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid duration unit = d"));
+        assert!(parse_duration("1.5s").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+}
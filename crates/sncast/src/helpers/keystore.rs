@@ -0,0 +1,175 @@
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use shared::print::print_as_warning;
+use starknet::core::types::FieldElement;
+use starknet::signers::SigningKey;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+/// Format of the file passed with `--keystore`
+#[derive(ValueEnum, Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeystoreFormat {
+    /// Detect the format from the shape of the file
+    #[default]
+    Auto,
+    /// Encrypted Web3 Secret Storage JSON, as created by starkli and `sncast account create --keystore`
+    Starkli,
+    /// Encrypted Web3 Secret Storage JSON with `Crypto` key and extra metadata, as written by ethers.js
+    Ethers,
+    /// Unencrypted private key, either as a hex string or a JSON object with `privateKey` or `private_key` field
+    PrivateKey,
+}
+
+const SUPPORTED_FORMATS: &str = "starkli, ethers, private-key";
+
+/// Reads the signing key from the keystore file. `password` is only requested for encrypted formats
+pub fn read_keystore(
+    path: &Utf8PathBuf,
+    format: KeystoreFormat,
+    password: impl FnOnce() -> Result<String>,
+) -> Result<SigningKey> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read keystore file = {path}"))?;
+    let format = match format {
+        KeystoreFormat::Auto => detect_format(&contents).with_context(|| {
+            format!("Unrecognized format of keystore file = {path}. Supported formats: {SUPPORTED_FORMATS}, select one with `--keystore-format`")
+        })?,
+        format => format,
+    };
+
+    match format {
+        KeystoreFormat::Starkli => Ok(SigningKey::from_keystore(path, &password()?)?),
+        KeystoreFormat::Ethers => read_ethers_keystore(&contents, &password()?)
+            .with_context(|| format!("Failed to read ethers keystore file = {path}")),
+        KeystoreFormat::PrivateKey => {
+            print_as_warning(&anyhow!(
+                "Keystore file = {path} holds an unencrypted private key, consider importing it into an encrypted keystore"
+            ));
+            parse_private_key(&contents)
+                .with_context(|| format!("Failed to read private key from file = {path}"))
+        }
+        KeystoreFormat::Auto => unreachable!("Format is detected above"),
+    }
+}
+
+fn detect_format(contents: &str) -> Option<KeystoreFormat> {
+    match serde_json::from_str::<Value>(contents) {
+        Ok(Value::Object(keystore)) if keystore.contains_key("crypto") => {
+            Some(KeystoreFormat::Starkli)
+        }
+        Ok(Value::Object(keystore)) if keystore.contains_key("Crypto") => {
+            Some(KeystoreFormat::Ethers)
+        }
+        Ok(Value::Object(keystore))
+            if keystore.contains_key("privateKey") || keystore.contains_key("private_key") =>
+        {
+            Some(KeystoreFormat::PrivateKey)
+        }
+        Ok(Value::String(key)) if key.starts_with("0x") => Some(KeystoreFormat::PrivateKey),
+        Err(_) if contents.trim().starts_with("0x") => Some(KeystoreFormat::PrivateKey),
+        _ => None,
+    }
+}
+
+/// Normalizes the keystore to the form read by starkli, which differs only in the casing of `crypto`
+/// and the lack of extra metadata, and decrypts it
+fn read_ethers_keystore(contents: &str, password: &str) -> Result<SigningKey> {
+    let keystore: Map<String, Value> = serde_json::from_str(contents)?;
+    let mut normalized = Map::new();
+    for key in ["id", "version"] {
+        if let Some(value) = keystore.get(key) {
+            normalized.insert(key.to_string(), value.clone());
+        }
+    }
+    let crypto = keystore
+        .get("Crypto")
+        .context("Missing `Crypto` field in keystore")?;
+    normalized.insert("crypto".to_string(), crypto.clone());
+
+    let mut normalized_file = NamedTempFile::new()?;
+    normalized_file.write_all(Value::Object(normalized).to_string().as_bytes())?;
+    Ok(SigningKey::from_keystore(normalized_file.path(), password)?)
+}
+
+fn parse_private_key(contents: &str) -> Result<SigningKey> {
+    let key = match serde_json::from_str::<Value>(contents) {
+        Ok(Value::Object(keystore)) => keystore
+            .get("privateKey")
+            .or_else(|| keystore.get("private_key"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        Ok(Value::String(key)) => Some(key),
+        _ => Some(contents.trim().to_string()),
+    }
+    .context("Missing `privateKey` field")?;
+    if !key.starts_with("0x") {
+        bail!("Private key should be a 0x prefixed hex");
+    }
+
+    Ok(SigningKey::from_secret_scalar(FieldElement::from_hex_be(
+        &key,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_format, read_keystore, KeystoreFormat};
+    use camino::Utf8PathBuf;
+    use starknet::core::types::FieldElement;
+
+    const PRIVATE_KEY: &str = "0x55ae34c86281fbd19292c7e3bfdfceb4";
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            detect_format(r#"{"crypto": {}, "id": "1", "version": 3}"#),
+            Some(KeystoreFormat::Starkli)
+        );
+        assert_eq!(
+            detect_format(r#"{"Crypto": {}, "address": "0x1", "x-ethers": {}}"#),
+            Some(KeystoreFormat::Ethers)
+        );
+        assert_eq!(
+            detect_format(r#"{"privateKey": "0x1"}"#),
+            Some(KeystoreFormat::PrivateKey)
+        );
+        assert_eq!(detect_format("0x1\n"), Some(KeystoreFormat::PrivateKey));
+        assert_eq!(detect_format(r#"{"mnemonic": "abc"}"#), None);
+    }
+
+    #[test]
+    fn test_read_keystore_formats() {
+        for path in [
+            "tests/data/keystore/my_key.json",
+            "tests/data/keystore/my_key_ethers.json",
+            "tests/data/keystore/my_key_private_key.json",
+        ] {
+            let key = read_keystore(&Utf8PathBuf::from(path), KeystoreFormat::Auto, || {
+                Ok("123".to_string())
+            })
+            .unwrap();
+            assert_eq!(
+                key.secret_scalar(),
+                FieldElement::from_hex_be(PRIVATE_KEY).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_keystore_unrecognized_format() {
+        let err = read_keystore(
+            &Utf8PathBuf::from("tests/data/keystore/my_account.json"),
+            KeystoreFormat::Auto,
+            || Ok("123".to_string()),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains(
+            "Unrecognized format of keystore file = tests/data/keystore/my_account.json. Supported formats: starkli, ethers, private-key"
+        ));
+    }
+}
@@ -0,0 +1,146 @@
+use clap::ValueEnum;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::EnvFilter;
+use url::Url;
+
+/// Environment variable setting the log level when `--log-level` is not passed
+pub const LOG_LEVEL_ENV_VAR: &str = "SNCAST_LOG";
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Level::ERROR,
+            LogLevel::Warn => Level::WARN,
+            LogLevel::Info => Level::INFO,
+            LogLevel::Debug => Level::DEBUG,
+            LogLevel::Trace => Level::TRACE,
+        }
+    }
+}
+
+/// Builds the filter of printed logs from `--log-level`, falling back to `SNCAST_LOG` environment variable
+/// and then to the default `warn` level. The variable also accepts `tracing` directives, e.g. `sncast::helpers=debug`,
+/// invalid ones are ignored
+#[must_use]
+pub fn log_filter(cli_level: Option<LogLevel>) -> EnvFilter {
+    match cli_level {
+        Some(level) => {
+            EnvFilter::default().add_directive(LevelFilter::from(Level::from(level)).into())
+        }
+        None => EnvFilter::builder()
+            .with_default_directive(LevelFilter::WARN.into())
+            .with_env_var(LOG_LEVEL_ENV_VAR)
+            .from_env_lossy(),
+    }
+}
+
+/// Installs a global subscriber writing events allowed by [`log_filter`] to stderr
+pub fn init_logging(cli_level: Option<LogLevel>) {
+    // Fails only if a subscriber is already installed, in which case it is kept
+    let _ = tracing::subscriber::set_global_default(subscriber(cli_level, std::io::stderr));
+}
+
+fn subscriber<W>(cli_level: Option<LogLevel>, writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .with_env_filter(log_filter(cli_level))
+        .with_writer(writer)
+        .with_ansi(false)
+        .without_time()
+        .finish()
+}
+
+/// Strips everything but the scheme, host and port from the url, as paths and queries of node urls
+/// often contain API keys
+#[must_use]
+pub fn redact_url(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(parsed) if parsed.has_host() => {
+            let origin = parsed.origin().ascii_serialization();
+            if parsed.path() == "/" && parsed.query().is_none() && parsed.username().is_empty() {
+                origin
+            } else {
+                format!("{origin}/[redacted]")
+            }
+        }
+        _ => "[redacted]".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+    use test_case::test_case;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn logs_at(level: LogLevel) -> String {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = subscriber(Some(level), move || writer.clone());
+        tracing::subscriber::with_default(subscriber, || tracing::debug!("debug line"));
+
+        let logs = buffer.0.lock().unwrap().clone();
+        String::from_utf8(logs).unwrap()
+    }
+
+    #[test_case(LogLevel::Error, false; "error")]
+    #[test_case(LogLevel::Warn, false; "warn")]
+    #[test_case(LogLevel::Info, false; "info")]
+    #[test_case(LogLevel::Debug, true; "debug")]
+    #[test_case(LogLevel::Trace, true; "trace")]
+    fn test_debug_line_printed_at_level(level: LogLevel, printed: bool) {
+        let logs = logs_at(level);
+
+        assert_eq!(
+            logs.contains("DEBUG sncast::helpers::logging::tests: debug line"),
+            printed,
+            "{logs}"
+        );
+    }
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("https://starknet-sepolia.infura.io/v3/secret-key"),
+            "https://starknet-sepolia.infura.io/[redacted]"
+        );
+        assert_eq!(
+            redact_url("https://node.example.com/rpc?apikey=secret"),
+            "https://node.example.com/[redacted]"
+        );
+        assert_eq!(
+            redact_url("http://127.0.0.1:5055/"),
+            "http://127.0.0.1:5055"
+        );
+        assert_eq!(redact_url("not a url"), "[redacted]");
+    }
+}
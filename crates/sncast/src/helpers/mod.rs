@@ -1,4 +1,17 @@
+pub mod abi;
+pub mod abi_cache;
+pub mod address_book;
 pub mod braavos;
+pub mod call_cache;
 pub mod configuration;
 pub mod constants;
+pub mod duration;
+pub mod keystore;
+pub mod logging;
+pub mod nonce_file;
+pub mod numbers;
+pub mod paymaster;
+pub mod proxy;
 pub mod scarb_utils;
+pub mod signer;
+pub mod traced_transport;
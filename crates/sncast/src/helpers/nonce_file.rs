@@ -0,0 +1,144 @@
+use crate::helpers::signer::CastSigner;
+use crate::helpers::traced_transport::TracedTransport;
+use crate::{chain_id_to_network_name, get_nonce, NonceArg};
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use fs2::FileExt;
+use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::FieldElement;
+use starknet::providers::JsonRpcClient;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// Next free nonce of every account, grouped by network
+type NonceFileContents = BTreeMap<String, BTreeMap<String, FieldElement>>;
+
+/// Returns the explicitly passed nonce or, if `nonce_file` is passed, allocates the next nonce of the account from it
+pub async fn nonce_or_allocate(
+    nonce: Option<FieldElement>,
+    nonce_file: Option<&Utf8PathBuf>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+) -> Result<Option<FieldElement>> {
+    match (nonce, nonce_file) {
+        (None, Some(path)) => allocate_nonce(path, account).await.map(Some),
+        (nonce, _) => Ok(nonce),
+    }
+}
+
+/// Resolves `--nonce` to the nonce of the transaction. `next` is allocated from `nonce_file` if it is passed,
+/// so transactions still in flight from other `sncast` processes are accounted for
+pub async fn resolve_nonce(
+    nonce: Option<NonceArg>,
+    nonce_file: Option<&Utf8PathBuf>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+) -> Result<Option<FieldElement>> {
+    match (nonce, nonce_file) {
+        (Some(NonceArg::Value(nonce)), _) => Ok(Some(nonce)),
+        (Some(NonceArg::Next), None) => get_nonce(account.provider(), "pending", account.address())
+            .await
+            .map(Some),
+        (Some(NonceArg::Next), Some(path)) => allocate_nonce(path, account).await.map(Some),
+        (None, nonce_file) => nonce_or_allocate(None, nonce_file, account).await,
+    }
+}
+
+/// Allocates the next nonce of the account from a file shared by concurrent `sncast` processes.
+/// The file stores the next free nonce of the account, which is raised to the account nonce
+/// if other transactions were sent without using the file
+pub async fn allocate_nonce(
+    path: &Utf8PathBuf,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+) -> Result<FieldElement> {
+    let account_nonce = get_nonce(account.provider(), "pending", account.address()).await?;
+    reserve_nonce(
+        path,
+        &chain_id_to_network_name(account.chain_id()),
+        account.address(),
+        account_nonce,
+    )
+}
+
+fn reserve_nonce(
+    path: &Utf8PathBuf,
+    network: &str,
+    address: FieldElement,
+    account_nonce: FieldElement,
+) -> Result<FieldElement> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .with_context(|| format!("Failed to open nonce file = {path}"))?;
+    // The lock is released when the file is closed
+    file.lock_exclusive()
+        .with_context(|| format!("Failed to lock nonce file = {path}"))?;
+
+    let mut contents = read_nonce_file(&mut file)
+        .with_context(|| format!("Failed to read nonce file = {path}"))?;
+    let next_nonce = contents
+        .entry(network.to_string())
+        .or_default()
+        .entry(format!("{address:#x}"))
+        .or_insert(account_nonce);
+    let nonce = (*next_nonce).max(account_nonce);
+    *next_nonce = nonce + FieldElement::ONE;
+
+    write_nonce_file(&mut file, &contents)
+        .with_context(|| format!("Failed to write nonce file = {path}"))?;
+    Ok(nonce)
+}
+
+fn read_nonce_file(file: &mut File) -> Result<NonceFileContents> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    if contents.trim().is_empty() {
+        return Ok(NonceFileContents::new());
+    }
+    Ok(serde_json::from_str(&contents)?)
+}
+
+fn write_nonce_file(file: &mut File, contents: &NonceFileContents) -> Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(serde_json::to_string_pretty(contents)?.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::reserve_nonce;
+    use camino::Utf8PathBuf;
+    use starknet::core::types::FieldElement;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_reserve_nonce() {
+        let tempdir = tempdir().expect("Failed to create a temporary directory");
+        let path = Utf8PathBuf::try_from(tempdir.path().join("nonces.json")).expect("Invalid path");
+        let address = FieldElement::ONE;
+
+        let reserve = |account_nonce: u32| {
+            reserve_nonce(
+                &path,
+                "alpha-sepolia",
+                address,
+                FieldElement::from(account_nonce),
+            )
+            .unwrap()
+        };
+
+        // Seeded from the account nonce
+        assert_eq!(reserve(5), FieldElement::from(5_u32));
+        // Transactions allocated before are not yet reflected in the account nonce
+        assert_eq!(reserve(5), FieldElement::from(6_u32));
+        // Raised when the account nonce was advanced without using the file
+        assert_eq!(reserve(10), FieldElement::from(10_u32));
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("alpha-sepolia"));
+        assert!(contents.contains("\"0x1\": \"0xb\""));
+    }
+}
@@ -0,0 +1,338 @@
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{de, Deserialize, Deserializer};
+use starknet::core::types::FieldElement;
+use std::io::Read;
+use std::str::FromStr;
+
+/// Felts have at most 76 decimal digits, so no valid value is scaled by more decimal places
+const MAX_DECIMALS: usize = 76;
+
+const UNITS: [(&str, usize); 6] = [
+    ("wei", 0),
+    ("gwei", 9),
+    ("eth", 18),
+    ("fri", 0),
+    ("gfri", 9),
+    ("strk", 18),
+];
+
+/// Parses a number passed as a CLI argument.
+/// Accepts hex values, decimal values with `_` separators, scientific notation (e.g. `1e18`, `2.5e9`)
+/// and values with a unit suffix (e.g. `1gwei`, `0.001eth`) which are converted to the base unit (wei/fri)
+pub fn parse_number(value: &str) -> Result<FieldElement> {
+    let value = value.trim().replace('_', "");
+
+    if value.starts_with("0x") {
+        return FieldElement::from_hex_be(&value)
+            .with_context(|| format!("Failed to parse hex value = {value}"));
+    }
+
+    let suffix_start = value
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(value.len());
+    let (amount, suffix) = value.split_at(suffix_start);
+    let (exponent, unit) = split_exponent(suffix)
+        .with_context(|| format!("Failed to parse exponent of number = {value}"))?;
+
+    let unit_decimals = if unit.is_empty() {
+        0
+    } else {
+        UNITS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+            .map(|(_, decimals)| *decimals)
+            .ok_or_else(|| {
+                let accepted_units = UNITS
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                anyhow!("Invalid unit = {unit}. Accepted units are: {accepted_units}")
+            })?
+    };
+    let decimals = unit_decimals + exponent;
+    if decimals > MAX_DECIMALS {
+        bail!("Value = {value} is out of the field range");
+    }
+
+    let (integer_part, fractional_part) = amount.split_once('.').unwrap_or((amount, ""));
+    if integer_part.is_empty() && fractional_part.is_empty() {
+        bail!("Failed to parse number = {value}");
+    }
+    if fractional_part.len() > decimals {
+        bail!("Value = {value} has too many decimal places");
+    }
+
+    let digits = format!(
+        "{integer_part}{fractional_part}{}",
+        "0".repeat(decimals - fractional_part.len())
+    );
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        bail!("Failed to parse number = {value}");
+    }
+
+    FieldElement::from_dec_str(&digits).with_context(|| format!("Failed to parse number = {value}"))
+}
+
+/// Splits the exponent of scientific notation (e.g. `e18` of `1e18eth`) from the unit,
+/// a suffix not starting with `e` followed by digits (e.g. `eth`) is only a unit
+fn split_exponent(suffix: &str) -> Result<(usize, &str)> {
+    let Some(exponent) = suffix.strip_prefix(['e', 'E']) else {
+        return Ok((0, suffix));
+    };
+    let exponent = exponent.strip_prefix('+').unwrap_or(exponent);
+    let digits_end = exponent
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(exponent.len());
+    if digits_end == 0 {
+        return Ok((0, suffix));
+    }
+    let (digits, unit) = exponent.split_at(digits_end);
+    Ok((digits.parse()?, unit))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawNumber {
+    Integer(u64),
+    Text(String),
+}
+
+/// Deserializes an optional number given either as an integer or as a string in a format accepted by [`parse_number`]
+pub fn deserialize_optional_number<'de, D>(
+    deserializer: D,
+) -> Result<Option<FieldElement>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<RawNumber>::deserialize(deserializer)? {
+        Some(RawNumber::Integer(value)) => Ok(Some(FieldElement::from(value))),
+        Some(RawNumber::Text(value)) => parse_number(&value).map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// Parses a felt requiring hex values to be prefixed with `0x` and decimal values to only contain digits,
+/// so no value is silently interpreted differently than intended
+pub fn parse_felt_strict(value: &str) -> Result<FieldElement> {
+    let (digits, radix) = match value.strip_prefix("0x") {
+        Some(hex_digits) => (hex_digits, 16),
+        None => (value, 10),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        bail!(
+            "Felt = {value} is ambiguous, hex values have to be prefixed with `0x` and decimal values may only contain digits"
+        );
+    }
+    FieldElement::from_str(value).map_err(|_| anyhow!("Felt = {value} is out of the field range"))
+}
+
+/// Parses calldata passed as separate CLI arguments
+pub fn parse_calldata_args(values: &[String], strict: bool) -> Result<Vec<FieldElement>> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let felt = if strict {
+                parse_felt_strict(value)
+            } else {
+                FieldElement::from_str(value).map_err(|err| anyhow!(err))
+            };
+            felt.with_context(|| {
+                format!(
+                    "Invalid felt = {value} at position {} of calldata",
+                    index + 1
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parses calldata passed as felts separated by whitespace, e.g. output of `call --raw`
+pub fn parse_calldata(input: &str, strict: bool) -> Result<Vec<FieldElement>> {
+    if input.trim().is_empty() {
+        bail!("No calldata was passed on stdin");
+    }
+
+    input
+        .split_whitespace()
+        .enumerate()
+        .map(|(index, value)| {
+            let felt = if strict {
+                parse_felt_strict(value)
+            } else {
+                FieldElement::from_str(value).map_err(|err| anyhow!(err))
+            };
+            felt.with_context(|| {
+                format!(
+                    "Invalid felt = {value} at position {} of calldata passed on stdin",
+                    index + 1
+                )
+            })
+        })
+        .collect()
+}
+
+pub fn read_calldata_from_stdin(strict: bool) -> Result<Vec<FieldElement>> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read calldata from stdin")?;
+
+    parse_calldata(&input, strict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deserialize_optional_number, parse_calldata, parse_felt_strict, parse_number};
+    use starknet::core::types::FieldElement;
+
+    #[test]
+    fn test_parse_number_plain() {
+        assert_eq!(parse_number("1000").unwrap(), FieldElement::from(1000_u32));
+        assert_eq!(parse_number("0x10").unwrap(), FieldElement::from(16_u8));
+    }
+
+    #[test]
+    fn test_parse_number_with_underscores() {
+        assert_eq!(
+            parse_number("1_000_000_000_000_000").unwrap(),
+            FieldElement::from(1_000_000_000_000_000_u64)
+        );
+    }
+
+    #[test]
+    fn test_parse_number_with_units() {
+        assert_eq!(
+            parse_number("1gwei").unwrap(),
+            FieldElement::from(1_000_000_000_u64)
+        );
+        assert_eq!(
+            parse_number("0.001eth").unwrap(),
+            FieldElement::from(1_000_000_000_000_000_u64)
+        );
+        assert_eq!(
+            parse_number("2.5strk").unwrap(),
+            FieldElement::from(2_500_000_000_000_000_000_u128)
+        );
+    }
+
+    #[test]
+    fn test_parse_number_scientific_notation() {
+        assert_eq!(
+            parse_number("1e18").unwrap(),
+            FieldElement::from(1_000_000_000_000_000_000_u64)
+        );
+        assert_eq!(
+            parse_number("2.5E+9").unwrap(),
+            FieldElement::from(2_500_000_000_u64)
+        );
+        assert_eq!(
+            parse_number("1.5e3gwei").unwrap(),
+            FieldElement::from(1_500_000_000_000_u64)
+        );
+        assert_eq!(
+            parse_number("1eth").unwrap(),
+            FieldElement::from(1_000_000_000_000_000_000_u64)
+        );
+
+        let err = parse_number("1.25e1").unwrap_err();
+        assert!(err.to_string().contains("has too many decimal places"));
+        let err = parse_number("1e1000").unwrap_err();
+        assert!(err.to_string().contains("is out of the field range"));
+    }
+
+    #[test]
+    fn test_deserialize_optional_number() {
+        #[derive(serde::Deserialize)]
+        struct Call {
+            #[serde(default, deserialize_with = "deserialize_optional_number")]
+            max_fee: Option<FieldElement>,
+        }
+
+        for (toml, expected) in [
+            ("max_fee = 1000", Some(FieldElement::from(1000_u32))),
+            ("max_fee = \"1_000\"", Some(FieldElement::from(1000_u32))),
+            (
+                "max_fee = \"1e3gwei\"",
+                Some(FieldElement::from(1_000_000_000_000_u64)),
+            ),
+            ("", None),
+        ] {
+            let call: Call = toml::from_str(toml).unwrap();
+            assert_eq!(call.max_fee, expected, "{toml}");
+        }
+    }
+
+    #[test]
+    fn test_parse_number_invalid_unit() {
+        let err = parse_number("1btc").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid unit = btc. Accepted units are: wei, gwei, eth, fri, gfri, strk"));
+    }
+
+    #[test]
+    fn test_parse_number_too_many_decimal_places() {
+        let err = parse_number("0.5wei").unwrap_err();
+        assert!(err.to_string().contains("has too many decimal places"));
+    }
+
+    #[test]
+    fn test_parse_calldata() {
+        assert_eq!(
+            parse_calldata("0x1 2\n  0x3\n", false).unwrap(),
+            vec![
+                FieldElement::ONE,
+                FieldElement::TWO,
+                FieldElement::from(3_u8)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_calldata_not_scaled() {
+        // Calldata is read like `--calldata`, units and scientific notation of amounts are not accepted
+        assert!(parse_calldata("1e18", false).is_err());
+        assert!(parse_calldata("2eth", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_calldata_empty() {
+        let err = parse_calldata(" \n", false).unwrap_err();
+        assert_eq!(err.to_string(), "No calldata was passed on stdin");
+    }
+
+    #[test]
+    fn test_parse_calldata_invalid_felt() {
+        let err = parse_calldata("0x1 0xz", false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid felt = 0xz at position 2 of calldata passed on stdin"
+        );
+    }
+
+    #[test]
+    fn test_parse_felt_strict() {
+        assert_eq!(
+            parse_felt_strict("0x10").unwrap(),
+            FieldElement::from(16_u8)
+        );
+        assert_eq!(parse_felt_strict("10").unwrap(), FieldElement::from(10_u8));
+
+        for value in ["ff", "1_000", "1gwei", "0x"] {
+            let err = parse_felt_strict(value).unwrap_err();
+            assert!(err.to_string().contains("is ambiguous"), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_parse_felt_strict_out_of_range() {
+        let value = "0x800000000000011000000000000000000000000000000000000000000000001";
+        let err = parse_felt_strict(value).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Felt = {value} is out of the field range")
+        );
+    }
+}
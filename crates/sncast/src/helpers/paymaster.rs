@@ -0,0 +1,380 @@
+use crate::decode_chain_id;
+use crate::helpers::signer::CastSigner;
+use anyhow::{bail, Context, Result};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use starknet::accounts::Call;
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{cairo_short_string_to_felt, starknet_keccak};
+use starknet::signers::Signer;
+use starknet_crypto::poseidon_hash_many;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+/// Header carrying the API key of the paymaster service
+const PAYMASTER_TOKEN_HEADER: &str = "x-paymaster-api-key";
+/// Time the paymaster has to submit the transaction before its signature expires
+const OUTSIDE_EXECUTION_VALIDITY: Duration = Duration::from_secs(60 * 60);
+/// Time the paymaster has to respond before the request is abandoned
+const PAYMASTER_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const STARKNET_DOMAIN_TYPE: &str = r#""StarknetDomain"("name":"shortstring","version":"shortstring","chainId":"shortstring","revision":"shortstring")"#;
+const OUTSIDE_EXECUTION_TYPE: &str = r#""OutsideExecution"("Caller":"ContractAddress","Nonce":"felt","Execute After":"u128","Execute Before":"u128","Calls":"Call*")"Call"("To":"ContractAddress","Selector":"selector","Calldata":"felt*")"#;
+const CALL_TYPE: &str =
+    r#""Call"("To":"ContractAddress","Selector":"selector","Calldata":"felt*")"#;
+const OUTSIDE_EXECUTION_DOMAIN_NAME: &str = "Account.execute_from_outside";
+const ANY_CALLER: &str = "ANY_CALLER";
+
+/// Paymaster service (SNIP-29) submitting transactions of the account and paying their fees
+#[derive(Clone, Debug)]
+pub struct Paymaster {
+    pub url: Url,
+    pub token: Option<String>,
+}
+
+impl Paymaster {
+    /// Host of the paymaster, which unlike the URL can not contain credentials
+    #[must_use]
+    pub fn host(&self) -> String {
+        self.url.host_str().unwrap_or_default().to_string()
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse {
+    result: Option<ExecuteTransactionResult>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct ExecuteTransactionResult {
+    transaction_hash: FieldElement,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// Outside execution (SNIP-9, version 2) of the calls, which any caller can submit until `execute_before`
+struct OutsideExecution {
+    caller: FieldElement,
+    nonce: FieldElement,
+    execute_after: u64,
+    execute_before: u64,
+    calls: Vec<Call>,
+}
+
+impl OutsideExecution {
+    /// Hash of the outside execution as typed data (SNIP-12, revision 1), signed by the account
+    fn message_hash(
+        &self,
+        account_address: FieldElement,
+        chain_id: FieldElement,
+    ) -> Result<FieldElement> {
+        let domain_hash = poseidon_hash_many(&[
+            starknet_keccak(STARKNET_DOMAIN_TYPE.as_bytes()),
+            cairo_short_string_to_felt(OUTSIDE_EXECUTION_DOMAIN_NAME)?,
+            // version
+            FieldElement::TWO,
+            chain_id,
+            // revision
+            FieldElement::ONE,
+        ]);
+        let call_hashes: Vec<FieldElement> = self
+            .calls
+            .iter()
+            .map(|call| {
+                poseidon_hash_many(&[
+                    starknet_keccak(CALL_TYPE.as_bytes()),
+                    call.to,
+                    call.selector,
+                    poseidon_hash_many(&call.calldata),
+                ])
+            })
+            .collect();
+        let struct_hash = poseidon_hash_many(&[
+            starknet_keccak(OUTSIDE_EXECUTION_TYPE.as_bytes()),
+            self.caller,
+            self.nonce,
+            FieldElement::from(self.execute_after),
+            FieldElement::from(self.execute_before),
+            poseidon_hash_many(&call_hashes),
+        ]);
+
+        Ok(poseidon_hash_many(&[
+            cairo_short_string_to_felt("StarkNet Message")?,
+            domain_hash,
+            account_address,
+            struct_hash,
+        ]))
+    }
+
+    /// The outside execution as typed data, in the format sent to the paymaster
+    fn typed_data(&self, chain_id: FieldElement) -> Value {
+        let calls: Vec<Value> = self
+            .calls
+            .iter()
+            .map(|call| {
+                json!({
+                    "To": format!("{:#x}", call.to),
+                    "Selector": format!("{:#x}", call.selector),
+                    "Calldata": call.calldata.iter().map(|felt| format!("{felt:#x}")).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+
+        json!({
+            "types": {
+                "StarknetDomain": [
+                    { "name": "name", "type": "shortstring" },
+                    { "name": "version", "type": "shortstring" },
+                    { "name": "chainId", "type": "shortstring" },
+                    { "name": "revision", "type": "shortstring" },
+                ],
+                "OutsideExecution": [
+                    { "name": "Caller", "type": "ContractAddress" },
+                    { "name": "Nonce", "type": "felt" },
+                    { "name": "Execute After", "type": "u128" },
+                    { "name": "Execute Before", "type": "u128" },
+                    { "name": "Calls", "type": "Call*" },
+                ],
+                "Call": [
+                    { "name": "To", "type": "ContractAddress" },
+                    { "name": "Selector", "type": "selector" },
+                    { "name": "Calldata", "type": "felt*" },
+                ],
+            },
+            "primaryType": "OutsideExecution",
+            "domain": {
+                "name": OUTSIDE_EXECUTION_DOMAIN_NAME,
+                "version": "2",
+                "chainId": decode_chain_id(chain_id),
+                "revision": "1",
+            },
+            "message": {
+                "Caller": format!("{:#x}", self.caller),
+                "Nonce": format!("{:#x}", self.nonce),
+                "Execute After": format!("{:#x}", self.execute_after),
+                "Execute Before": format!("{:#x}", self.execute_before),
+                "Calls": calls,
+            },
+        })
+    }
+}
+
+/// Signs the calls as an outside execution of the account and sends them to the paymaster,
+/// which submits them in a transaction it pays for. Returns hash of the transaction
+pub async fn execute_sponsored(
+    paymaster: &Paymaster,
+    calls: Vec<Call>,
+    account_address: FieldElement,
+    chain_id: FieldElement,
+    signer: &CastSigner,
+) -> Result<FieldElement> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System time is before the Unix epoch")?;
+    let outside_execution = OutsideExecution {
+        caller: cairo_short_string_to_felt(ANY_CALLER)?,
+        nonce: FieldElement::from(OsRng.next_u64()),
+        execute_after: 0,
+        execute_before: (now + OUTSIDE_EXECUTION_VALIDITY).as_secs(),
+        calls,
+    };
+    let signature = signer
+        .sign_hash(&outside_execution.message_hash(account_address, chain_id)?)
+        .await?;
+
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "paymaster_executeTransaction",
+        "params": {
+            "transaction": {
+                "type": "invoke",
+                "invoke": {
+                    "user_address": format!("{account_address:#x}"),
+                    "typed_data": outside_execution.typed_data(chain_id),
+                    "signature": [format!("{:#x}", signature.r), format!("{:#x}", signature.s)],
+                },
+            },
+            "parameters": {
+                "version": "0x1",
+                "fee_mode": { "mode": "sponsored" },
+            },
+        },
+    });
+    let mut request = reqwest::Client::builder()
+        .timeout(PAYMASTER_REQUEST_TIMEOUT)
+        .build()
+        .context("Failed to create the paymaster client")?
+        .post(paymaster.url.clone())
+        .header("Content-Type", "application/json")
+        .body(body.to_string());
+    if let Some(token) = &paymaster.token {
+        request = request.header(PAYMASTER_TOKEN_HEADER, token);
+    }
+    let response = request.send().await.with_context(|| {
+        format!(
+            "Failed to send the transaction to paymaster = {}",
+            paymaster.url
+        )
+    })?;
+    let status = response.status();
+    let response: JsonRpcResponse =
+        serde_json::from_str(&response.text().await?).with_context(|| {
+            format!(
+                "Paymaster = {} returned an invalid response with status {status}",
+                paymaster.url
+            )
+        })?;
+
+    match response {
+        JsonRpcResponse {
+            error: Some(error), ..
+        } => bail!(
+            "Paymaster = {} rejected the transaction: {} (code {})",
+            paymaster.url,
+            error.message,
+            error.code
+        ),
+        JsonRpcResponse {
+            result: Some(result),
+            ..
+        } => Ok(result.transaction_hash),
+        JsonRpcResponse { .. } => bail!(
+            "Paymaster = {} returned neither a result nor an error",
+            paymaster.url
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        execute_sponsored, OutsideExecution, Paymaster, CALL_TYPE, OUTSIDE_EXECUTION_TYPE,
+        STARKNET_DOMAIN_TYPE,
+    };
+    use crate::helpers::signer::CastSigner;
+    use starknet::accounts::Call;
+    use starknet::core::types::FieldElement;
+    use starknet::core::utils::starknet_keccak;
+    use starknet::macros::felt;
+    use starknet::signers::{LocalWallet, SigningKey};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn outside_execution() -> OutsideExecution {
+        OutsideExecution {
+            caller: FieldElement::ONE,
+            nonce: FieldElement::TWO,
+            execute_after: 0,
+            execute_before: 16,
+            calls: vec![Call {
+                to: FieldElement::from(3_u8),
+                selector: FieldElement::ONE,
+                calldata: vec![FieldElement::TWO],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_type_hashes() {
+        // Values of `OUTSIDE_EXECUTION_TYPE_HASH` and `CALL_TYPE_HASH` of SNIP-9, version 2
+        assert_eq!(
+            starknet_keccak(OUTSIDE_EXECUTION_TYPE.as_bytes()),
+            felt!("0x312b56c05a7965066ddbda31c016d8d05afc305071c0ca3cdc2192c3c2f1f0f")
+        );
+        assert_eq!(
+            starknet_keccak(CALL_TYPE.as_bytes()),
+            felt!("0x3635c7f2a7ba93844c0d064e18e487f35ab90f7c39d00f186a781fc3f0c2ca9")
+        );
+    }
+
+    #[test]
+    fn test_domain_type_hash() {
+        // Type hash of `StarknetDomain` of SNIP-12, revision 1
+        assert_eq!(
+            starknet_keccak(STARKNET_DOMAIN_TYPE.as_bytes()),
+            felt!("0x1ff2f602e42168014d405a94f75e8a93d640751d71d16311266e140d8b0a210")
+        );
+    }
+
+    #[test]
+    fn test_message_hash_binds_account_and_chain() {
+        let outside_execution = outside_execution();
+        let account = felt!("0x123");
+        let sepolia = felt!("0x534e5f5345504f4c4941");
+        let mainnet = felt!("0x534e5f4d41494e");
+
+        let hash = outside_execution.message_hash(account, sepolia).unwrap();
+
+        assert_eq!(
+            hash,
+            outside_execution.message_hash(account, sepolia).unwrap()
+        );
+        assert_ne!(
+            hash,
+            outside_execution.message_hash(account, mainnet).unwrap()
+        );
+        assert_ne!(
+            hash,
+            outside_execution
+                .message_hash(felt!("0x456"), sepolia)
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_sponsorship() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = [0; 16 * 1024];
+            let _ = stream.read(&mut buffer).await.unwrap();
+            let body = r#"{"jsonrpc":"2.0","id":1,"error":{"code":163,"message":"Sponsorship not allowed"}}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+        let paymaster = Paymaster {
+            url: format!("http://{address}").parse().unwrap(),
+            token: None,
+        };
+        let signer = CastSigner::Local(LocalWallet::from(SigningKey::from_secret_scalar(
+            FieldElement::ONE,
+        )));
+
+        let error = execute_sponsored(
+            &paymaster,
+            outside_execution().calls,
+            felt!("0x123"),
+            felt!("0x534e5f5345504f4c4941"),
+            &signer,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("rejected the transaction: Sponsorship not allowed (code 163)"));
+    }
+
+    #[test]
+    fn test_typed_data_message() {
+        let outside_execution = outside_execution();
+
+        let typed_data = outside_execution.typed_data(felt!("0x534e5f5345504f4c4941"));
+
+        assert_eq!(typed_data["domain"]["chainId"], "SN_SEPOLIA");
+        assert_eq!(typed_data["message"]["Execute Before"], "0x10");
+        assert_eq!(typed_data["message"]["Calls"][0]["Calldata"][0], "0x2");
+    }
+}
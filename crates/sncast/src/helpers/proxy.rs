@@ -0,0 +1,131 @@
+use crate::helpers::abi::parse_abi;
+use crate::helpers::traced_transport::TracedTransport;
+use anyhow::{anyhow, bail, Context, Result};
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::{BlockId, ContractClass, FieldElement, FunctionCall};
+use starknet::core::utils::{get_selector_from_name, get_storage_var_address};
+use starknet::providers::{JsonRpcClient, Provider};
+
+/// Functions returning the implementation in common proxy contracts
+const IMPLEMENTATION_GETTERS: [&str; 3] = [
+    "get_implementation",
+    "implementation",
+    "get_implementation_hash",
+];
+
+/// Storage variables holding the implementation in common proxy contracts
+const IMPLEMENTATION_STORAGE_VARS: [&str; 2] = ["Proxy_implementation_hash", "_implementation"];
+
+/// Parses the storage slot holding the implementation of a proxy,
+/// passed either as a storage address (hex or decimal) or as a storage variable name
+pub fn parse_impl_slot(value: &str) -> Result<FieldElement> {
+    if value.starts_with("0x") {
+        return FieldElement::from_hex_be(value)
+            .with_context(|| format!("Failed to parse storage slot = {value}"));
+    }
+    if value.chars().all(|c| c.is_ascii_digit()) {
+        return FieldElement::from_dec_str(value)
+            .with_context(|| format!("Failed to parse storage slot = {value}"));
+    }
+    get_storage_var_address(value, &[])
+        .with_context(|| format!("Failed to compute storage address of variable = {value}"))
+}
+
+/// Returns the class hash of the implementation the proxy at given address delegates to.
+/// If `impl_slot` is not passed, getters and storage variables of common proxy patterns are tried
+pub async fn get_implementation_class_hash(
+    provider: &JsonRpcClient<TracedTransport>,
+    proxy_address: FieldElement,
+    block_id: &BlockId,
+    impl_slot: Option<FieldElement>,
+) -> Result<FieldElement> {
+    let mut candidates = vec![];
+    if let Some(slot) = impl_slot {
+        candidates.push(read_storage(provider, proxy_address, slot, block_id).await?);
+    } else {
+        for getter in IMPLEMENTATION_GETTERS {
+            let selector = get_selector_from_name(getter)?;
+            let call = FunctionCall {
+                contract_address: proxy_address,
+                entry_point_selector: selector,
+                calldata: vec![],
+            };
+            if let Ok(response) = provider.call(call, block_id).await {
+                candidates.extend(response.first());
+            }
+        }
+        for variable in IMPLEMENTATION_STORAGE_VARS {
+            let slot = get_storage_var_address(variable, &[])?;
+            candidates.push(read_storage(provider, proxy_address, slot, block_id).await?);
+        }
+    }
+
+    for candidate in candidates
+        .into_iter()
+        .filter(|value| *value != FieldElement::ZERO)
+    {
+        if provider.get_class(block_id, candidate).await.is_ok() {
+            return Ok(candidate);
+        }
+        // Some proxies store the address of the implementation contract instead of its class hash
+        if let Ok(class_hash) = provider.get_class_hash_at(block_id, candidate).await {
+            return Ok(class_hash);
+        }
+    }
+
+    match impl_slot {
+        Some(slot) => bail!(
+            "Failed to resolve the implementation of proxy = {proxy_address:#x}: storage slot = {slot:#x} does not hold a class hash nor an address of a contract"
+        ),
+        None => bail!(
+            "Failed to resolve the implementation of proxy = {proxy_address:#x}: none of the common proxy patterns matched, pass the storage slot holding the implementation with `--impl-slot`"
+        ),
+    }
+}
+
+/// Returns the ABI of the implementation the proxy at given address delegates to
+pub async fn get_implementation_abi(
+    provider: &JsonRpcClient<TracedTransport>,
+    proxy_address: FieldElement,
+    block_id: &BlockId,
+    impl_slot: Option<FieldElement>,
+) -> Result<Vec<AbiEntry>> {
+    let class_hash =
+        get_implementation_class_hash(provider, proxy_address, block_id, impl_slot).await?;
+
+    match provider.get_class(block_id, class_hash).await {
+        Ok(ContractClass::Sierra(class)) => parse_abi(&class.abi),
+        Ok(ContractClass::Legacy(_)) => Err(anyhow!(
+            "ABI of Cairo 0 implementation class with hash {class_hash:#x} is not supported"
+        )),
+        Err(err) => Err(crate::handle_rpc_error(err)),
+    }
+}
+
+async fn read_storage(
+    provider: &JsonRpcClient<TracedTransport>,
+    address: FieldElement,
+    slot: FieldElement,
+    block_id: &BlockId,
+) -> Result<FieldElement> {
+    provider
+        .get_storage_at(address, slot, block_id)
+        .await
+        .map_err(crate::handle_rpc_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_impl_slot() {
+        assert_eq!(parse_impl_slot("0x10").unwrap(), FieldElement::from(16_u8));
+        assert_eq!(parse_impl_slot("16").unwrap(), FieldElement::from(16_u8));
+        assert_eq!(
+            parse_impl_slot("Proxy_implementation_hash").unwrap(),
+            get_storage_var_address("Proxy_implementation_hash", &[]).unwrap()
+        );
+        assert!(parse_impl_slot("0xz").is_err());
+    }
+}
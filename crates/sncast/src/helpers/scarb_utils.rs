@@ -1,15 +1,20 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use scarb_api::{
-    get_contracts_artifacts_and_source_sierra_paths,
+    get_contracts_artifacts_by_module_path,
     metadata::{Metadata, MetadataCommand, PackageMetadata},
-    ScarbCommand, ScarbCommandError, StarknetContractArtifacts,
+    ScarbCommand, StarknetContractArtifacts,
 };
 use scarb_ui::args::PackagesFilter;
+use semver::Version;
 use shared::{command::CommandExt, print::print_as_warning};
-use std::collections::HashMap;
-use std::env;
+use starknet::core::types::FieldElement;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
 use std::str::FromStr;
+use std::{env, fs};
 
 pub fn get_scarb_manifest() -> Result<Utf8PathBuf> {
     get_scarb_manifest_for(<&Utf8Path>::from("."))
@@ -34,6 +39,126 @@ pub fn get_scarb_manifest_for(dir: &Utf8Path) -> Result<Utf8PathBuf> {
     Ok(path)
 }
 
+/// Reads `default-profile` from `[tool.sncast]` section of the manifest found with [`read_scarb_manifest`]
+pub fn get_default_profile_from_scarb() -> Result<Option<String>> {
+    match read_scarb_manifest()? {
+        Some((manifest_path, manifest)) => default_profile_from_manifest(&manifest_path, &manifest),
+        None => Ok(None),
+    }
+}
+
+/// Reads the manifest Scarb would use, without invoking Scarb: the one at `SCARB_MANIFEST_PATH`
+/// if it is set, otherwise the closest `Scarb.toml` in the current directory or its ancestors
+pub fn read_scarb_manifest() -> Result<Option<(Utf8PathBuf, toml::Value)>> {
+    if let Some(manifest_path) = env::var_os("SCARB_MANIFEST_PATH") {
+        let manifest_path = Utf8PathBuf::try_from(PathBuf::from(manifest_path))
+            .context("Failed to convert `SCARB_MANIFEST_PATH` to Utf8PathBuf")?;
+        let manifest = read_manifest(&manifest_path)?;
+        return Ok(Some((manifest_path, manifest)));
+    }
+
+    let current_dir = Utf8PathBuf::try_from(env::current_dir()?)
+        .context("Failed to convert current directory to Utf8PathBuf")?;
+    read_closest_scarb_manifest(&current_dir)
+}
+
+/// Reads the closest `Scarb.toml` in the directory or its ancestors, without invoking Scarb
+pub fn read_closest_scarb_manifest(dir: &Utf8Path) -> Result<Option<(Utf8PathBuf, toml::Value)>> {
+    let Some(manifest_path) = dir
+        .ancestors()
+        .map(|path| path.join("Scarb.toml"))
+        .find(|path| path.exists())
+    else {
+        return Ok(None);
+    };
+
+    let manifest = read_manifest(&manifest_path)?;
+    Ok(Some((manifest_path, manifest)))
+}
+
+fn read_manifest(manifest_path: &Utf8Path) -> Result<toml::Value> {
+    fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read {manifest_path}"))?
+        .parse::<toml::Value>()
+        .with_context(|| format!("Failed to parse {manifest_path}"))
+}
+
+/// Reads `default-profile` from `[tool.sncast]` section of the closest `Scarb.toml`
+pub fn get_default_profile_from_scarb_for(dir: &Utf8Path) -> Result<Option<String>> {
+    match read_closest_scarb_manifest(dir)? {
+        Some((manifest_path, manifest)) => default_profile_from_manifest(&manifest_path, &manifest),
+        None => Ok(None),
+    }
+}
+
+fn default_profile_from_manifest(
+    manifest_path: &Utf8Path,
+    manifest: &toml::Value,
+) -> Result<Option<String>> {
+    match manifest
+        .get("tool")
+        .and_then(|tool| tool.get("sncast"))
+        .and_then(|sncast| sncast.get("default-profile"))
+    {
+        Some(toml::Value::String(profile)) => Ok(Some(profile.clone())),
+        Some(_) => Err(anyhow!(
+            "Field `tool.sncast.default-profile` in {manifest_path} must be a string"
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Reads named class hashes and addresses from `[tool.sncast.contracts]` section of the package manifest
+pub fn get_contracts_from_package(
+    package_metadata: &PackageMetadata,
+) -> Result<HashMap<String, FieldElement>> {
+    let Some(contracts) = package_metadata
+        .tool_metadata("sncast")
+        .and_then(|sncast| sncast.get("contracts"))
+    else {
+        return Ok(HashMap::new());
+    };
+
+    contracts
+        .as_object()
+        .context("Field `tool.sncast.contracts` must be a table")?
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .as_str()
+                .and_then(|value| FieldElement::from_str(value).ok())
+                .with_context(|| {
+                    format!("Field `tool.sncast.contracts.{name}` must be a string with a class hash or an address")
+                })?;
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Reads names of environment variables which scripts are allowed to read from `allowed-env-vars`
+/// in `[tool.sncast]` section of the package manifest
+pub fn get_allowed_env_vars_from_package(
+    package_metadata: &PackageMetadata,
+) -> Result<HashSet<String>> {
+    let Some(allowed_env_vars) = package_metadata
+        .tool_metadata("sncast")
+        .and_then(|sncast| sncast.get("allowed-env-vars"))
+    else {
+        return Ok(HashSet::new());
+    };
+
+    allowed_env_vars
+        .as_array()
+        .context("Field `tool.sncast.allowed-env-vars` must be an array")?
+        .iter()
+        .map(|name| {
+            name.as_str().map(String::from).with_context(|| {
+                format!("Field `tool.sncast.allowed-env-vars` must contain names of environment variables, got {name}")
+            })
+        })
+        .collect()
+}
+
 fn get_scarb_metadata_command(manifest_path: &Utf8PathBuf) -> Result<MetadataCommand> {
     ScarbCommand::new().ensure_available()?;
 
@@ -82,13 +207,19 @@ pub fn assert_manifest_path_exists() -> Result<Utf8PathBuf> {
     Ok(manifest_path)
 }
 
+/// Packages which are members of the workspace; metadata with dependencies also lists the packages they depend on
+fn workspace_packages(metadata: &Metadata) -> impl Iterator<Item = &PackageMetadata> {
+    metadata
+        .packages
+        .iter()
+        .filter(|package| metadata.workspace.members.contains(&package.id))
+}
+
 fn get_package_metadata_by_name<'a>(
     metadata: &'a Metadata,
     package_name: &str,
 ) -> Result<&'a PackageMetadata> {
-    metadata
-        .packages
-        .iter()
+    workspace_packages(metadata)
         .find(|package| package.name == package_name)
         .ok_or(anyhow!(
             "Package {} not found in scarb metadata",
@@ -97,7 +228,7 @@ fn get_package_metadata_by_name<'a>(
 }
 
 fn get_default_package_metadata(metadata: &Metadata) -> Result<&PackageMetadata> {
-    match metadata.packages.iter().collect::<Vec<_>>().as_slice() {
+    match workspace_packages(metadata).collect::<Vec<_>>().as_slice() {
         [package] => Ok(package),
         [] => Err(anyhow!("No package found in scarb metadata")),
         _ => Err(anyhow!(
@@ -111,74 +242,228 @@ pub fn get_package_metadata(
     package_name: &Option<String>,
 ) -> Result<PackageMetadata> {
     let metadata = get_scarb_metadata(manifest_path)?;
+    select_package_metadata(&metadata, package_name)
+}
+
+/// Selects the package from already obtained metadata, which may also list dependencies of the workspace
+pub fn select_package_metadata(
+    metadata: &Metadata,
+    package_name: &Option<String>,
+) -> Result<PackageMetadata> {
     match &package_name {
-        Some(package_name) => Ok(get_package_metadata_by_name(&metadata, package_name)?.clone()),
-        None => Ok(get_default_package_metadata(&metadata)?.clone()),
+        Some(package_name) => Ok(get_package_metadata_by_name(metadata, package_name)?.clone()),
+        None => Ok(get_default_package_metadata(metadata)?.clone()),
     }
 }
 
-pub struct BuildConfig {
+/// Selects the version of Scarb run through asdf shims
+const ASDF_SCARB_VERSION_ENV_VAR: &str = "ASDF_SCARB_VERSION";
+
+pub struct BuildConfig<'a> {
     pub scarb_toml_path: Utf8PathBuf,
+    /// Metadata of the workspace with dependencies, obtained once and shared with the config resolution
+    pub metadata: &'a Metadata,
     pub json: bool,
     pub profile: String,
+    /// Scarb profile used for the build; if not provided, `profile` is used when it exists in Scarb and `dev` otherwise
+    pub build_profile: Option<String>,
+    /// Name of the package target to build; all targets are built if not provided
+    pub target: Option<String>,
+    /// Version of Scarb used for the build; the default one is used if not provided
+    pub scarb_version: Option<Version>,
+    /// If true, artifacts of previous builds are removed before building
+    pub force_rebuild: bool,
+    /// If true, the package is not built and existing artifacts are used
+    pub no_build: bool,
+    /// Extra arguments passed to `scarb build`
+    pub build_args: Vec<String>,
 }
 
-pub fn build(package: &PackageMetadata, config: &BuildConfig) -> Result<(), ScarbCommandError> {
+/// Checks that the explicitly requested Scarb profile and target exist
+fn validate_build_config(
+    package: &PackageMetadata,
+    metadata: &Metadata,
+    config: &BuildConfig,
+) -> Result<()> {
+    if let Some(profile) = &config.build_profile {
+        if !metadata.profiles.contains(profile) {
+            bail!(
+                "Profile = {profile} does not exist in Scarb. Available profiles: {}",
+                metadata.profiles.join(", ")
+            );
+        }
+    }
+    if let Some(target) = &config.target {
+        let targets: Vec<&str> = package
+            .targets
+            .iter()
+            .map(|package_target| package_target.name.as_str())
+            .collect();
+        if !targets.contains(&target.as_str()) {
+            bail!(
+                "Target = {target} does not exist in package = {}. Available targets: {}",
+                package.name,
+                targets.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the requested Scarb version can be selected, listing the installed versions otherwise
+pub fn verify_scarb_version(version: &Version) -> Result<()> {
+    let reported_version = ScarbCommand::new()
+        .arg("--version")
+        .env(ASDF_SCARB_VERSION_ENV_VAR, version.to_string())
+        .command()
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let version = stdout.split_whitespace().nth(1)?;
+            Version::parse(version).ok()
+        });
+    if reported_version.as_ref() == Some(version) {
+        return Ok(());
+    }
+
+    match installed_scarb_versions() {
+        Some(installed) if !installed.is_empty() => bail!(
+            "Scarb version = {version} is not installed. Installed versions: {}",
+            installed.join(", ")
+        ),
+        _ => bail!(
+            "Scarb version = {version} is not available. Selecting Scarb version requires Scarb installed with asdf (https://asdf-vm.com)"
+        ),
+    }
+}
+
+fn installed_scarb_versions() -> Option<Vec<String>> {
+    let output = Command::new("asdf")
+        .args(["list", "scarb"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').to_string())
+            .filter(|line| !line.is_empty())
+            .collect(),
+    )
+}
+
+/// Builds the package. The output of Scarb is captured, so that the diagnostics
+/// are included verbatim in the error if the build fails
+pub fn build(package: &PackageMetadata, config: &BuildConfig) -> Result<()> {
     let filter = PackagesFilter::generate_for::<Metadata>([package].into_iter());
 
-    let mut cmd = ScarbCommand::new_with_stdio();
-    let metadata =
-        get_scarb_metadata_with_deps(&config.scarb_toml_path).expect("Failed to obtain metadata");
-    let profile = if metadata.profiles.contains(&config.profile) {
-        &config.profile
-    } else {
-        "dev"
+    let mut cmd = ScarbCommand::new();
+    let profile = match &config.build_profile {
+        Some(build_profile) => build_profile,
+        None if config.metadata.profiles.contains(&config.profile) => &config.profile,
+        None => "dev",
     };
+    tracing::info!(
+        package = %package.name,
+        profile,
+        target = ?config.target,
+        "Building package with Scarb"
+    );
     cmd.arg("--profile")
         .arg(profile)
         .arg("build")
         .manifest_path(&config.scarb_toml_path)
         .packages_filter(filter);
+    if let Some(target) = &config.target {
+        cmd.arg("--target-names").arg(target);
+    }
+    cmd.args(&config.build_args);
 
     if config.json {
         cmd.json();
     }
-    cmd.run()
+    if let Some(scarb_version) = &config.scarb_version {
+        cmd.env(ASDF_SCARB_VERSION_ENV_VAR, scarb_version.to_string());
+    }
+    let output = cmd
+        .command()
+        .output()
+        .context("Failed to run `scarb build`")?;
+    if !output.status.success() {
+        let diagnostics = [output.stdout, output.stderr]
+            .iter()
+            .map(|stream| String::from_utf8_lossy(stream).trim_end().to_string())
+            .filter(|stream| !stream.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        bail!("`scarb` exited with error\n{diagnostics}");
+    }
+    // Output and warnings are still shown when the build succeeds
+    io::stdout().write_all(&output.stdout)?;
+    io::stderr().write_all(&output.stderr)?;
+    Ok(())
 }
 
 pub fn build_and_load_artifacts(
     package: &PackageMetadata,
     config: &BuildConfig,
 ) -> Result<HashMap<String, StarknetContractArtifacts>> {
+    validate_build_config(package, config.metadata, config)?;
+    if config.no_build {
+        return load_artifacts(package, config).context(
+            "Failed to load artifacts of the package, build it first or run without `--no-build`",
+        );
+    }
+    if config.force_rebuild {
+        clean(config)?;
+    }
     build(package, config).map_err(|e| anyhow!(format!("Failed to build using scarb; {e}")))?;
 
-    let metadata = get_scarb_metadata_with_deps(&config.scarb_toml_path)?;
-    if metadata.profiles.contains(&config.profile) {
-        Ok(get_contracts_artifacts_and_source_sierra_paths(
-            &metadata,
-            &package.id,
-            Some(&config.profile),
-        )?
-        .into_iter()
-        .map(|(name, (artifacts, _))| (name, artifacts))
-        .collect())
+    load_artifacts(package, config)
+}
+
+/// Removes artifacts of previous builds, so the next build starts from scratch
+fn clean(config: &BuildConfig) -> Result<()> {
+    let mut cmd = ScarbCommand::new_with_stdio();
+    cmd.arg("clean").manifest_path(&config.scarb_toml_path);
+    if let Some(scarb_version) = &config.scarb_version {
+        cmd.env(ASDF_SCARB_VERSION_ENV_VAR, scarb_version.to_string());
+    }
+    cmd.run()
+        .map_err(|e| anyhow!(format!("Failed to clean using scarb; {e}")))
+}
+
+fn load_artifacts(
+    package: &PackageMetadata,
+    config: &BuildConfig,
+) -> Result<HashMap<String, StarknetContractArtifacts>> {
+    let metadata = config.metadata;
+    if let Some(build_profile) = &config.build_profile {
+        get_contracts_artifacts_by_module_path(metadata, &package.id, Some(build_profile))
+    } else if metadata.profiles.contains(&config.profile) {
+        get_contracts_artifacts_by_module_path(metadata, &package.id, Some(&config.profile))
     } else {
         let profile = &config.profile;
         print_as_warning(&anyhow!(
             "Profile {profile} does not exist in scarb, using default 'dev' profile."
         ));
-        Ok(
-            get_contracts_artifacts_and_source_sierra_paths(&metadata, &package.id, None)?
-                .into_iter()
-                .map(|(name, (artifacts, _))| (name, artifacts))
-                .collect(),
-        )
+        get_contracts_artifacts_by_module_path(metadata, &package.id, None)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers::scarb_utils::{get_package_metadata, get_scarb_metadata};
+    use crate::helpers::scarb_utils::{
+        get_default_profile_from_scarb_for, get_package_metadata, get_scarb_metadata,
+        get_scarb_metadata_with_deps, select_package_metadata,
+    };
+    use camino::Utf8PathBuf;
+    use indoc::indoc;
+    use std::fs;
+    use tempfile::tempdir;
 
     #[test]
     fn test_get_scarb_metadata() {
@@ -211,6 +496,14 @@ mod tests {
         assert_eq!(metadata.name, "package2");
     }
 
+    #[test]
+    fn test_select_package_metadata_skips_dependencies() {
+        let metadata =
+            get_scarb_metadata_with_deps(&"tests/data/contracts/map/Scarb.toml".into()).unwrap();
+        let package = select_package_metadata(&metadata, &None).unwrap();
+        assert_eq!(package.name, "map");
+    }
+
     #[test]
     #[should_panic(
         expected = "More than one package found in scarb metadata - specify package using --package flag"
@@ -233,4 +526,35 @@ mod tests {
         .unwrap();
         assert_eq!(metadata.name, "package2");
     }
+
+    #[test]
+    fn test_get_default_profile_from_scarb() {
+        let tempdir = tempdir().unwrap();
+        fs::write(
+            tempdir.path().join("Scarb.toml"),
+            indoc! {r#"
+                [package]
+                name = "example"
+                version = "0.1.0"
+
+                [tool.sncast]
+                default-profile = "staging"
+            "#},
+        )
+        .unwrap();
+        fs::create_dir(tempdir.path().join("src")).unwrap();
+
+        let profile = get_default_profile_from_scarb_for(
+            &Utf8PathBuf::try_from(tempdir.path().join("src")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(profile, Some(String::from("staging")));
+    }
+
+    #[test]
+    fn test_get_default_profile_from_scarb_not_set() {
+        let profile =
+            get_default_profile_from_scarb_for(&"tests/data/contracts/map".into()).unwrap();
+        assert_eq!(profile, None);
+    }
 }
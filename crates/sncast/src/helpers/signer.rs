@@ -0,0 +1,183 @@
+use async_trait::async_trait;
+use starknet::core::crypto::Signature;
+use starknet::core::types::FieldElement;
+use starknet::signers::{LocalWallet, Signer, VerifyingKey};
+use std::fmt::{Debug, Formatter};
+use std::process::Stdio;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Signer of the account used to send transactions
+#[derive(Clone)]
+pub enum CastSigner {
+    Local(LocalWallet),
+    External(ExternalSigner),
+}
+
+/// Delegates signing to an external command, e.g. a hardware wallet bridge.
+/// The command is run with `sh -c`, receives the hash to sign as a hex string on stdin
+/// and has to print `r` and `s` of the signature on stdout, separated by whitespace
+#[derive(Clone)]
+pub struct ExternalSigner {
+    command: String,
+    public_key: FieldElement,
+}
+
+// Delegates to the local wallet, so accounts using it keep their debug representation
+impl Debug for CastSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CastSigner::Local(wallet) => wallet.fmt(f),
+            CastSigner::External(signer) => signer.fmt(f),
+        }
+    }
+}
+
+// The command is left out, as it can contain credentials
+impl Debug for ExternalSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalSigner")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CastSignerError {
+    #[error("Failed to sign the transaction: {0}")]
+    Local(String),
+    #[error("Failed to run signer command = {command}: {reason}")]
+    CommandFailed { command: String, reason: String },
+    #[error("Signer command = {command} returned invalid signature = {output}, expected `r` and `s` separated by whitespace")]
+    InvalidSignature { command: String, output: String },
+}
+
+impl ExternalSigner {
+    #[must_use]
+    pub fn new(command: String, public_key: FieldElement) -> Self {
+        Self {
+            command,
+            public_key,
+        }
+    }
+
+    async fn run(&self, hash: &FieldElement) -> Result<String, CastSignerError> {
+        let command_failed = |reason: String| CastSignerError::CommandFailed {
+            command: self.command.clone(),
+            reason,
+        };
+
+        tracing::debug!(command = %self.command, "Running signer command");
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|err| command_failed(err.to_string()))?;
+
+        child
+            .stdin
+            .take()
+            .expect("Stdin of the signer command should be piped")
+            .write_all(format!("{hash:#x}\n").as_bytes())
+            .await
+            .map_err(|err| command_failed(err.to_string()))?;
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|err| command_failed(err.to_string()))?;
+        if !output.status.success() {
+            return Err(command_failed(output.status.to_string()));
+        }
+
+        String::from_utf8(output.stdout).map_err(|err| command_failed(err.to_string()))
+    }
+
+    fn parse_signature(&self, output: &str) -> Result<Signature, CastSignerError> {
+        let invalid_signature = || CastSignerError::InvalidSignature {
+            command: self.command.clone(),
+            output: output.trim().to_string(),
+        };
+
+        let values: Vec<FieldElement> = output
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| invalid_signature())?;
+
+        match values[..] {
+            [r, s] => Ok(Signature { r, s }),
+            _ => Err(invalid_signature()),
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for CastSigner {
+    type GetPublicKeyError = CastSignerError;
+    type SignError = CastSignerError;
+
+    async fn get_public_key(&self) -> Result<VerifyingKey, Self::GetPublicKeyError> {
+        match self {
+            CastSigner::Local(wallet) => wallet
+                .get_public_key()
+                .await
+                .map_err(|err| CastSignerError::Local(err.to_string())),
+            CastSigner::External(signer) => Ok(VerifyingKey::from_scalar(signer.public_key)),
+        }
+    }
+
+    async fn sign_hash(&self, hash: &FieldElement) -> Result<Signature, Self::SignError> {
+        match self {
+            CastSigner::Local(wallet) => wallet
+                .sign_hash(hash)
+                .await
+                .map_err(|err| CastSignerError::Local(err.to_string())),
+            CastSigner::External(signer) => {
+                let output = signer.run(hash).await?;
+                signer.parse_signature(&output)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign_with_command(command: &str) -> Result<Signature, CastSignerError> {
+        let signer =
+            CastSigner::External(ExternalSigner::new(command.to_string(), FieldElement::ONE));
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(signer.sign_hash(&FieldElement::from(0x123_u32)))
+    }
+
+    #[test]
+    fn test_external_signer_passes_hash_on_stdin() {
+        let signature = sign_with_command("read hash; echo \"$hash 0x2\"").unwrap();
+
+        assert_eq!(signature.r, FieldElement::from(0x123_u32));
+        assert_eq!(signature.s, FieldElement::TWO);
+    }
+
+    #[test]
+    fn test_external_signer_invalid_output() {
+        let err = sign_with_command("echo 0x1").unwrap_err();
+
+        assert!(err.to_string().contains("returned invalid signature = 0x1"));
+    }
+
+    #[test]
+    fn test_external_signer_command_fails() {
+        let err = sign_with_command("exit 1").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Failed to run signer command = exit 1"));
+    }
+}
@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use camino::Utf8PathBuf;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use starknet::providers::jsonrpc::{
+    HttpTransport, HttpTransportError, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport,
+};
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Time between consecutive attempts to resend a request which failed because of a network error
+const NETWORK_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `HttpTransport` which optionally records every JSON-RPC request and its response
+/// to a file, one JSON object per line, and resends requests which failed because of network errors
+pub struct TracedTransport {
+    inner: HttpTransport,
+    trace_file: Option<Arc<Mutex<File>>>,
+    network_timeout_retries: u8,
+}
+
+#[derive(Debug, Error)]
+pub enum TracedTransportError {
+    #[error(transparent)]
+    Http(#[from] HttpTransportError),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl TracedTransport {
+    #[must_use]
+    pub fn new(inner: HttpTransport) -> Self {
+        Self {
+            inner,
+            trace_file: None,
+            network_timeout_retries: 0,
+        }
+    }
+
+    pub fn with_trace_file(inner: HttpTransport, trace_path: &Utf8PathBuf) -> Result<Self> {
+        let trace_file = File::create(trace_path)
+            .with_context(|| format!("Failed to create trace file = {trace_path}"))?;
+        Ok(Self {
+            inner,
+            trace_file: Some(Arc::new(Mutex::new(trace_file))),
+            network_timeout_retries: 0,
+        })
+    }
+
+    /// Resends requests which failed because of a timeout or a connection error up to `retries` times.
+    /// Independent of `--wait` retries, which poll the status of an already sent transaction
+    #[must_use]
+    pub fn with_network_timeout_retries(mut self, retries: u8) -> Self {
+        self.network_timeout_retries = retries;
+        self
+    }
+
+    async fn send_with_retries<P, R>(
+        &self,
+        method: JsonRpcMethod,
+        params: &P,
+    ) -> Result<JsonRpcResponse<R>, HttpTransportError>
+    where
+        P: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        let mut attempt = 0;
+        loop {
+            match self.inner.send_request(method, params).await {
+                Err(HttpTransportError::Reqwest(error))
+                    if (error.is_timeout() || error.is_connect())
+                        && attempt < self.network_timeout_retries =>
+                {
+                    attempt += 1;
+                    tracing::warn!(?method, %error, attempt, "Network error, resending JSON-RPC request");
+                    tokio::time::sleep(NETWORK_RETRY_INTERVAL).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn record(&self, entry: &Value) {
+        if let Some(trace_file) = &self.trace_file {
+            let mut trace_file = trace_file.lock().expect("Failed to lock trace file");
+            // Tracing is a debugging aid, failing to write it should not fail the command
+            let _ = writeln!(trace_file, "{entry}");
+        }
+    }
+}
+
+// Delegates to the inner transport, so tracing does not leak into debug representations
+impl Debug for TracedTransport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[async_trait]
+impl JsonRpcTransport for TracedTransport {
+    type Error = TracedTransportError;
+
+    async fn send_request<P, R>(
+        &self,
+        method: JsonRpcMethod,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, Self::Error>
+    where
+        P: Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        tracing::debug!(?method, "Sending JSON-RPC request");
+        if self.trace_file.is_none() {
+            let response = self.send_with_retries(method, &params).await?;
+            tracing::debug!(?method, "Received JSON-RPC response");
+            return Ok(response);
+        }
+
+        let request = json!({
+            "method": serde_json::to_value(method)?,
+            "params": serde_json::to_value(&params)?,
+        });
+        let response: JsonRpcResponse<Value> = self.send_with_retries(method, &params).await?;
+        tracing::debug!(?method, "Received JSON-RPC response");
+
+        match response {
+            JsonRpcResponse::Success { id, result } => {
+                self.record(&json!({ "request": request, "result": result }));
+                Ok(JsonRpcResponse::Success {
+                    id,
+                    result: serde_json::from_value(result)?,
+                })
+            }
+            JsonRpcResponse::Error { id, error } => {
+                self.record(&json!({
+                    "request": request,
+                    "error": {
+                        "code": error.code,
+                        "message": error.message,
+                        "data": error.data,
+                    },
+                }));
+                Ok(JsonRpcResponse::Error { id, error })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::types::FieldElement;
+    use starknet::providers::{JsonRpcClient, Provider};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use url::Url;
+
+    const CHAIN_ID_RESPONSE: &str = r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#;
+
+    /// Starts a node which leaves the first `failing_attempts` requests unanswered, so they time out,
+    /// and answers the following ones with a chain id. Returns its url and the counter of received requests
+    async fn start_flaky_node(failing_attempts: usize) -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        let counter = attempts.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let attempt = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                tokio::spawn(async move {
+                    let mut request = [0; 4096];
+                    let _ = stream.read(&mut request).await;
+                    if attempt <= failing_attempts {
+                        tokio::time::sleep(Duration::from_secs(30)).await;
+                        return;
+                    }
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{CHAIN_ID_RESPONSE}",
+                        CHAIN_ID_RESPONSE.len()
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+
+        (url, attempts)
+    }
+
+    fn provider(url: Url, retries: u8) -> JsonRpcClient<TracedTransport> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        JsonRpcClient::new(
+            TracedTransport::new(HttpTransport::new_with_client(url, client))
+                .with_network_timeout_retries(retries),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resends_request_until_it_succeeds() {
+        let (url, attempts) = start_flaky_node(2).await;
+
+        let chain_id = provider(url, 2).chain_id().await.unwrap();
+
+        assert_eq!(
+            chain_id,
+            FieldElement::from_hex_be("0x534e5f5345504f4c4941").unwrap()
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fails_when_retries_are_exhausted() {
+        let (url, attempts) = start_flaky_node(2).await;
+
+        let result = provider(url, 1).chain_id().await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,41 +1,50 @@
 use anyhow::{anyhow, bail, Context, Error, Result};
 use camino::Utf8PathBuf;
-use helpers::constants::{KEYSTORE_PASSWORD_ENV_VAR, UDC_ADDRESS};
+use helpers::constants::{CONFIRM_MAINNET_ENV_VAR, ETH_ADDRESS, KEYSTORE_PASSWORD_ENV_VAR};
+use primitive_types::U256;
 use rand::rngs::OsRng;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use serde_json::{Deserializer, Value};
+use starknet::core::types::contract::AbiEntry;
 use starknet::core::types::{
     BlockId, BlockTag,
     BlockTag::{Latest, Pending},
-    ContractClass, ContractErrorData, FieldElement,
+    ContractClass, ContractErrorData, FieldElement, FunctionCall, MaybePendingBlockWithTxHashes,
+    MaybePendingTransactionReceipt, PendingTransactionReceipt,
     StarknetError::{ClassHashNotFound, ContractNotFound, TransactionHashNotFound},
+    TransactionReceipt,
 };
 use starknet::core::utils::UdcUniqueness::{NotUnique, Unique};
-use starknet::core::utils::{UdcUniqueSettings, UdcUniqueness};
+use starknet::core::utils::{get_selector_from_name, UdcUniqueSettings, UdcUniqueness};
 use starknet::{
-    accounts::{ExecutionEncoding, SingleOwnerAccount},
-    providers::{
-        jsonrpc::{HttpTransport, JsonRpcClient},
-        Provider, ProviderError,
-        ProviderError::StarknetError,
-    },
+    accounts::{Account, ConnectedAccount, ExecutionEncoding, SingleOwnerAccount},
+    providers::{jsonrpc::JsonRpcClient, Provider, ProviderError, ProviderError::StarknetError},
     signers::{LocalWallet, SigningKey},
 };
 
+use crate::helpers::abi::parse_abi;
+use crate::helpers::abi_cache::AbiCache;
 use crate::helpers::constants::{DEFAULT_STATE_FILE_SUFFIX, WAIT_RETRY_INTERVAL, WAIT_TIMEOUT};
-use crate::response::errors::SNCastProviderError;
+use crate::helpers::duration::{deserialize_duration, serialize_duration};
+use crate::helpers::keystore::{read_keystore, KeystoreFormat};
+use crate::helpers::signer::{CastSigner, ExternalSigner};
+use crate::helpers::traced_transport::TracedTransport;
+use crate::response::errors::{SNCastProviderError, SncastError};
 use conversions::serde::serialize::CairoSerialize;
 use serde::de::DeserializeOwned;
-use shared::rpc::create_rpc_client;
-use starknet::accounts::{AccountFactory, AccountFactoryError};
+use shared::print::print_as_warning;
+use shared::rpc::create_http_transport;
+use starknet::accounts::{AccountFactory, AccountFactoryError, Call};
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
 use std::thread::sleep;
 use std::time::Duration;
 use std::{env, fs};
 use thiserror::Error;
 
+pub mod commands;
 pub mod helpers;
 pub mod response;
 pub mod state;
@@ -107,21 +116,36 @@ pub struct WaitForTx {
 
 #[derive(Deserialize, Serialize, Clone, Debug, Copy, PartialEq)]
 pub struct ValidatedWaitParams {
-    #[serde(default)]
-    timeout: u16,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
+    )]
+    timeout: Duration,
 
     #[serde(
         default,
-        rename(serialize = "retry-interval", deserialize = "retry-interval")
+        rename(serialize = "retry-interval", deserialize = "retry-interval"),
+        deserialize_with = "deserialize_duration",
+        serialize_with = "serialize_duration"
     )]
-    retry_interval: u8,
+    retry_interval: Duration,
 }
 
 impl ValidatedWaitParams {
+    /// Creates wait params from values given in seconds
+    #[must_use]
+    pub fn new(retry_interval: u64, timeout: u64) -> Self {
+        Self::from_durations(
+            Duration::from_secs(retry_interval),
+            Duration::from_secs(timeout),
+        )
+    }
+
     #[must_use]
-    pub fn new(retry_interval: u8, timeout: u16) -> Self {
+    pub fn from_durations(retry_interval: Duration, timeout: Duration) -> Self {
         assert!(
-            !(retry_interval == 0 || timeout == 0 || u16::from(retry_interval) > timeout),
+            !(retry_interval.is_zero() || timeout.is_zero() || retry_interval > timeout),
             "Invalid values for retry_interval and/or timeout!"
         );
 
@@ -132,22 +156,24 @@ impl ValidatedWaitParams {
     }
 
     #[must_use]
-    pub fn get_retries(&self) -> u16 {
-        self.timeout / u16::from(self.retry_interval)
+    pub fn get_retries(&self) -> u128 {
+        self.timeout.as_millis() / self.retry_interval.as_millis()
     }
 
     #[must_use]
-    pub fn remaining_time(&self, steps_done: u16) -> u16 {
-        steps_done * u16::from(self.retry_interval)
+    pub fn remaining_time(&self, steps_done: u128) -> Duration {
+        Duration::from_millis(
+            u64::try_from(steps_done * self.retry_interval.as_millis()).unwrap_or(u64::MAX),
+        )
     }
 
     #[must_use]
-    pub fn get_retry_interval(&self) -> u8 {
+    pub fn get_retry_interval(&self) -> Duration {
         self.retry_interval
     }
 
     #[must_use]
-    pub fn get_timeout(&self) -> u16 {
+    pub fn get_timeout(&self) -> Duration {
         self.timeout
     }
 }
@@ -158,12 +184,33 @@ impl Default for ValidatedWaitParams {
     }
 }
 
-pub fn get_provider(url: &str) -> Result<JsonRpcClient<HttpTransport>> {
+pub fn get_provider(url: &str) -> Result<JsonRpcClient<TracedTransport>> {
     raise_if_empty(url, "RPC url")?;
-    create_rpc_client(url)
+    Ok(JsonRpcClient::new(TracedTransport::new(
+        create_http_transport(url)?,
+    )))
 }
 
-pub async fn get_chain_id(provider: &JsonRpcClient<HttpTransport>) -> Result<FieldElement> {
+/// Creates a provider resending requests which failed because of network errors up to `network_timeout_retries` times,
+/// recording every JSON-RPC request and response to the file at `trace_path` if passed
+pub fn get_provider_with_retries(
+    url: &str,
+    trace_path: Option<&Utf8PathBuf>,
+    network_timeout_retries: u8,
+) -> Result<JsonRpcClient<TracedTransport>> {
+    raise_if_empty(url, "RPC url")?;
+    let transport = match trace_path {
+        Some(trace_path) => {
+            TracedTransport::with_trace_file(create_http_transport(url)?, trace_path)?
+        }
+        None => TracedTransport::new(create_http_transport(url)?),
+    };
+    Ok(JsonRpcClient::new(
+        transport.with_network_timeout_retries(network_timeout_retries),
+    ))
+}
+
+pub async fn get_chain_id(provider: &JsonRpcClient<TracedTransport>) -> Result<FieldElement> {
     provider
         .chain_id()
         .await
@@ -177,6 +224,55 @@ pub fn get_keystore_password(env_var: &str) -> std::io::Result<String> {
     }
 }
 
+/// Returns the names (profile or account) which suggest a development setup, matching
+/// any of given patterns case-insensitively
+#[must_use]
+pub fn find_dev_looking_names<'a>(names: &[&'a str], patterns: &[String]) -> Vec<&'a str> {
+    names
+        .iter()
+        .filter(|name| {
+            let name = name.to_lowercase();
+            patterns
+                .iter()
+                .any(|pattern| !pattern.is_empty() && name.contains(&pattern.to_lowercase()))
+        })
+        .copied()
+        .collect()
+}
+
+/// Fails if a state-changing command is about to be sent to mainnet using a profile or account
+/// that looks like a development one, unless the user confirmed it explicitly
+pub fn check_mainnet_guard(
+    chain_id: FieldElement,
+    profile: Option<&str>,
+    account: &str,
+    dev_name_patterns: &[String],
+    confirmed: bool,
+) -> Result<()> {
+    if decode_chain_id(chain_id) != "SN_MAIN" {
+        return Ok(());
+    }
+
+    let names: Vec<&str> = profile.into_iter().chain([account]).collect();
+    let dev_looking_names = find_dev_looking_names(&names, dev_name_patterns);
+    if dev_looking_names.is_empty() {
+        return Ok(());
+    }
+
+    let names = dev_looking_names.join(", ");
+    if confirmed {
+        print_as_warning(&anyhow!(
+            "Sending a transaction to mainnet using {names}, which looks like a development setup"
+        ));
+        return Ok(());
+    }
+
+    bail!(
+        "Refusing to send a transaction to mainnet using {names}, which looks like a development setup. \
+        Pass `--yes` or set {CONFIRM_MAINNET_ENV_VAR}=1 to proceed"
+    )
+}
+
 #[must_use]
 pub fn chain_id_to_network_name(chain_id: FieldElement) -> String {
     let decoded = decode_chain_id(chain_id);
@@ -202,7 +298,7 @@ pub fn decode_chain_id(chain_id: FieldElement) -> String {
 }
 
 pub async fn get_nonce(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     block_id: &str,
     address: FieldElement,
 ) -> Result<FieldElement> {
@@ -215,35 +311,297 @@ pub async fn get_nonce(
         .context("Failed to get a nonce")
 }
 
+/// Checks that an explicitly provided nonce matches the nonce of the account at the pending block
+pub async fn validate_nonce(
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    nonce: FieldElement,
+) -> Result<()> {
+    let account_nonce = get_nonce(account.provider(), "pending", account.address()).await?;
+    if nonce != account_nonce {
+        return Err(SncastError::Account(anyhow!(
+            "Provided nonce = {nonce:#x} does not match the account nonce = {account_nonce:#x}. Use `--force-nonce` to send the transaction anyway"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Nonce passed with `--nonce`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonceArg {
+    Value(FieldElement),
+    /// The pending nonce of the account increased by the number of transactions this process
+    /// already has in flight; a single command only sends one, so it is the pending nonce
+    Next,
+}
+
+pub fn parse_nonce(value: &str) -> Result<NonceArg> {
+    if value == "next" {
+        return Ok(NonceArg::Next);
+    }
+    FieldElement::from_str(value)
+        .map(NonceArg::Value)
+        .with_context(|| format!("Failed to parse nonce = {value}, expected a felt or `next`"))
+}
+
+pub async fn get_eth_balance(
+    provider: &JsonRpcClient<TracedTransport>,
+    address: FieldElement,
+) -> Result<U256> {
+    get_token_balance(provider, ETH_ADDRESS, address).await
+}
+
+/// Returns the balance of the address in the ERC20 token contract at `token_address`
+pub async fn get_token_balance(
+    provider: &JsonRpcClient<TracedTransport>,
+    token_address: FieldElement,
+    address: FieldElement,
+) -> Result<U256> {
+    let balance = provider
+        .call(
+            FunctionCall {
+                contract_address: token_address,
+                entry_point_selector: get_selector_from_name("balanceOf")?,
+                calldata: vec![address],
+            },
+            BlockId::Tag(Pending),
+        )
+        .await
+        .map_err(handle_rpc_error)?;
+
+    match balance.as_slice() {
+        [low, high] => Ok(U256::from_big_endian(&low.to_bytes_be())
+            + (U256::from_big_endian(&high.to_bytes_be()) << 128)),
+        _ => bail!("Failed to parse balance of address {address:#x}"),
+    }
+}
+
+/// Returns the fee paid for the transaction with given hash, read from its receipt
+pub async fn get_actual_fee(
+    provider: &JsonRpcClient<TracedTransport>,
+    tx_hash: FieldElement,
+) -> Result<FieldElement> {
+    let receipt = provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(handle_rpc_error)?;
+
+    let actual_fee = match receipt {
+        MaybePendingTransactionReceipt::Receipt(receipt) => match receipt {
+            TransactionReceipt::Invoke(receipt) => receipt.actual_fee,
+            TransactionReceipt::L1Handler(receipt) => receipt.actual_fee,
+            TransactionReceipt::Declare(receipt) => receipt.actual_fee,
+            TransactionReceipt::Deploy(receipt) => receipt.actual_fee,
+            TransactionReceipt::DeployAccount(receipt) => receipt.actual_fee,
+        },
+        MaybePendingTransactionReceipt::PendingReceipt(receipt) => match receipt {
+            PendingTransactionReceipt::Invoke(receipt) => receipt.actual_fee,
+            PendingTransactionReceipt::L1Handler(receipt) => receipt.actual_fee,
+            PendingTransactionReceipt::Declare(receipt) => receipt.actual_fee,
+            PendingTransactionReceipt::DeployAccount(receipt) => receipt.actual_fee,
+        },
+    };
+    Ok(actual_fee.amount)
+}
+
+/// Returns the address of a contract deployed in the transaction with given hash,
+/// either directly (deploy / deploy account transactions) or through the Universal Deployer Contract at `udc_address`
+pub async fn get_deployed_contract_address(
+    provider: &JsonRpcClient<TracedTransport>,
+    tx_hash: FieldElement,
+    udc_address: FieldElement,
+) -> Result<FieldElement> {
+    let receipt = provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(|err| match err {
+            StarknetError(TransactionHashNotFound) => {
+                anyhow!("Transaction with hash = {tx_hash:#x} not found")
+            }
+            err => handle_rpc_error(err),
+        })?;
+
+    let events = match receipt {
+        MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Deploy(receipt)) => {
+            return Ok(receipt.contract_address)
+        }
+        MaybePendingTransactionReceipt::Receipt(TransactionReceipt::DeployAccount(receipt)) => {
+            return Ok(receipt.contract_address)
+        }
+        MaybePendingTransactionReceipt::Receipt(TransactionReceipt::Invoke(receipt)) => {
+            receipt.events
+        }
+        MaybePendingTransactionReceipt::PendingReceipt(PendingTransactionReceipt::Invoke(
+            receipt,
+        )) => receipt.events,
+        _ => bail!("Transaction with hash = {tx_hash:#x} is not a deploy transaction"),
+    };
+
+    let contract_deployed_selector = get_selector_from_name("ContractDeployed")?;
+    events
+        .iter()
+        .find(|event| {
+            event.from_address == udc_address
+                && event.keys.first() == Some(&contract_deployed_selector)
+        })
+        .and_then(|event| event.data.first().copied())
+        .with_context(|| {
+            format!("Transaction with hash = {tx_hash:#x} is not a deploy transaction")
+        })
+}
+
 pub async fn get_account<'a>(
     account: &str,
     accounts_file: &Utf8PathBuf,
-    provider: &'a JsonRpcClient<HttpTransport>,
+    provider: &'a JsonRpcClient<TracedTransport>,
+    keystore: Option<Utf8PathBuf>,
+    keystore_format: KeystoreFormat,
+    signer_command: Option<&str>,
+) -> Result<SingleOwnerAccount<&'a JsonRpcClient<TracedTransport>, CastSigner>> {
+    get_account_with_signer(
+        account,
+        accounts_file,
+        provider,
+        keystore,
+        keystore_format,
+        signer_command,
+        true,
+    )
+    .await
+    .map(|(account, _)| account)
+}
+
+/// Same as [`get_account`], but does not check whether the account is deployed
+pub async fn get_account_without_deploy_check<'a>(
+    account: &str,
+    accounts_file: &Utf8PathBuf,
+    provider: &'a JsonRpcClient<TracedTransport>,
+    keystore: Option<Utf8PathBuf>,
+    keystore_format: KeystoreFormat,
+    signer_command: Option<&str>,
+) -> Result<SingleOwnerAccount<&'a JsonRpcClient<TracedTransport>, CastSigner>> {
+    get_account_with_signer(
+        account,
+        accounts_file,
+        provider,
+        keystore,
+        keystore_format,
+        signer_command,
+        false,
+    )
+    .await
+    .map(|(account, _)| account)
+}
+
+/// Returns the account together with its signer, e.g. to sign typed data on its behalf,
+/// as `SingleOwnerAccount` does not give access to the signer it was built with
+pub async fn get_account_with_signer<'a>(
+    account: &str,
+    accounts_file: &Utf8PathBuf,
+    provider: &'a JsonRpcClient<TracedTransport>,
+    keystore: Option<Utf8PathBuf>,
+    keystore_format: KeystoreFormat,
+    signer_command: Option<&str>,
+    check_deployed: bool,
+) -> Result<(
+    SingleOwnerAccount<&'a JsonRpcClient<TracedTransport>, CastSigner>,
+    CastSigner,
+)> {
+    load_account(
+        account,
+        accounts_file,
+        provider,
+        keystore,
+        keystore_format,
+        signer_command,
+        check_deployed,
+    )
+    .await
+    .map_err(|error| SncastError::categorize(error, SncastError::Account))
+}
+
+async fn load_account<'a>(
+    account: &str,
+    accounts_file: &Utf8PathBuf,
+    provider: &'a JsonRpcClient<TracedTransport>,
     keystore: Option<Utf8PathBuf>,
-) -> Result<SingleOwnerAccount<&'a JsonRpcClient<HttpTransport>, LocalWallet>> {
+    keystore_format: KeystoreFormat,
+    signer_command: Option<&str>,
+    check_deployed: bool,
+) -> Result<(
+    SingleOwnerAccount<&'a JsonRpcClient<TracedTransport>, CastSigner>,
+    CastSigner,
+)> {
     let chain_id = get_chain_id(provider).await?;
+    tracing::debug!(
+        account,
+        %accounts_file,
+        keystore = ?keystore,
+        external_signer = signer_command.is_some(),
+        "Loading account"
+    );
+    let (account_data, signer) = load_account_data(
+        account,
+        accounts_file,
+        chain_id,
+        keystore,
+        keystore_format,
+        signer_command,
+    )?;
+
+    let account = build_account(
+        account_data,
+        signer.clone(),
+        chain_id,
+        provider,
+        check_deployed,
+    )
+    .await?;
+    Ok((account, signer))
+}
+
+fn load_account_data(
+    account: &str,
+    accounts_file: &Utf8PathBuf,
+    chain_id: FieldElement,
+    keystore: Option<Utf8PathBuf>,
+    keystore_format: KeystoreFormat,
+    signer_command: Option<&str>,
+) -> Result<(AccountData, CastSigner)> {
+    if let Some(command) = signer_command {
+        let account_data = get_account_data_from_account_file(account, FieldElement::ZERO)?;
+        let signer = CastSigner::External(ExternalSigner::new(
+            command.to_string(),
+            account_data.public_key,
+        ));
+        return Ok((account_data, signer));
+    }
+
     let account_data = if let Some(keystore) = keystore {
-        get_account_data_from_keystore(account, &keystore)?
+        get_account_data_from_keystore(account, &keystore, keystore_format)?
     } else {
         get_account_data_from_accounts_file(account, chain_id, accounts_file)?
     };
+    let signer = CastSigner::Local(LocalWallet::from(SigningKey::from_secret_scalar(
+        account_data.private_key,
+    )));
 
-    let account = build_account(account_data, chain_id, provider).await?;
-
-    Ok(account)
+    Ok((account_data, signer))
 }
 
 async fn build_account(
     account_data: AccountData,
+    signer: CastSigner,
     chain_id: FieldElement,
-    provider: &JsonRpcClient<HttpTransport>,
-) -> Result<SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>> {
-    let signer = LocalWallet::from(SigningKey::from_secret_scalar(account_data.private_key));
-
+    provider: &JsonRpcClient<TracedTransport>,
+    check_deployed: bool,
+) -> Result<SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>> {
     let address = account_data
         .address
         .context("Failed to get address - make sure the account is deployed")?;
-    verify_account_address(address, chain_id, provider).await?;
+    if check_deployed {
+        verify_account_address(address, chain_id, provider).await?;
+    }
 
     let class_hash = account_data.class_hash;
 
@@ -261,7 +619,7 @@ async fn build_account(
 async fn verify_account_address(
     address: FieldElement,
     chain_id: FieldElement,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
 ) -> Result<()> {
     match provider.get_nonce(BlockId::Tag(Pending), address).await {
         Ok(_) => Ok(()),
@@ -269,7 +627,7 @@ async fn verify_account_address(
             if let StarknetError(ContractNotFound) = error {
                 let decoded_chain_id = decode_chain_id(chain_id);
                 Err(anyhow!(
-                    "Account with address {address:#x} not found on network {decoded_chain_id}"
+                    "Account with address {address:#x} not found on network {decoded_chain_id}. Make sure the account is deployed, e.g. with `sncast account deploy` command"
                 ))
             } else {
                 Err(handle_rpc_error(error))
@@ -279,7 +637,7 @@ async fn verify_account_address(
 }
 
 pub async fn check_class_hash_exists(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     class_hash: FieldElement,
 ) -> Result<()> {
     match provider.get_class(BlockId::Tag(BlockTag::Latest), class_hash).await {
@@ -291,19 +649,100 @@ pub async fn check_class_hash_exists(
     }
 }
 
+/// Returns the ABI of the class, using `abi_cache` if provided
+pub async fn get_class_abi(
+    provider: &JsonRpcClient<TracedTransport>,
+    class_hash: FieldElement,
+    abi_cache: Option<&AbiCache>,
+) -> Result<Vec<AbiEntry>> {
+    if let Some(abi) = abi_cache.and_then(|cache| cache.load(class_hash)) {
+        return Ok(abi);
+    }
+    match provider.get_class(BlockId::Tag(Pending), class_hash).await {
+        Ok(ContractClass::Sierra(class)) => {
+            if let Some(cache) = abi_cache {
+                cache.store(class_hash, &class.abi);
+            }
+            parse_abi(&class.abi)
+        }
+        Ok(ContractClass::Legacy(_)) => Err(anyhow!(
+            "ABI of Cairo 0 class with hash {class_hash:#x} is not supported, use `--constructor-calldata` instead"
+        )),
+        Err(StarknetError(ClassHashNotFound)) => Err(anyhow!(
+            "Class with hash {class_hash:#x} is not declared, try using --class-hash with a hash of the declared class"
+        )),
+        Err(err) => Err(handle_rpc_error(err)),
+    }
+}
+
+/// Returns the ABI of the contract, using `abi_cache` if provided
+pub async fn get_contract_abi(
+    provider: &JsonRpcClient<TracedTransport>,
+    address: FieldElement,
+    block_id: &BlockId,
+    abi_cache: Option<&AbiCache>,
+) -> Result<Vec<AbiEntry>> {
+    let Some(abi_cache) = abi_cache else {
+        return match provider.get_class_at(block_id, address).await {
+            Ok(ContractClass::Sierra(class)) => parse_abi(&class.abi),
+            Ok(ContractClass::Legacy(_)) => Err(anyhow!(
+                "ABI of Cairo 0 contract at address {address:#x} is not supported"
+            )),
+            Err(StarknetError(ContractNotFound)) => {
+                Err(anyhow!("There is no contract at the address {address:#x}"))
+            }
+            Err(err) => Err(handle_rpc_error(err)),
+        };
+    };
+
+    // Class of the contract can be replaced, so only the class is cached, not the address
+    let class_hash = match provider.get_class_hash_at(block_id, address).await {
+        Ok(class_hash) => class_hash,
+        Err(StarknetError(ContractNotFound)) => {
+            bail!("There is no contract at the address {address:#x}")
+        }
+        Err(err) => return Err(handle_rpc_error(err)),
+    };
+    if let Some(abi) = abi_cache.load(class_hash) {
+        return Ok(abi);
+    }
+    match provider.get_class(block_id, class_hash).await {
+        Ok(ContractClass::Sierra(class)) => {
+            abi_cache.store(class_hash, &class.abi);
+            parse_abi(&class.abi)
+        }
+        Ok(ContractClass::Legacy(_)) => Err(anyhow!(
+            "ABI of Cairo 0 contract at address {address:#x} is not supported"
+        )),
+        Err(err) => Err(handle_rpc_error(err)),
+    }
+}
+
 pub fn get_account_data_from_keystore(
     account: &str,
     keystore_path: &Utf8PathBuf,
+    keystore_format: KeystoreFormat,
 ) -> Result<AccountData> {
     check_keystore_and_account_files_exist(keystore_path, account)?;
-    let path_to_account = Utf8PathBuf::from(account);
 
-    let private_key = SigningKey::from_keystore(
-        keystore_path,
-        get_keystore_password(KEYSTORE_PASSWORD_ENV_VAR)?.as_str(),
-    )?
+    let private_key = read_keystore(keystore_path, keystore_format, || {
+        Ok(get_keystore_password(KEYSTORE_PASSWORD_ENV_VAR)?)
+    })?
     .secret_scalar();
 
+    get_account_data_from_account_file(account, private_key)
+}
+
+/// Reads account data from a starkli JSON account file; the private key is not stored in it
+fn get_account_data_from_account_file(
+    account: &str,
+    private_key: FieldElement,
+) -> Result<AccountData> {
+    let path_to_account = Utf8PathBuf::from(account);
+    if !path_to_account.exists() {
+        bail!("File containing the account does not exist: When using `--signer-command` argument, the `--account` argument should be a path to the starkli JSON account file");
+    }
+
     let account_info: Value = read_and_parse_json_file(&path_to_account)?;
 
     let parse_to_felt = |pointer: &str| -> Option<FieldElement> {
@@ -378,6 +817,24 @@ pub fn get_account_data_from_accounts_file(
         .ok_or_else(|| anyhow!("Account = {name} not found under network = {network_name}"))
 }
 
+/// Returns all accounts stored in the accounts file for the network of `chain_id`, sorted by name
+pub fn get_accounts_data_from_accounts_file(
+    chain_id: FieldElement,
+    path: &Utf8PathBuf,
+) -> Result<Vec<(String, AccountData)>> {
+    check_account_file_exists(path)?;
+
+    let mut accounts: HashMap<String, HashMap<String, AccountData>> =
+        read_and_parse_json_file(path)?;
+    let mut accounts: Vec<(String, AccountData)> = accounts
+        .remove(&chain_id_to_network_name(chain_id))
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    accounts.sort_by(|(first, _), (second, _)| first.cmp(second));
+    Ok(accounts)
+}
+
 fn read_and_parse_json_file<T: DeserializeOwned>(path: &Utf8PathBuf) -> Result<T> {
     let file_content =
         fs::read_to_string(path).with_context(|| format!("Failed to read a file = {path}"))?;
@@ -395,7 +852,7 @@ async fn get_account_encoding(
     legacy: Option<bool>,
     class_hash: Option<FieldElement>,
     address: FieldElement,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
 ) -> Result<ExecutionEncoding> {
     if let Some(legacy) = legacy {
         Ok(map_encoding(legacy))
@@ -408,7 +865,7 @@ async fn get_account_encoding(
 pub async fn check_if_legacy_contract(
     class_hash: Option<FieldElement>,
     address: FieldElement,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
 ) -> Result<bool> {
     let contract_class = match class_hash {
         Some(class_hash) => provider.get_class(BlockId::Tag(Pending), class_hash).await,
@@ -420,7 +877,7 @@ pub async fn check_if_legacy_contract(
 }
 
 pub async fn get_class_hash_by_address(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     address: FieldElement,
 ) -> Result<Option<FieldElement>> {
     match provider
@@ -463,6 +920,80 @@ pub fn get_block_id(value: &str) -> Result<BlockId> {
     }
 }
 
+/// Resolves block identifier passed as a CLI argument. Apart from values accepted by `get_block_id`,
+/// accepts `time:<unix_seconds>` resolved to the latest block created at or before given timestamp
+pub async fn resolve_block_id(
+    provider: &JsonRpcClient<TracedTransport>,
+    value: &str,
+) -> Result<BlockId> {
+    match value.strip_prefix("time:") {
+        Some(timestamp) => {
+            let timestamp = timestamp
+                .parse::<u64>()
+                .with_context(|| format!("Incorrect timestamp passed for block_id = {value}"))?;
+            Ok(BlockId::Number(
+                get_block_number_at_timestamp(provider, timestamp).await?,
+            ))
+        }
+        None => get_block_id(value),
+    }
+}
+
+static BLOCK_NUMBERS_BY_TIMESTAMP: OnceLock<Mutex<HashMap<u64, u64>>> = OnceLock::new();
+
+async fn get_block_number_at_timestamp(
+    provider: &JsonRpcClient<TracedTransport>,
+    timestamp: u64,
+) -> Result<u64> {
+    let cache = BLOCK_NUMBERS_BY_TIMESTAMP.get_or_init(Mutex::default);
+    let cached = cache
+        .lock()
+        .expect("Failed to lock block numbers cache")
+        .get(&timestamp)
+        .copied();
+    if let Some(block_number) = cached {
+        return Ok(block_number);
+    }
+
+    if get_block_timestamp(provider, 0).await? > timestamp {
+        bail!("Timestamp = {timestamp} predates the genesis block");
+    }
+
+    // Block `low` is always created at or before the timestamp
+    let mut low = 0;
+    let mut high = provider.block_number().await.map_err(handle_rpc_error)?;
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if get_block_timestamp(provider, mid).await? <= timestamp {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    cache
+        .lock()
+        .expect("Failed to lock block numbers cache")
+        .insert(timestamp, low);
+    Ok(low)
+}
+
+async fn get_block_timestamp(
+    provider: &JsonRpcClient<TracedTransport>,
+    block_number: u64,
+) -> Result<u64> {
+    match provider
+        .get_block_with_tx_hashes(BlockId::Number(block_number))
+        .await
+        .map_err(handle_rpc_error)?
+    {
+        MaybePendingBlockWithTxHashes::Block(block) => Ok(block.timestamp),
+        MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+            bail!("Block number = {block_number} is still pending")
+        }
+    }
+}
+
 #[derive(Debug, CairoSerialize)]
 pub struct ErrorData {
     pub data: String,
@@ -502,7 +1033,7 @@ pub enum WaitForTransactionError {
 }
 
 pub async fn wait_for_tx(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     tx_hash: FieldElement,
     wait_params: ValidatedWaitParams,
 ) -> Result<&str, WaitForTransactionError> {
@@ -510,7 +1041,9 @@ pub async fn wait_for_tx(
 
     let retries = wait_params.get_retries();
     for i in (1..retries).rev() {
-        match provider.get_transaction_status(tx_hash).await {
+        let status = provider.get_transaction_status(tx_hash).await;
+        tracing::debug!(?status, retries_left = i, "Polled transaction status");
+        match status {
             Ok(starknet::core::types::TransactionStatus::Rejected) => {
                 return Err(WaitForTransactionError::TransactionError(
                     TransactionError::Rejected,
@@ -530,23 +1063,23 @@ pub async fn wait_for_tx(
             Ok(starknet::core::types::TransactionStatus::Received)
             | Err(StarknetError(TransactionHashNotFound)) => {
                 let remaining_time = wait_params.remaining_time(i);
-                println!("Waiting for transaction to be accepted ({i} retries / {remaining_time}s left until timeout)");
+                println!("Waiting for transaction to be accepted ({i} retries / {remaining_time:?} left until timeout)");
             }
             Err(ProviderError::RateLimited) => {
                 println!("Request rate limited while waiting for transaction to be accepted");
-                sleep(Duration::from_secs(wait_params.get_retry_interval().into()));
+                sleep(wait_params.get_retry_interval());
             }
             Err(err) => return Err(WaitForTransactionError::ProviderError(err.into())),
         };
 
-        sleep(Duration::from_secs(wait_params.get_retry_interval().into()));
+        sleep(wait_params.get_retry_interval());
     }
 
     Err(WaitForTransactionError::TimedOut)
 }
 
 async fn get_revert_reason(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     tx_hash: FieldElement,
 ) -> Result<&str, WaitForTransactionError> {
     let receipt = provider
@@ -569,7 +1102,7 @@ async fn get_revert_reason(
 #[must_use]
 pub fn handle_rpc_error(error: impl Into<SNCastProviderError>) -> Error {
     let err: SNCastProviderError = error.into();
-    err.into()
+    SncastError::from(err).into()
 }
 
 #[must_use]
@@ -584,11 +1117,16 @@ where
 }
 
 pub async fn handle_wait_for_tx<T>(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     transaction_hash: FieldElement,
     return_value: T,
     wait_config: WaitForTx,
 ) -> Result<T, WaitForTransactionError> {
+    tracing::info!(
+        transaction_hash = format_args!("{transaction_hash:#x}"),
+        wait = wait_config.wait,
+        "Transaction sent"
+    );
     if wait_config.wait {
         return match wait_for_tx(provider, transaction_hash, wait_config.wait_params).await {
             Ok(_) => Ok(return_value),
@@ -637,11 +1175,15 @@ pub fn extract_or_generate_salt(salt: Option<FieldElement>) -> FieldElement {
 }
 
 #[must_use]
-pub fn udc_uniqueness(unique: bool, account_address: FieldElement) -> UdcUniqueness {
+pub fn udc_uniqueness(
+    unique: bool,
+    account_address: FieldElement,
+    udc_address: FieldElement,
+) -> UdcUniqueness {
     if unique {
         Unique(UdcUniqueSettings {
             deployer_address: account_address,
-            udc_contract_address: UDC_ADDRESS,
+            udc_contract_address: udc_address,
         })
     } else {
         NotUnique
@@ -655,6 +1197,97 @@ pub fn apply_optional<T, R, F: FnOnce(T, R) -> T>(initial: T, option: Option<R>,
     }
 }
 
+/// Fails if `max_fee` is more than `ratio` times higher than `estimated_fee`.
+/// Zero estimates, e.g. on devnets without fees, are not checked
+pub fn check_max_fee_ratio(
+    max_fee: FieldElement,
+    estimated_fee: FieldElement,
+    ratio: u64,
+) -> Result<()> {
+    let max_fee_value = U256::from_big_endian(&max_fee.to_bytes_be());
+    let estimated_fee_value = U256::from_big_endian(&estimated_fee.to_bytes_be());
+    if estimated_fee_value.is_zero()
+        || max_fee_value <= estimated_fee_value.saturating_mul(U256::from(ratio))
+    {
+        return Ok(());
+    }
+
+    bail!(
+        "Max fee = {max_fee} is {}x the estimated fee = {estimated_fee}, above the allowed ratio = {ratio}. Lower `--max-fee` or raise `--max-fee-abort-ratio` if this is intended",
+        max_fee_value / estimated_fee_value
+    )
+}
+
+/// Returns the `--max-fee-abort-ratio` to check. It can not be used with an external signer,
+/// as the fee estimation would make the signer command sign a second time
+pub fn max_fee_abort_ratio_for(
+    ratio: Option<u64>,
+    signer_command: Option<&str>,
+) -> Result<Option<u64>> {
+    if ratio.is_some() && signer_command.is_some() {
+        bail!(
+            "`--max-fee-abort-ratio` can not be used with `--signer-command`, estimating the fee would require signing twice"
+        );
+    }
+    Ok(ratio)
+}
+
+/// Fails if the calldata is longer than `max_len`, which usually means it was generated incorrectly
+pub fn check_calldata_len(calldata: &[FieldElement], max_len: usize) -> Result<()> {
+    check_len(calldata.len(), max_len)
+}
+
+/// Checks the combined calldata length of calls sent in a single transaction,
+/// e.g. of a multicall or of an invoke together with the call moving its `--value`
+pub fn check_calls_calldata_len(calls: &[Call], max_len: usize) -> Result<()> {
+    check_len(calls.iter().map(|call| call.calldata.len()).sum(), max_len)
+}
+
+fn check_len(len: usize, max_len: usize) -> Result<()> {
+    if len > max_len {
+        bail!(
+            "Calldata length = {len} exceeds the maximum = {max_len}. Pass a higher `--max-calldata-len` if this is intended"
+        );
+    }
+    Ok(())
+}
+
+/// Checks whether the function is passed as a raw selector, directly or as an `@<path>` reference to a file
+/// holding it, instead of its name
+#[must_use]
+pub fn is_raw_selector(function: &str) -> bool {
+    function.starts_with("0x") || function.starts_with('@')
+}
+
+/// Returns the selector of a function passed either by its name, as a raw `0x` prefixed selector
+/// or as an `@<path>` reference to a file holding the selector
+pub fn get_function_selector(function: &str) -> Result<FieldElement> {
+    if let Some(path) = function.strip_prefix('@') {
+        return read_selector_from_file(&Utf8PathBuf::from(path));
+    }
+    if is_raw_selector(function) {
+        return FieldElement::from_hex_be(function)
+            .with_context(|| format!("Failed to parse function selector = {function}"));
+    }
+    get_selector_from_name(function)
+        .context("Failed to convert entry point selector to FieldElement")
+}
+
+/// Reads the selector from a file, which should hold a single felt, either hex or decimal
+pub fn read_selector_from_file(path: &Utf8PathBuf) -> Result<FieldElement> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read selector file = {path}"))?;
+    let values: Vec<&str> = contents.split_whitespace().collect();
+    let [selector] = values.as_slice() else {
+        bail!(
+            "Selector file = {path} should contain a single felt, found {} values",
+            values.len()
+        );
+    };
+    FieldElement::from_str(selector)
+        .with_context(|| format!("Failed to parse selector = {selector} from file = {path}"))
+}
+
 #[must_use]
 pub fn get_default_state_file_name(script_name: &str, chain_id: &str) -> String {
     format!("{script_name}_{chain_id}_{DEFAULT_STATE_FILE_SUFFIX}")
@@ -663,12 +1296,16 @@ pub fn get_default_state_file_name(script_name: &str, chain_id: &str) -> String
 #[cfg(test)]
 mod tests {
     use crate::helpers::constants::KEYSTORE_PASSWORD_ENV_VAR;
+    use crate::helpers::keystore::KeystoreFormat;
     use crate::{
-        chain_id_to_network_name, extract_or_generate_salt, get_account_data_from_accounts_file,
-        get_account_data_from_keystore, get_block_id, udc_uniqueness, AccountType,
+        chain_id_to_network_name, check_calldata_len, check_calls_calldata_len,
+        check_mainnet_guard, check_max_fee_ratio, extract_or_generate_salt, find_dev_looking_names,
+        get_account_data_from_accounts_file, get_account_data_from_keystore, get_block_id,
+        get_function_selector, max_fee_abort_ratio_for, udc_uniqueness, AccountType,
     };
     use camino::Utf8PathBuf;
     use conversions::string::IntoHexStr;
+    use starknet::accounts::Call;
     use starknet::core::types::{
         BlockId,
         BlockTag::{Latest, Pending},
@@ -733,14 +1370,14 @@ mod tests {
 
     #[test]
     fn test_udc_uniqueness_unique() {
-        let uniqueness = udc_uniqueness(true, FieldElement::ONE);
+        let uniqueness = udc_uniqueness(true, FieldElement::ONE, FieldElement::TWO);
 
         assert!(matches!(uniqueness, Unique(UdcUniqueSettings { .. })));
     }
 
     #[test]
     fn test_udc_uniqueness_not_unique() {
-        let uniqueness = udc_uniqueness(false, FieldElement::ONE);
+        let uniqueness = udc_uniqueness(false, FieldElement::ONE, FieldElement::TWO);
 
         assert!(matches!(uniqueness, NotUnique));
     }
@@ -793,6 +1430,7 @@ mod tests {
         let account = get_account_data_from_keystore(
             "tests/data/keystore/my_account.json",
             &Utf8PathBuf::from("tests/data/keystore/my_key.json"),
+            KeystoreFormat::Auto,
         )
         .unwrap();
         assert_eq!(
@@ -819,6 +1457,7 @@ mod tests {
         let err = get_account_data_from_keystore(
             "tests/data/keystore/my_account_braavos_invalid_multisig.json",
             &Utf8PathBuf::from("tests/data/keystore/my_key.json"),
+            KeystoreFormat::Auto,
         )
         .unwrap_err();
 
@@ -833,6 +1472,7 @@ mod tests {
         let err = get_account_data_from_keystore(
             "tests/data/keystore/my_account_braavos_multiple_signers.json",
             &Utf8PathBuf::from("tests/data/keystore/my_key.json"),
+            KeystoreFormat::Auto,
         )
         .unwrap_err();
 
@@ -854,4 +1494,120 @@ mod tests {
             .to_string()
             .contains("Account = user1 not found under network = CUSTOM_CHAIN_ID"));
     }
+
+    #[test]
+    fn test_find_dev_looking_names() {
+        let patterns = vec![String::from("dev"), String::from("TEST")];
+
+        assert_eq!(
+            find_dev_looking_names(&["my_Devnet_profile", "testing-account", "prod"], &patterns),
+            vec!["my_Devnet_profile", "testing-account"]
+        );
+        assert!(find_dev_looking_names(&["prod"], &patterns).is_empty());
+    }
+
+    #[test]
+    fn test_mainnet_guard() {
+        let mainnet = FieldElement::from_byte_slice_be("SN_MAIN".as_bytes()).unwrap();
+        let sepolia = FieldElement::from_byte_slice_be("SN_SEPOLIA".as_bytes()).unwrap();
+        let patterns = vec![String::from("dev")];
+
+        assert!(check_mainnet_guard(sepolia, Some("dev"), "user1", &patterns, false).is_ok());
+        assert!(check_mainnet_guard(mainnet, Some("prod"), "user1", &patterns, false).is_ok());
+        assert!(check_mainnet_guard(mainnet, Some("prod"), "dev_account", &patterns, true).is_ok());
+
+        let err = check_mainnet_guard(mainnet, Some("dev"), "user1", &patterns, false).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Refusing to send a transaction to mainnet using dev"));
+    }
+
+    #[test]
+    fn test_check_max_fee_ratio() {
+        let estimated_fee = FieldElement::from(100_u32);
+
+        assert!(check_max_fee_ratio(FieldElement::from(1000_u32), estimated_fee, 10).is_ok());
+        assert!(check_max_fee_ratio(FieldElement::from(1000_u32), FieldElement::ZERO, 10).is_ok());
+
+        let err =
+            check_max_fee_ratio(FieldElement::from(100_000_u32), estimated_fee, 10).unwrap_err();
+        assert!(err.to_string().contains(
+            "Max fee = 100000 is 1000x the estimated fee = 100, above the allowed ratio = 10"
+        ));
+    }
+
+    #[test]
+    fn test_max_fee_abort_ratio_with_signer_command() {
+        assert_eq!(max_fee_abort_ratio_for(Some(10), None).unwrap(), Some(10));
+        assert_eq!(max_fee_abort_ratio_for(None, Some("sign")).unwrap(), None);
+
+        let err = max_fee_abort_ratio_for(Some(10), Some("sign")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`--max-fee-abort-ratio` can not be used with `--signer-command`"));
+    }
+
+    #[test]
+    fn test_check_calls_calldata_len() {
+        let call = |len| Call {
+            to: FieldElement::ONE,
+            selector: FieldElement::TWO,
+            calldata: vec![FieldElement::ONE; len],
+        };
+        let calls = vec![call(2), call(3)];
+
+        assert!(check_calls_calldata_len(&calls, 5).is_ok());
+        let err = check_calls_calldata_len(&calls, 4).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Calldata length = 5 exceeds the maximum = 4"));
+    }
+
+    #[test]
+    fn test_check_calldata_len() {
+        let calldata = vec![FieldElement::ONE; 3];
+
+        assert!(check_calldata_len(&calldata, 3).is_ok());
+
+        let err = check_calldata_len(&calldata, 2).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Calldata length = 3 exceeds the maximum = 2"));
+    }
+
+    #[test]
+    fn test_get_function_selector() {
+        assert_eq!(
+            get_function_selector("0x1234").unwrap(),
+            FieldElement::from(0x1234_u32)
+        );
+        assert_eq!(
+            get_function_selector("get").unwrap(),
+            starknet::core::utils::get_selector_from_name("get").unwrap()
+        );
+        assert!(get_function_selector("0xz")
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to parse function selector = 0xz"));
+    }
+
+    #[test]
+    fn test_get_function_selector_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Utf8PathBuf::from_path_buf(dir.path().join("selector")).unwrap();
+
+        std::fs::write(&path, "0x1234\n").unwrap();
+        assert_eq!(
+            get_function_selector(&format!("@{path}")).unwrap(),
+            FieldElement::from(0x1234_u32)
+        );
+
+        std::fs::write(&path, "0x1 0x2").unwrap();
+        assert_eq!(
+            get_function_selector(&format!("@{path}"))
+                .unwrap_err()
+                .to_string(),
+            format!("Selector file = {path} should contain a single felt, found 2 values")
+        );
+    }
 }
@@ -1,30 +1,72 @@
 use crate::starknet_commands::account::Account;
+use crate::starknet_commands::doctor::Doctor;
 use crate::starknet_commands::show_config::ShowConfig;
 use crate::starknet_commands::{
-    account, call::Call, declare::Declare, deploy::Deploy, invoke::Invoke, multicall::Multicall,
-    script::Script, tx_status::TxStatus,
+    abi::Abi, account, call::Call, class_hash::ClassHash, declare::Declare, deploy::Deploy,
+    deploy::Salt, invoke::Invoke, multicall::Multicall, schema::Schema, script::Script,
+    tx_status::TxStatus,
+};
+use anyhow::{anyhow, bail, Context, Result};
+use configuration::{load_global_config, load_global_config_with_overrides};
+use sncast::response::print::{
+    print_command_result, print_raw_felts, validate_output_template, OutputFormat,
+};
+use sncast::response::schema::command_output_fields;
+use sncast::response::structs::{
+    AbiResponse, AutoSaltResponse, CallResponse, ClassHashResponse, CommandResponse, Decimal,
+    DecodedCallResponse, DumpConfigResponse, Felt, InvokeReplaceClassResponse,
+    MulticallCallExecutedResponse, NonceResponse, SizedResponse, SponsoredResponse,
 };
-use anyhow::{Context, Result};
-use configuration::load_global_config;
-use sncast::response::print::{print_command_result, OutputFormat};
 
 use camino::Utf8PathBuf;
 use clap::{Parser, Subcommand};
-use shared::verify_and_warn_if_incompatible_rpc_version;
-use sncast::helpers::configuration::CastConfig;
-use sncast::helpers::constants::{DEFAULT_ACCOUNTS_FILE, DEFAULT_MULTICALL_CONTENTS};
+use promptly::prompt;
+use semver::Version;
+use shared::print::print_as_warning;
+use shared::verify_rpc_version;
+use sncast::commands::declare::{get_class_hash, get_contract_sizes, ContractSizes};
+use sncast::commands::invoke::{validate_replace_class, validate_value, value_call};
+use sncast::helpers::abi::{
+    decode_function_outputs, encode_constructor_args, encode_function_args, find_function,
+    load_abi_from_file,
+};
+use sncast::helpers::abi_cache::AbiCache;
+use sncast::helpers::call_cache::CallCache;
+use sncast::helpers::configuration::{apply_inline_config, CastConfig};
+use sncast::helpers::constants::{
+    ALWAYS_CONFIRM_ENV_VAR, AUTO_SALT_MAX_ATTEMPTS, CONFIRM_MAINNET_ENV_VAR, DEFAULT_ACCOUNTS_FILE,
+    DEFAULT_DEV_NAME_PATTERNS, DEFAULT_MAX_CALLDATA_LEN, DEFAULT_MULTICALL_CONTENTS,
+    PROFILE_ENV_VAR, UDC_ADDRESS,
+};
+use sncast::helpers::duration::parse_duration;
+use sncast::helpers::keystore::KeystoreFormat;
+use sncast::helpers::logging::{init_logging, redact_url, LogLevel};
+use sncast::helpers::nonce_file::{nonce_or_allocate, resolve_nonce};
+use sncast::helpers::numbers::{parse_calldata_args, read_calldata_from_stdin};
+use sncast::helpers::paymaster::Paymaster;
+use sncast::helpers::proxy::get_implementation_abi;
 use sncast::helpers::scarb_utils::{
-    assert_manifest_path_exists, build, build_and_load_artifacts, get_package_metadata,
-    get_scarb_metadata_with_deps, BuildConfig,
+    assert_manifest_path_exists, build, build_and_load_artifacts, get_default_profile_from_scarb,
+    get_scarb_metadata_with_deps, select_package_metadata, verify_scarb_version, BuildConfig,
+};
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::response::errors::{
+    error_kind, handle_starknet_command_error, is_transaction_reverted_error, ErrorKind,
+    SncastError,
 };
-use sncast::response::errors::handle_starknet_command_error;
 use sncast::{
-    chain_id_to_network_name, get_account, get_block_id, get_chain_id, get_default_state_file_name,
-    get_nonce, get_provider, NumbersFormat, ValidatedWaitParams, WaitForTx,
+    chain_id_to_network_name, check_calldata_len, check_calls_calldata_len, check_mainnet_guard,
+    get_account, get_account_with_signer, get_chain_id, get_class_abi, get_contract_abi,
+    get_default_state_file_name, get_deployed_contract_address, get_function_selector, get_nonce,
+    get_provider_with_retries, is_raw_selector, max_fee_abort_ratio_for, resolve_block_id,
+    validate_nonce, NonceArg, NumbersFormat, ValidatedWaitParams, WaitForTx,
 };
-use starknet::core::utils::get_selector_from_name;
-use starknet::providers::jsonrpc::HttpTransport;
+use starknet::accounts::{Account as _, Call};
+use starknet::core::types::{BlockId, BlockTag, FieldElement};
 use starknet::providers::JsonRpcClient;
+use std::collections::HashSet;
+use std::env;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
 mod starknet_commands;
@@ -63,10 +105,22 @@ Report bugs: https://github.com/foundry-rs/starknet-foundry/issues/new/choose\
 #[clap(name = "sncast")]
 #[allow(clippy::struct_excessive_bools)]
 struct Cli {
-    /// Profile name in snfoundry.toml config file
+    /// Profile name in snfoundry.toml config file; defaults to the `SNCAST_PROFILE` environment variable,
+    /// then to `default-profile` from `[tool.sncast]` in Scarb.toml if set
     #[clap(short, long)]
     profile: Option<String>,
 
+    /// `default-profile` from `[tool.sncast]` in Scarb.toml, used when no profile is passed. Unlike `profile`,
+    /// it only selects the profile in snfoundry.toml and is not used as the Scarb build profile
+    #[clap(skip)]
+    default_profile: Option<String>,
+
+    /// Configuration entries overriding the ones from snfoundry.toml, as a TOML fragment with entries
+    /// separated by `;` or new lines, e.g. `url = "http://127.0.0.1:5050"; account = "user1"`.
+    /// Arguments passed explicitly take precedence over it
+    #[clap(long, value_name = "TOML")]
+    config_inline: Option<String>,
+
     /// RPC provider url address; overrides url from snfoundry.toml
     #[clap(short = 'u', long = "url")]
     rpc_url: Option<String>,
@@ -85,6 +139,15 @@ struct Cli {
     #[clap(short, long)]
     keystore: Option<Utf8PathBuf>,
 
+    /// Format of the keystore file; detected from its contents by default
+    #[clap(long, value_enum)]
+    keystore_format: Option<KeystoreFormat>,
+
+    /// Command used to sign transactions instead of a private key, e.g. a hardware wallet bridge;
+    /// if specified, --account should be a path to starkli JSON account file
+    #[clap(long, conflicts_with = "keystore")]
+    signer_command: Option<String>,
+
     /// If passed, values will be displayed as integers
     #[clap(long, conflicts_with = "hex_format")]
     int_format: bool,
@@ -94,21 +157,98 @@ struct Cli {
     hex_format: bool,
 
     /// If passed, output will be displayed in json format
-    #[clap(short, long)]
+    #[clap(short, long, conflicts_with = "print_txn_hash_only")]
     json: bool,
 
+    /// If passed, only the transaction hash will be printed
+    #[clap(long, conflicts_with = "json")]
+    print_txn_hash_only: bool,
+
+    /// If passed, successful output is displayed in human readable format and errors in json format
+    #[clap(long, conflicts_with_all = ["json", "print_txn_hash_only"])]
+    json_errors_only: bool,
+
+    /// Template into which fields of successful output are interpolated, e.g. "{transaction_hash} {status}";
+    /// use `{{` and `}}` for literal braces
+    #[clap(long, conflicts_with_all = ["json", "print_txn_hash_only", "json_errors_only"])]
+    output_template: Option<String>,
+
+    /// If passed, human readable output is printed without colors and alignment;
+    /// can also be set with `NO_COLOR` environment variable
+    #[clap(long)]
+    no_color: bool,
+
+    /// If passed, ABIs of classes are always fetched from the network instead of the local cache
+    /// and `call --cache-reads` always performs the call
+    #[clap(long)]
+    no_cache: bool,
+
+    /// If passed, calldata felts have to be `0x` prefixed hex or plain decimal values,
+    /// and values out of the field range are rejected with an error naming them
+    #[clap(long)]
+    strict_felt_parsing: bool,
+
+    /// Path to a file used to allocate nonces of transactions, so concurrent sncast processes
+    /// sending transactions from the same account do not use the same nonce
+    #[clap(long)]
+    nonce_file: Option<Utf8PathBuf>,
+
+    /// Path to a TOML file with `<name> = "<address>"` entries, referenced as `@<name>` in place of
+    /// contract addresses. Defaults to `[tool.sncast.addresses]` section of Scarb.toml
+    #[clap(long, value_name = "PATH")]
+    address_book: Option<Utf8PathBuf>,
+
+    /// Level of logs printed to stderr; can also be set with `SNCAST_LOG` environment variable [default: warn]
+    #[clap(long, value_enum)]
+    log_level: Option<LogLevel>,
+
     /// If passed, command will wait until transaction is accepted or rejected
     #[clap(short = 'w', long)]
     wait: bool,
 
-    /// Adjusts the time after which --wait assumes transaction was not received or rejected
+    /// Adjusts the time after which --wait assumes transaction was not received or rejected (e.g. `300s`, `5m`)
+    #[clap(long, value_parser = parse_duration, conflicts_with = "wait_timeout")]
+    poll_timeout: Option<Duration>,
+
+    /// Adjusts the time between consecutive attempts to fetch transaction by --wait flag (e.g. `500ms`, `5s`)
+    #[clap(long, value_parser = parse_duration, conflicts_with = "wait_retry_interval")]
+    poll_interval: Option<Duration>,
+
+    /// Deprecated, use --poll-timeout instead. Time in seconds after which --wait assumes transaction was not received or rejected
     #[clap(long)]
     wait_timeout: Option<u16>,
 
-    /// Adjusts the time between consecutive attempts to fetch transaction by --wait flag
+    /// Deprecated, use --poll-interval instead. Time in seconds between consecutive attempts to fetch transaction by --wait flag
     #[clap(long)]
     wait_retry_interval: Option<u8>,
 
+    /// Number of times a request to the node is resent after a timeout or a connection error.
+    /// Independent of --wait, which polls the status of an already sent transaction
+    #[clap(long)]
+    network_timeout_retries: Option<u8>,
+
+    /// Maximum length of calldata of `call`, `invoke` and `deploy`; longer calldata is rejected before sending it.
+    /// Defaults to 10000
+    #[clap(long)]
+    max_calldata_len: Option<usize>,
+
+    /// If passed, every JSON-RPC request sent to the node and its response will be saved to the file at given path
+    #[clap(long)]
+    trace: Option<Utf8PathBuf>,
+
+    /// RPC version assumed for the node. If passed, the version is not queried from the node
+    #[clap(long)]
+    rpc_version: Option<Version>,
+
+    /// If passed, confirms sending transactions to mainnet using a profile or account that looks like a development one
+    /// and skips the `--confirm` prompt
+    #[clap(long)]
+    yes: bool,
+
+    /// If passed, a summary of the transaction is shown and confirmation is required before sending it
+    #[clap(long)]
+    confirm: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -141,22 +281,157 @@ enum Commands {
 
     /// Get the status of a transaction
     TxStatus(TxStatus),
+
+    /// Check the environment and configuration for common problems
+    Doctor(Doctor),
+
+    /// Print JSON Schema of the `--json` output of a command
+    Schema(Schema),
+
+    /// Compute the class hash of a contract from its local artifacts
+    ClassHash(ClassHash),
+
+    /// Fetch the ABI of a contract or a class
+    Abi(Abi),
+}
+
+impl Commands {
+    /// Name of the command, as printed in the `command` field of its output
+    fn output_name(&self) -> Option<&'static str> {
+        let name = match self {
+            Commands::Declare(_) => "declare",
+            Commands::Deploy(_) => "deploy",
+            Commands::Call(_) => "call",
+            Commands::Invoke(_) => "invoke",
+            Commands::Multicall(multicall) => match multicall.command {
+                starknet_commands::multicall::Commands::Run(_) => "multicall run",
+                starknet_commands::multicall::Commands::New(_) => "multicall new",
+            },
+            Commands::Account(account) => match account.command {
+                account::Commands::Add(_) => "account add",
+                account::Commands::Create(_) => "account create",
+                account::Commands::Deploy(_) => "account deploy",
+                account::Commands::Delete(_) => "account delete",
+                account::Commands::Rename(_) => "account rename",
+                account::Commands::MigrateFile(_) => "account migrate-file",
+                account::Commands::FixNonce(_) => "account fix-nonce",
+            },
+            Commands::ShowConfig(_) => "show-config",
+            Commands::Script(script) => match script.command {
+                starknet_commands::script::Commands::Init(_) => "script init",
+                starknet_commands::script::Commands::Run(_) => "script run",
+            },
+            Commands::TxStatus(_) => "tx-status",
+            Commands::Doctor(_) => "doctor",
+            Commands::ClassHash(_) => "class-hash",
+            Commands::Abi(_) => "abi",
+            Commands::Schema(_) => return None,
+        };
+        Some(name)
+    }
+}
+
+impl Cli {
+    /// Profile in snfoundry.toml: the one passed explicitly, otherwise `default-profile` from Scarb.toml
+    fn sncast_profile(&self) -> Option<String> {
+        self.profile
+            .clone()
+            .or_else(|| self.default_profile.clone())
+    }
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("Error: {error:?}");
+        std::process::exit(error_kind(&error).exit_code());
+    }
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn run() -> Result<()> {
+    let mut cli = Cli::parse();
+    init_logging(cli.log_level);
+    if cli.profile.is_none() {
+        match env::var(PROFILE_ENV_VAR) {
+            Ok(profile) if !profile.is_empty() => cli.profile = Some(profile),
+            _ => {
+                cli.default_profile = get_default_profile_from_scarb().unwrap_or_else(|err| {
+                    print_as_warning(&anyhow!(
+                        "Failed to read `default-profile` from Scarb.toml: {err:#}"
+                    ));
+                    None
+                });
+            }
+        }
+    }
+
+    if cli.no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
 
     let numbers_format = NumbersFormat::from_flags(cli.hex_format, cli.int_format);
-    let output_format = OutputFormat::from_flag(cli.json);
+    let output_format = OutputFormat::from_flags(
+        cli.json,
+        cli.print_txn_hash_only,
+        cli.json_errors_only,
+        cli.output_template.clone(),
+    );
+    if let (Some(template), Some(command)) = (&cli.output_template, cli.command.output_name()) {
+        validate_output_template(template, &command_output_fields(command)?)?;
+    }
 
     let runtime = Runtime::new().expect("Failed to instantiate Runtime");
 
+    if let Commands::Schema(schema) = &cli.command {
+        let schema = starknet_commands::schema::schema(schema)?;
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    if let Commands::ClassHash(class_hash) = &cli.command {
+        let mut result = starknet_commands::class_hash::class_hash(class_hash);
+        print_command_result("class-hash", &mut result, numbers_format, &output_format)?;
+        return Ok(());
+    }
+
     if let Commands::Script(script) = &cli.command {
         run_script_command(&cli, runtime, script, numbers_format, &output_format)
+    } else if let Commands::Doctor(_) = &cli.command {
+        run_doctor_command(&cli, &runtime, numbers_format, &output_format)
     } else {
-        let mut config = load_global_config::<CastConfig>(&None, &cli.profile)?;
-        update_cast_config(&mut config, &cli);
-        let provider = get_provider(&config.url)?;
+        let config = load_cast_config(&cli, None)?;
+        if let Commands::ShowConfig(ShowConfig {
+            dump_config: Some(output_path),
+            ..
+        }) = &cli.command
+        {
+            return dump_config(
+                &cli,
+                &config,
+                output_path.as_ref(),
+                numbers_format,
+                &output_format,
+            );
+        }
+        if let Commands::Account(Account {
+            command: account::Commands::MigrateFile(_),
+        }) = &cli.command
+        {
+            let mut result =
+                starknet_commands::account::migrate_file::migrate_file(&config.accounts_file);
+            print_command_result(
+                "account migrate-file",
+                &mut result,
+                numbers_format,
+                &output_format,
+            )?;
+            return Ok(());
+        }
+        let provider = get_provider_with_retries(
+            &config.url,
+            cli.trace.as_ref(),
+            config.network_timeout_retries,
+        )?;
         runtime.block_on(run_async_command(
             cli,
             config,
@@ -171,40 +446,170 @@ fn main() -> Result<()> {
 async fn run_async_command(
     cli: Cli,
     config: CastConfig,
-    provider: JsonRpcClient<HttpTransport>,
+    provider: JsonRpcClient<TracedTransport>,
     numbers_format: NumbersFormat,
     output_format: OutputFormat,
 ) -> Result<()> {
-    verify_and_warn_if_incompatible_rpc_version(&provider, &config.url).await?;
+    verify_rpc_version(&provider, &config.url, cli.rpc_version.as_ref()).await?;
 
     let wait_config = WaitForTx {
         wait: cli.wait,
         wait_params: config.wait_params,
     };
+    let abi_cache = (!cli.no_cache).then(AbiCache::default);
+    let max_calldata_len = config.max_calldata_len.unwrap_or(DEFAULT_MAX_CALLDATA_LEN);
+    tracing::debug!(
+        url = %redact_url(&config.url),
+        account = %config.account,
+        accounts_file = %config.accounts_file,
+        "Resolved configuration"
+    );
+
+    if matches!(
+        cli.command,
+        Commands::Declare(Declare {
+            size_only: false,
+            ..
+        }) | Commands::Deploy(_)
+            | Commands::Invoke(_)
+    ) {
+        let dev_name_patterns = config.dev_name_patterns.clone().unwrap_or_else(|| {
+            DEFAULT_DEV_NAME_PATTERNS
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        });
+        let chain_id = get_chain_id(&provider).await?;
+        check_mainnet_guard(
+            chain_id,
+            cli.sncast_profile().as_deref(),
+            &config.account,
+            &dev_name_patterns,
+            cli.yes || env::var(CONFIRM_MAINNET_ENV_VAR).is_ok_and(|value| value == "1"),
+        )?;
 
+        let confirm =
+            cli.confirm || env::var(ALWAYS_CONFIRM_ENV_VAR).is_ok_and(|value| value == "1");
+        if confirm && !cli.yes {
+            if matches!(output_format, OutputFormat::Json) {
+                bail!("Confirmation prompt is not shown in JSON mode, pass `--yes` to send the transaction");
+            }
+            confirm_transaction(&transaction_summary(
+                &cli.command,
+                chain_id,
+                &config.account,
+            ))?;
+        }
+    }
+
+    // Computed before the command is moved out of `cli`
+    let cli_overrides = get_cli_overrides(&cli);
     match cli.command {
         Commands::Declare(declare) => {
-            let account = get_account(
-                &config.account,
-                &config.accounts_file,
-                &provider,
-                config.keystore,
-            )
-            .await?;
+            let max_fee_abort_ratio = max_fee_abort_ratio_for(
+                declare.max_fee_abort_ratio,
+                config.signer_command.as_deref(),
+            )?;
             let manifest_path = assert_manifest_path_exists()?;
-            let package_metadata = get_package_metadata(&manifest_path, &declare.package)?;
+            let metadata = get_scarb_metadata_with_deps(&manifest_path)?;
+            let package_metadata = select_package_metadata(&metadata, &declare.package)?;
+            if let Some(scarb_version) = &declare.scarb_version {
+                verify_scarb_version(scarb_version)?;
+            }
             let artifacts = build_and_load_artifacts(
                 &package_metadata,
                 &BuildConfig {
                     scarb_toml_path: manifest_path,
+                    metadata: &metadata,
                     json: cli.json,
                     profile: cli.profile.unwrap_or("dev".to_string()),
+                    build_profile: declare.build_profile,
+                    target: declare.target,
+                    scarb_version: declare.scarb_version,
+                    force_rebuild: declare.force_rebuild,
+                    no_build: declare.no_build,
+                    build_args: declare.build_args,
                 },
             )
-            .expect("Failed to build contract");
-            let mut result = starknet_commands::declare::declare(
-                &declare.contract,
+            .context("Failed to build contract")?;
+
+            if declare.size_only {
+                let [contract] = declare.contracts.as_slice() else {
+                    bail!("`--size-only` can only be used with a single contract");
+                };
+                let mut result = get_class_hash(contract, &artifacts)
+                    .and_then(|class_hash| {
+                        Ok(sized_response(
+                            ClassHashResponse {
+                                class_hash: Felt(class_hash),
+                                compiled_class_hash: None,
+                            },
+                            &get_contract_sizes(contract, &artifacts)?,
+                        ))
+                    })
+                    .map_err(handle_starknet_command_error);
+                print_command_result("declare", &mut result, numbers_format, &output_format)?;
+                return Ok(());
+            }
+
+            let account = get_account(
+                &config.account,
+                &config.accounts_file,
+                &provider,
+                config.keystore,
+                config.keystore_format,
+                config.signer_command.as_deref(),
+            )
+            .await?;
+            if let (Some(nonce), false) = (declare.nonce, declare.force_nonce) {
+                validate_nonce(&account, nonce).await?;
+            }
+
+            // Passing the same contract more than once would make its second declaration fail
+            let mut declared_contracts = HashSet::new();
+            let mut contracts = declare.contracts;
+            contracts.retain(|contract| declared_contracts.insert(contract.clone()));
+
+            if let [contract] = contracts.as_slice() {
+                let nonce =
+                    nonce_or_allocate(declare.nonce, cli.nonce_file.as_ref(), &account).await?;
+                let mut result = sncast::commands::declare::declare(
+                    contract,
+                    declare.max_fee,
+                    max_fee_abort_ratio,
+                    &account,
+                    nonce,
+                    &artifacts,
+                    declare.class_hash,
+                    wait_config,
+                )
+                .await
+                .and_then(|response| {
+                    Ok(sized_response(
+                        response,
+                        &get_contract_sizes(contract, &artifacts)?,
+                    ))
+                })
+                .map_err(handle_starknet_command_error);
+
+                print_command_result("declare", &mut result, numbers_format, &output_format)?;
+                exit_if_transaction_reverted(&result);
+                return Ok(());
+            }
+
+            if declare.class_hash.is_some() {
+                bail!("`--class-hash` can only be used when declaring a single contract");
+            }
+            if cli.nonce_file.is_some() {
+                bail!("`--nonce-file` can only be used when declaring a single contract");
+            }
+            if declare.all {
+                contracts = sncast::commands::declare::contract_names(&artifacts);
+            }
+            let mut result = sncast::commands::declare::declare_multiple(
+                &contracts,
                 declare.max_fee,
+                max_fee_abort_ratio,
                 &account,
                 declare.nonce,
                 &artifacts,
@@ -214,72 +619,400 @@ async fn run_async_command(
             .map_err(handle_starknet_command_error);
 
             print_command_result("declare", &mut result, numbers_format, &output_format)?;
+            exit_if_transaction_reverted(&result);
+            if matches!(&result, Ok(response) if response.has_failures()) {
+                std::process::exit(1);
+            }
             Ok(())
         }
         Commands::Deploy(deploy) => {
-            let account = get_account(
+            let max_fee_abort_ratio = max_fee_abort_ratio_for(
+                deploy.max_fee_abort_ratio,
+                config.signer_command.as_deref(),
+            )?;
+            let (account, signer) = get_account_with_signer(
                 &config.account,
                 &config.accounts_file,
                 &provider,
-                config.keystore,
+                config.keystore.clone(),
+                config.keystore_format,
+                config.signer_command.as_deref(),
+                true,
             )
             .await?;
-            let mut result = starknet_commands::deploy::deploy(
-                deploy.class_hash,
-                deploy.constructor_calldata,
-                deploy.salt,
-                deploy.unique,
-                deploy.max_fee,
-                &account,
-                deploy.nonce,
-                wait_config,
-            )
-            .await
-            .map_err(handle_starknet_command_error);
+            if let (Some(NonceArg::Value(nonce)), false) = (deploy.nonce, deploy.force_nonce) {
+                validate_nonce(&account, nonce).await?;
+            }
+            let constructor_calldata = match &deploy.constructor_args {
+                Some(constructor_args) => {
+                    let abi = match &deploy.abi_file {
+                        Some(abi_file) => load_abi_from_file(abi_file)?,
+                        None => {
+                            get_class_abi(&provider, deploy.class_hash, abi_cache.as_ref()).await?
+                        }
+                    };
+                    encode_constructor_args(&abi, constructor_args)?
+                }
+                None if deploy.constructor_calldata_stdin => {
+                    read_calldata_from_stdin(cli.strict_felt_parsing)?
+                }
+                None => parse_calldata_args(&deploy.constructor_calldata, cli.strict_felt_parsing)?,
+            };
+            check_calldata_len(&constructor_calldata, max_calldata_len)?;
+            let udc_address = deploy
+                .udc_address
+                .or(config.udc_address)
+                .unwrap_or(UDC_ADDRESS);
+            let salt = match deploy.salt {
+                Some(Salt::Auto) => Some(
+                    sncast::commands::deploy::find_free_salt(
+                        deploy.class_hash,
+                        &constructor_calldata,
+                        deploy.auto_salt_start,
+                        AUTO_SALT_MAX_ATTEMPTS,
+                        deploy.unique,
+                        udc_address,
+                        &account,
+                    )
+                    .await?,
+                ),
+                Some(Salt::Value(salt)) => {
+                    // Addresses computed from random salts are not expected to be taken
+                    if !deploy.allow_redeploy {
+                        sncast::commands::deploy::check_deployment_address_free(
+                            deploy.class_hash,
+                            &constructor_calldata,
+                            salt,
+                            deploy.unique,
+                            udc_address,
+                            &account,
+                        )
+                        .await?;
+                    }
+                    Some(salt)
+                }
+                None => None,
+            };
+            let paymaster = deploy.paymaster_url.clone().map(|url| Paymaster {
+                url,
+                token: deploy.paymaster_token.clone(),
+            });
+            let (result, resolved_nonce) = if let Some(paymaster) = &paymaster {
+                let result = sncast::commands::deploy::deploy_sponsored(
+                    deploy.class_hash,
+                    constructor_calldata,
+                    salt,
+                    deploy.unique,
+                    udc_address,
+                    &account,
+                    paymaster,
+                    &signer,
+                    wait_config,
+                )
+                .await;
+                (result, None)
+            } else {
+                let nonce = resolve_nonce(deploy.nonce, cli.nonce_file.as_ref(), &account).await?;
+                let result = sncast::commands::deploy::deploy(
+                    deploy.class_hash,
+                    constructor_calldata,
+                    salt,
+                    deploy.unique,
+                    udc_address,
+                    deploy.max_fee,
+                    max_fee_abort_ratio,
+                    &account,
+                    nonce,
+                    wait_config,
+                )
+                .await;
+                (
+                    result,
+                    nonce.filter(|_| deploy.nonce == Some(NonceArg::Next)),
+                )
+            };
+            let result = result.map_err(handle_starknet_command_error);
 
-            print_command_result("deploy", &mut result, numbers_format, &output_format)?;
-            Ok(())
+            let auto_salt = salt.filter(|_| matches!(deploy.salt, Some(Salt::Auto)));
+            let result = result.map(|response| AutoSaltResponse {
+                response,
+                salt: auto_salt.map(Felt),
+            });
+            print_transaction_result(
+                "deploy",
+                result,
+                resolved_nonce,
+                paymaster.as_ref(),
+                numbers_format,
+                &output_format,
+            )
         }
         Commands::Call(call) => {
-            let block_id = get_block_id(&call.block_id)?;
+            if call.raw && matches!(output_format, OutputFormat::Json) {
+                bail!("`--raw` cannot be used with `--json`");
+            }
+            let block_id = resolve_block_id(&provider, &call.block_id).await?;
+            if let Some(batch_file) = &call.batch_file {
+                let mut result = starknet_commands::call::call_batch(
+                    batch_file,
+                    &provider,
+                    block_id.as_ref(),
+                    call.concurrency,
+                    call.decode,
+                    abi_cache.as_ref(),
+                    cli.address_book.as_ref(),
+                    cli.strict_felt_parsing,
+                )
+                .await;
+                print_command_result("call", &mut result, numbers_format, &output_format)?;
+                return Ok(());
+            }
 
-            let mut result = starknet_commands::call::call(
-                call.contract_address,
-                get_selector_from_name(&call.function)
-                    .context("Failed to convert entry point selector to FieldElement")?,
-                call.calldata,
-                &provider,
-                block_id.as_ref(),
-            )
-            .await
-            .map_err(handle_starknet_command_error);
+            let contract_address = call
+                .contract_address
+                .as_ref()
+                .expect("Either `--contract-address` or `--batch-file` should be validated by clap")
+                .resolve(cli.address_book.as_ref())?;
+            let function = match &call.selector_from_file {
+                Some(path) => format!("@{path}"),
+                None => call.function.expect(
+                    "Either `--function`, `--selector-from-file` or `--batch-file` should be validated by clap",
+                ),
+            };
+            let known_abi = if call.follow_proxy {
+                Some(
+                    get_implementation_abi(
+                        &provider,
+                        contract_address,
+                        block_id.as_ref(),
+                        call.impl_slot,
+                    )
+                    .await?,
+                )
+            } else {
+                call.abi_file.as_ref().map(load_abi_from_file).transpose()?
+            };
+            if let (Some(abi), false) = (&known_abi, is_raw_selector(&function)) {
+                find_function(abi, &function)?;
+            }
+
+            let calldata = if call.calldata_stdin {
+                read_calldata_from_stdin(cli.strict_felt_parsing)?
+            } else {
+                parse_calldata_args(&call.calldata, cli.strict_felt_parsing)?
+            };
+            check_calldata_len(&calldata, max_calldata_len)?;
+            let selector = get_function_selector(&function)?;
+            let call_cache = call
+                .cache_reads
+                .filter(|_| !cli.no_cache)
+                .map(|ttl| CallCache::new(&config.url, ttl));
+            let cached = call_cache.as_ref().and_then(|cache| {
+                cache.load(contract_address, selector, &calldata, block_id.as_ref())
+            });
+            let mut result = match cached {
+                Some(response) => Ok(CallResponse {
+                    response: response.into_iter().map(Felt).collect(),
+                }),
+                None => {
+                    let result = sncast::commands::call::call(
+                        contract_address,
+                        selector,
+                        calldata.clone(),
+                        &provider,
+                        block_id.as_ref(),
+                    )
+                    .await
+                    .map_err(handle_starknet_command_error);
+                    if let (Some(cache), Ok(response)) = (&call_cache, &result) {
+                        cache.store(
+                            contract_address,
+                            selector,
+                            &calldata,
+                            block_id.as_ref(),
+                            response.response.iter().map(|felt| felt.0).collect(),
+                        );
+                    }
+                    result
+                }
+            };
 
+            if let (true, Ok(response)) = (call.raw, &result) {
+                print_raw_felts(&response.response, numbers_format);
+                return Ok(());
+            }
+            if let (true, Ok(response)) = (call.decode, &result) {
+                let abi = match known_abi {
+                    Some(abi) => Ok(abi),
+                    None => {
+                        get_contract_abi(
+                            &provider,
+                            contract_address,
+                            block_id.as_ref(),
+                            abi_cache.as_ref(),
+                        )
+                        .await
+                    }
+                };
+                let felts: Vec<FieldElement> =
+                    response.response.iter().map(|felt| felt.0).collect();
+                match abi.and_then(|abi| decode_function_outputs(&abi, &function, &felts)) {
+                    Ok(decoded) => {
+                        let mut result = Ok(DecodedCallResponse {
+                            response: response.response.clone(),
+                            decoded,
+                        });
+                        print_command_result("call", &mut result, numbers_format, &output_format)?;
+                        return Ok(());
+                    }
+                    Err(err) => print_as_warning(&anyhow!(
+                        "Failed to decode the call response, printing raw response instead: {err:#}"
+                    )),
+                }
+            }
             print_command_result("call", &mut result, numbers_format, &output_format)?;
             Ok(())
         }
         Commands::Invoke(invoke) => {
-            let account = get_account(
+            let max_fee_abort_ratio = max_fee_abort_ratio_for(
+                invoke.max_fee_abort_ratio,
+                config.signer_command.as_deref(),
+            )?;
+            let function = invoke.function_name();
+            let abi = invoke
+                .abi_file
+                .as_ref()
+                .map(load_abi_from_file)
+                .transpose()?;
+            if let Some(abi) = &abi {
+                find_function(abi, &function)?;
+            }
+            let (account, signer) = get_account_with_signer(
                 &config.account,
                 &config.accounts_file,
                 &provider,
-                config.keystore,
+                config.keystore.clone(),
+                config.keystore_format,
+                config.signer_command.as_deref(),
+                !invoke.skip_deploy_check,
             )
             .await?;
-            let mut result = starknet_commands::invoke::invoke(
-                invoke.contract_address,
-                get_selector_from_name(&invoke.function)
-                    .context("Failed to convert entry point selector to FieldElement")?,
-                invoke.calldata,
-                invoke.max_fee,
-                &account,
-                invoke.nonce,
-                wait_config,
-            )
-            .await
-            .map_err(handle_starknet_command_error);
+            if let (Some(NonceArg::Value(nonce)), false) = (invoke.nonce, invoke.force_nonce) {
+                validate_nonce(&account, nonce).await?;
+            }
+            let contract_address = match invoke.from_deploy {
+                Some(tx_hash) => {
+                    get_deployed_contract_address(
+                        &provider,
+                        tx_hash,
+                        config.udc_address.unwrap_or(UDC_ADDRESS),
+                    )
+                    .await?
+                }
+                None => invoke
+                    .contract_address
+                    .as_ref()
+                    .expect(
+                        "Either `--contract-address` or `--from-deploy` should be validated by clap",
+                    )
+                    .resolve(cli.address_book.as_ref())?,
+            };
+            if invoke.follow_proxy && !is_raw_selector(&function) {
+                let abi = get_implementation_abi(
+                    &provider,
+                    contract_address,
+                    &BlockId::Tag(BlockTag::Pending),
+                    invoke.impl_slot,
+                )
+                .await?;
+                find_function(&abi, &function)?;
+            }
+            let old_class_hash = match invoke.replace_class {
+                Some(new_class_hash) => {
+                    Some(validate_replace_class(&provider, contract_address, new_class_hash).await?)
+                }
+                None => None,
+            };
+            let calldata = if let Some(new_class_hash) = invoke.replace_class {
+                vec![new_class_hash]
+            } else if invoke.calldata_stdin {
+                read_calldata_from_stdin(cli.strict_felt_parsing)?
+            } else if let Some(abi) = &abi {
+                encode_function_args(abi, &function, &invoke.calldata)?
+            } else {
+                parse_calldata_args(&invoke.calldata, cli.strict_felt_parsing)?
+            };
+            let mut calls = vec![Call {
+                to: contract_address,
+                selector: get_function_selector(&function)?,
+                calldata,
+            }];
+            if let (Some(value), Some(token)) = (invoke.value, invoke.token) {
+                validate_value(&provider, token, account.address(), value).await?;
+                calls.insert(
+                    0,
+                    value_call(invoke.value_call, token, contract_address, value)?,
+                );
+            }
+            check_calls_calldata_len(&calls, max_calldata_len)?;
+            let paymaster = invoke.paymaster_url.clone().map(|url| Paymaster {
+                url,
+                token: invoke.paymaster_token.clone(),
+            });
+            let (result, resolved_nonce) = if let Some(paymaster) = &paymaster {
+                let result = sncast::commands::invoke::execute_calls_sponsored(
+                    &account,
+                    calls,
+                    paymaster,
+                    &signer,
+                    wait_config,
+                )
+                .await;
+                (result, None)
+            } else {
+                let nonce = resolve_nonce(invoke.nonce, cli.nonce_file.as_ref(), &account).await?;
+                let result = sncast::commands::invoke::execute_calls(
+                    &account,
+                    calls,
+                    invoke.max_fee,
+                    max_fee_abort_ratio,
+                    nonce,
+                    wait_config,
+                )
+                .await;
+                (
+                    result,
+                    nonce.filter(|_| invoke.nonce == Some(NonceArg::Next)),
+                )
+            };
+            let result = result.map_err(handle_starknet_command_error);
 
-            print_command_result("invoke", &mut result, numbers_format, &output_format)?;
-            Ok(())
+            if let (Some(old_class_hash), Some(new_class_hash)) =
+                (old_class_hash, invoke.replace_class)
+            {
+                let result = result.map(|response| InvokeReplaceClassResponse {
+                    transaction_hash: response.transaction_hash,
+                    old_class_hash: Felt(old_class_hash),
+                    new_class_hash: Felt(new_class_hash),
+                });
+                print_transaction_result(
+                    "invoke",
+                    result,
+                    resolved_nonce,
+                    paymaster.as_ref(),
+                    numbers_format,
+                    &output_format,
+                )
+            } else {
+                print_transaction_result(
+                    "invoke",
+                    result,
+                    resolved_nonce,
+                    paymaster.as_ref(),
+                    numbers_format,
+                    &output_format,
+                )
+            }
         }
         Commands::Multicall(multicall) => {
             match &multicall.command {
@@ -303,22 +1036,59 @@ async fn run_async_command(
                         &config.accounts_file,
                         &provider,
                         config.keystore,
+                        config.keystore_format,
+                        config.signer_command.as_deref(),
                     )
                     .await?;
-                    let mut result = starknet_commands::multicall::run::run(
-                        &run.path,
-                        &account,
-                        run.max_fee,
-                        wait_config,
-                    )
-                    .await;
+                    let udc_address = config.udc_address.unwrap_or(UDC_ADDRESS);
+                    if run.non_atomic {
+                        let mut result = starknet_commands::multicall::run::run_non_atomic(
+                            &run.path,
+                            &account,
+                            udc_address,
+                            run.max_fee,
+                            max_calldata_len,
+                            cli.strict_felt_parsing,
+                            wait_config,
+                            |response| {
+                                if run.stream {
+                                    print_multicall_progress(
+                                        response,
+                                        numbers_format,
+                                        &output_format,
+                                    );
+                                }
+                            },
+                        )
+                        .await;
 
-                    print_command_result(
-                        "multicall run",
-                        &mut result,
-                        numbers_format,
-                        &output_format,
-                    )?;
+                        print_command_result(
+                            "multicall run",
+                            &mut result,
+                            numbers_format,
+                            &output_format,
+                        )?;
+                        exit_if_transaction_reverted(&result);
+                    } else {
+                        let mut result = starknet_commands::multicall::run::run(
+                            &run.path,
+                            &account,
+                            udc_address,
+                            run.max_fee,
+                            max_calldata_len,
+                            cli.strict_felt_parsing,
+                            wait_config,
+                        )
+                        .await;
+
+                        print_command_result(
+                            "multicall run",
+                            &mut result,
+                            numbers_format,
+                            &output_format,
+                        )?;
+                        exit_if_transaction_reverted(&result);
+                    }
                 }
             }
             Ok(())
@@ -357,6 +1127,9 @@ async fn run_async_command(
                     create.salt,
                     create.add_profile,
                     create.class_hash,
+                    create.deploy,
+                    create.fund_faucet,
+                    wait_config,
                 )
                 .await;
 
@@ -366,10 +1139,35 @@ async fn run_async_command(
                     numbers_format,
                     &output_format,
                 )?;
+                exit_if_transaction_reverted(&result);
                 Ok(())
             }
             account::Commands::Deploy(deploy) => {
                 let chain_id = get_chain_id(&provider).await?;
+                if deploy.all_pending {
+                    if config.keystore.is_some() {
+                        bail!("`--all-pending` deploys accounts from the accounts file and cannot be used with `--keystore`");
+                    }
+                    let mut result = starknet_commands::account::deploy::deploy_all_pending(
+                        &provider,
+                        config.accounts_file,
+                        chain_id,
+                        deploy.max_fee,
+                        wait_config,
+                    )
+                    .await;
+
+                    print_command_result(
+                        "account deploy",
+                        &mut result,
+                        numbers_format,
+                        &output_format,
+                    )?;
+                    if matches!(&result, Ok(response) if response.has_failures()) {
+                        std::process::exit(1);
+                    }
+                    return Ok(());
+                }
                 let keystore_path = config.keystore.clone();
                 let mut result = starknet_commands::account::deploy::deploy(
                     &provider,
@@ -379,6 +1177,7 @@ async fn run_async_command(
                     wait_config,
                     &config.account,
                     keystore_path,
+                    config.keystore_format,
                 )
                 .await;
 
@@ -388,6 +1187,7 @@ async fn run_async_command(
                     numbers_format,
                     &output_format,
                 )?;
+                exit_if_transaction_reverted(&result);
                 Ok(())
             }
             account::Commands::Delete(delete) => {
@@ -411,10 +1211,69 @@ async fn run_async_command(
                 )?;
                 Ok(())
             }
+            account::Commands::Rename(rename) => {
+                let network_name = match rename.network {
+                    Some(network) => network,
+                    None => chain_id_to_network_name(get_chain_id(&provider).await?),
+                };
+
+                let mut result = starknet_commands::account::rename::rename(
+                    &rename.from,
+                    &rename.to,
+                    &config.accounts_file,
+                    &network_name,
+                    rename.update_profiles,
+                );
+
+                print_command_result(
+                    "account rename",
+                    &mut result,
+                    numbers_format,
+                    &output_format,
+                )?;
+                Ok(())
+            }
+            account::Commands::FixNonce(fix_nonce) => {
+                let account = get_account(
+                    &config.account,
+                    &config.accounts_file,
+                    &provider,
+                    config.keystore,
+                    config.keystore_format,
+                    config.signer_command.as_deref(),
+                )
+                .await?;
+                let mut result = starknet_commands::account::fix_nonce::fix_nonce(
+                    &account,
+                    &fix_nonce,
+                    wait_config,
+                )
+                .await;
+
+                print_command_result(
+                    "account fix-nonce",
+                    &mut result,
+                    numbers_format,
+                    &output_format,
+                )?;
+                Ok(())
+            }
+            account::Commands::MigrateFile(_) => {
+                unreachable!(
+                    "`account migrate-file` should be handled before connecting to the network"
+                )
+            }
         },
-        Commands::ShowConfig(_) => {
-            let mut result =
-                starknet_commands::show_config::show_config(&provider, config, cli.profile).await;
+        Commands::ShowConfig(show_config) => {
+            let mut result = starknet_commands::show_config::show_config(
+                &provider,
+                config,
+                cli.profile.or(cli.default_profile),
+                &cli_overrides,
+                show_config.check,
+                show_config.balance,
+            )
+            .await;
             print_command_result("show-config", &mut result, numbers_format, &output_format)?;
             Ok(())
         }
@@ -426,7 +1285,25 @@ async fn run_async_command(
             print_command_result("tx-status", &mut result, numbers_format, &output_format)?;
             Ok(())
         }
-        Commands::Script(_) => unreachable!(),
+        Commands::Abi(abi) => {
+            let printed = starknet_commands::abi::abi(&abi, &provider, abi_cache.as_ref()).await?;
+            match &abi.abi_file {
+                Some(abi_file) => {
+                    std::fs::write(abi_file, printed)
+                        .with_context(|| format!("Failed to write ABI to file = {abi_file}"))?;
+                    let mut result = Ok(AbiResponse {
+                        path: abi_file.clone(),
+                    });
+                    print_command_result("abi", &mut result, numbers_format, &output_format)?;
+                }
+                None => println!("{printed}"),
+            }
+            Ok(())
+        }
+        Commands::Script(_)
+        | Commands::Doctor(_)
+        | Commands::Schema(_)
+        | Commands::ClassHash(_) => unreachable!(),
     }
 }
 
@@ -444,48 +1321,65 @@ fn run_script_command(
         }
         starknet_commands::script::Commands::Run(run) => {
             let manifest_path = assert_manifest_path_exists()?;
-            let package_metadata = get_package_metadata(&manifest_path, &run.package)?;
+            let metadata_with_deps = get_scarb_metadata_with_deps(&manifest_path)?;
+            let package_metadata = select_package_metadata(&metadata_with_deps, &run.package)?;
 
-            let mut config = load_global_config::<CastConfig>(
-                &Some(package_metadata.root.clone()),
-                &cli.profile,
+            let config = load_cast_config(cli, Some(package_metadata.root.clone()))?;
+            let provider = get_provider_with_retries(
+                &config.url,
+                cli.trace.as_ref(),
+                config.network_timeout_retries,
             )?;
-            update_cast_config(&mut config, cli);
-            let provider = get_provider(&config.url)?;
-            runtime.block_on(verify_and_warn_if_incompatible_rpc_version(
+            runtime.block_on(verify_rpc_version(
                 &provider,
                 &config.url,
+                cli.rpc_version.as_ref(),
             ))?;
 
             let mut artifacts = build_and_load_artifacts(
                 &package_metadata,
                 &BuildConfig {
                     scarb_toml_path: manifest_path.clone(),
+                    metadata: &metadata_with_deps,
                     json: cli.json,
                     profile: cli.profile.clone().unwrap_or("dev".to_string()),
+                    build_profile: None,
+                    target: None,
+                    scarb_version: None,
+                    force_rebuild: false,
+                    no_build: false,
+                    build_args: vec![],
                 },
             )
-            .expect("Failed to build artifacts");
+            .context("Failed to build artifacts")?;
             // TODO(#2042): remove duplicated compilation
             build(
                 &package_metadata,
                 &BuildConfig {
                     scarb_toml_path: manifest_path.clone(),
+                    metadata: &metadata_with_deps,
                     json: cli.json,
                     profile: "dev".to_string(),
+                    build_profile: None,
+                    target: None,
+                    scarb_version: None,
+                    force_rebuild: false,
+                    no_build: false,
+                    build_args: vec![],
                 },
             )
-            .expect("Failed to build script");
-            let metadata_with_deps = get_scarb_metadata_with_deps(&manifest_path)?;
+            .context("Failed to build script")?;
 
             let chain_id = runtime.block_on(get_chain_id(&provider))?;
+            let network = chain_id_to_network_name(chain_id);
             let state_file_path = if run.no_state_file {
                 None
             } else {
-                Some(package_metadata.root.join(get_default_state_file_name(
-                    &run.script_name,
-                    &chain_id_to_network_name(chain_id),
-                )))
+                Some(
+                    package_metadata
+                        .root
+                        .join(get_default_state_file_name(&run.script_name, &network)),
+                )
             };
 
             let mut result = starknet_commands::script::run::run(
@@ -497,6 +1391,10 @@ fn run_script_command(
                 runtime,
                 &config,
                 state_file_path,
+                run.plan.as_ref(),
+                run.manifest_out.as_ref(),
+                &network,
+                run.fee_report,
             );
 
             print_command_result("script run", &mut result, numbers_format, output_format)?;
@@ -506,6 +1404,229 @@ fn run_script_command(
     Ok(())
 }
 
+fn dump_config(
+    cli: &Cli,
+    config: &CastConfig,
+    output_path: Option<&Utf8PathBuf>,
+    numbers_format: NumbersFormat,
+    output_format: &OutputFormat,
+) -> Result<()> {
+    let dumped = starknet_commands::show_config::dump_config(
+        config,
+        cli.sncast_profile().as_deref(),
+        &get_cli_overrides(cli),
+    )?;
+    match output_path {
+        Some(output_path) => {
+            std::fs::write(output_path, dumped)
+                .with_context(|| format!("Failed to write config to file = {output_path}"))?;
+            let mut result = Ok(DumpConfigResponse {
+                path: output_path.clone(),
+            });
+            print_command_result("show-config", &mut result, numbers_format, output_format)?;
+        }
+        None => print!("{dumped}"),
+    }
+    Ok(())
+}
+
+fn run_doctor_command(
+    cli: &Cli,
+    runtime: &Runtime,
+    numbers_format: NumbersFormat,
+    output_format: &OutputFormat,
+) -> Result<()> {
+    let config = load_cast_config(cli, None);
+    let response = runtime.block_on(starknet_commands::doctor::doctor(
+        config,
+        cli.sncast_profile(),
+    ));
+    let critical_checks_failed = response.critical_checks_failed;
+
+    print_command_result("doctor", &mut Ok(response), numbers_format, output_format)?;
+    if critical_checks_failed {
+        bail!("Some of the critical checks failed");
+    }
+    Ok(())
+}
+
+/// Names of `show-config` response fields which were overridden with CLI arguments
+fn get_cli_overrides(cli: &Cli) -> Vec<&'static str> {
+    [
+        ("rpc_url", cli.rpc_url.is_some()),
+        ("account", cli.account.is_some()),
+        ("accounts_file_path", cli.accounts_file_path.is_some()),
+        ("keystore", cli.keystore.is_some()),
+        (
+            "network_timeout_retries",
+            cli.network_timeout_retries.is_some(),
+        ),
+        (
+            "wait_timeout",
+            cli.poll_timeout.is_some() || cli.wait_timeout.is_some(),
+        ),
+        (
+            "wait_retry_interval",
+            cli.poll_interval.is_some() || cli.wait_retry_interval.is_some(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, passed)| *passed)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+/// Prints result of a single call executed by `multicall run --non-atomic --stream`.
+/// In JSON mode results are printed to stdout as separate JSON lines, otherwise to stderr,
+/// so they do not interfere with the final response
+fn print_multicall_progress(
+    response: MulticallCallExecutedResponse,
+    numbers_format: NumbersFormat,
+    output_format: &OutputFormat,
+) {
+    match output_format {
+        OutputFormat::Json => {
+            // Printing to stdout fails only when it is closed, the final response would fail as well then
+            let _ = print_command_result(
+                "multicall run",
+                &mut Ok(response),
+                numbers_format,
+                output_format,
+            );
+        }
+        OutputFormat::Human
+        | OutputFormat::TransactionHashOnly
+        | OutputFormat::JsonErrorsOnly
+        | OutputFormat::Template(_) => {
+            eprintln!(
+                "call {}/{}: transaction_hash: {:#x}",
+                response.call_number.0, response.calls_count.0, response.transaction_hash.0
+            );
+        }
+    }
+}
+
+/// Describes the transaction about to be sent by a state-changing command
+fn transaction_summary(command: &Commands, chain_id: FieldElement, account: &str) -> Vec<String> {
+    let (action, max_fee) = match command {
+        Commands::Declare(declare) if declare.all => {
+            ("declare all contracts".to_string(), declare.max_fee)
+        }
+        Commands::Declare(declare) => (
+            format!("declare {}", declare.contracts.join(", ")),
+            declare.max_fee,
+        ),
+        Commands::Deploy(deploy) => (
+            format!("deploy class {:#x}", deploy.class_hash),
+            deploy.max_fee,
+        ),
+        Commands::Invoke(invoke) => {
+            let target = match (&invoke.contract_address, invoke.from_deploy) {
+                (Some(contract_address), _) => contract_address.to_string(),
+                (None, Some(tx_hash)) => format!("contract deployed in transaction {tx_hash:#x}"),
+                (None, None) => unreachable!("Contract address is required by clap"),
+            };
+            (
+                format!("invoke {} on {target}", invoke.function_name()),
+                invoke.max_fee,
+            )
+        }
+        _ => unreachable!("Only state-changing commands are confirmed"),
+    };
+    let max_fee = max_fee.map_or_else(
+        || "estimated automatically".to_string(),
+        |max_fee| format!("{max_fee:#}"),
+    );
+
+    vec![
+        format!("network: {}", chain_id_to_network_name(chain_id)),
+        format!("account: {account}"),
+        format!("action: {action}"),
+        format!("max fee: {max_fee}"),
+    ]
+}
+
+fn confirm_transaction(summary: &[String]) -> Result<()> {
+    println!("About to send a transaction:");
+    for line in summary {
+        println!("  {line}");
+    }
+    let input: String = prompt("Do you want to proceed? (y/N)")?;
+    if !matches!(input.trim().to_lowercase().as_str(), "y" | "yes") {
+        bail!("Transaction aborted");
+    }
+    Ok(())
+}
+
+/// Prints the result of a transaction, including the nonce `--nonce next` was resolved to
+/// or the paymaster which sponsored it
+fn print_transaction_result<T: CommandResponse>(
+    command: &str,
+    result: Result<T>,
+    resolved_nonce: Option<FieldElement>,
+    paymaster: Option<&Paymaster>,
+    numbers_format: NumbersFormat,
+    output_format: &OutputFormat,
+) -> Result<()> {
+    if let Some(nonce) = resolved_nonce {
+        let mut result = result.map(|response| NonceResponse {
+            response,
+            nonce: Felt(nonce),
+        });
+        print_command_result(command, &mut result, numbers_format, output_format)?;
+        exit_if_transaction_reverted(&result);
+    } else if let Some(paymaster) = paymaster {
+        let mut result = result.map(|response| SponsoredResponse {
+            response,
+            sponsored_by: paymaster.host(),
+        });
+        print_command_result(command, &mut result, numbers_format, output_format)?;
+        exit_if_transaction_reverted(&result);
+    } else {
+        let mut result = result;
+        print_command_result(command, &mut result, numbers_format, output_format)?;
+        exit_if_transaction_reverted(&result);
+    }
+    Ok(())
+}
+
+/// Wraps the response of `declare` with the sizes of the declared contract
+fn sized_response<T>(response: T, sizes: &ContractSizes) -> SizedResponse<T> {
+    SizedResponse {
+        response,
+        sierra_size: Decimal(sizes.sierra_size as u64),
+        casm_size: Decimal(sizes.casm_size as u64),
+        bytecode_length: Decimal(sizes.bytecode_length as u64),
+    }
+}
+
+/// Makes the process exit with a failure code if the command failed because a waited transaction reverted,
+/// so that scripts relying on the exit code notice it
+fn exit_if_transaction_reverted<T>(result: &Result<T>) {
+    if matches!(result, Err(error) if is_transaction_reverted_error(error)) {
+        std::process::exit(ErrorKind::Revert.exit_code());
+    }
+}
+
+/// Loads the config of the selected profile from `snfoundry.toml` and applies CLI overrides to it
+fn load_cast_config(cli: &Cli, package_root: Option<Utf8PathBuf>) -> Result<CastConfig> {
+    tracing::debug!(?package_root, profile = ?cli.sncast_profile(), "Loading configuration");
+    let config = match &cli.config_inline {
+        Some(config_inline) => load_global_config_with_overrides::<CastConfig>(
+            &package_root,
+            &cli.sncast_profile(),
+            |raw| apply_inline_config(raw, config_inline),
+        ),
+        None => load_global_config::<CastConfig>(&package_root, &cli.sncast_profile()),
+    };
+    config
+        .map(|mut config| {
+            update_cast_config(&mut config, cli);
+            config
+        })
+        .map_err(|error| SncastError::categorize(error, SncastError::Config))
+}
+
 fn update_cast_config(config: &mut CastConfig, cli: &Cli) {
     macro_rules! clone_or_else {
         ($field:expr, $config_field:expr) => {
@@ -516,6 +1637,12 @@ fn update_cast_config(config: &mut CastConfig, cli: &Cli) {
     config.url = clone_or_else!(cli.rpc_url, config.url);
     config.account = clone_or_else!(cli.account, config.account);
     config.keystore = cli.keystore.clone().or(config.keystore.clone());
+    config.keystore_format = cli.keystore_format.unwrap_or(config.keystore_format);
+    config.signer_command = cli.signer_command.clone().or(config.signer_command.clone());
+    config.network_timeout_retries = cli
+        .network_timeout_retries
+        .unwrap_or(config.network_timeout_retries);
+    config.max_calldata_len = cli.max_calldata_len.or(config.max_calldata_len);
 
     if config.accounts_file == Utf8PathBuf::default() {
         config.accounts_file = Utf8PathBuf::from(DEFAULT_ACCOUNTS_FILE);
@@ -524,11 +1651,36 @@ fn update_cast_config(config: &mut CastConfig, cli: &Cli) {
 
     config.accounts_file = Utf8PathBuf::from(shellexpand::tilde(&new_accounts_file).to_string());
 
-    config.wait_params = ValidatedWaitParams::new(
-        clone_or_else!(
-            cli.wait_retry_interval,
-            config.wait_params.get_retry_interval()
-        ),
-        clone_or_else!(cli.wait_timeout, config.wait_params.get_timeout()),
-    );
+    if cli.wait_timeout.is_some() {
+        print_as_warning(&anyhow!(
+            "`--wait-timeout` is deprecated and will be removed in the future, use `--poll-timeout` instead"
+        ));
+    }
+    if cli.wait_retry_interval.is_some() {
+        print_as_warning(&anyhow!(
+            "`--wait-retry-interval` is deprecated and will be removed in the future, use `--poll-interval` instead"
+        ));
+    }
+
+    let mut retry_interval = cli
+        .poll_interval
+        .or(cli
+            .wait_retry_interval
+            .map(|seconds| Duration::from_secs(seconds.into())))
+        .unwrap_or(config.wait_params.get_retry_interval());
+    let timeout = cli
+        .poll_timeout
+        .or(cli
+            .wait_timeout
+            .map(|seconds| Duration::from_secs(seconds.into())))
+        .unwrap_or(config.wait_params.get_timeout());
+
+    if retry_interval >= timeout {
+        print_as_warning(&anyhow!(
+            "Wait retry interval = {retry_interval:?} should be shorter than timeout = {timeout:?}"
+        ));
+        retry_interval = retry_interval.min(timeout);
+    }
+
+    config.wait_params = ValidatedWaitParams::from_durations(retry_interval, timeout);
 }
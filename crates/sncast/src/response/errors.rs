@@ -1,4 +1,4 @@
-use crate::{handle_rpc_error, ErrorData, WaitForTransactionError};
+use crate::{handle_rpc_error, ErrorData, TransactionError, WaitForTransactionError};
 use anyhow::anyhow;
 use conversions::serde::serialize::CairoSerialize;
 use starknet::core::types::StarknetError::{
@@ -6,6 +6,7 @@ use starknet::core::types::StarknetError::{
 };
 use starknet::core::types::{ContractErrorData, StarknetError, TransactionExecutionErrorData};
 use starknet::providers::ProviderError;
+use std::fmt::Display;
 use thiserror::Error;
 
 #[derive(Error, Debug, CairoSerialize)]
@@ -23,11 +24,182 @@ pub enum StarknetCommandError {
 #[must_use]
 pub fn handle_starknet_command_error(error: StarknetCommandError) -> anyhow::Error {
     match error {
-        StarknetCommandError::ProviderError(err) => handle_rpc_error(err),
-        _ => error.into(),
+        // Keeps the category of an error already categorized at its source
+        StarknetCommandError::UnknownError(error) => error,
+        error @ StarknetCommandError::ContractArtifactsNotFound(_) => {
+            SncastError::NotFound(error.into()).into()
+        }
+        StarknetCommandError::WaitForTransactionError(error) => SncastError::from(error).into(),
+        StarknetCommandError::ProviderError(error) => handle_rpc_error(error),
+    }
+}
+
+/// Error of a command, categorized by its cause at the place it occurred.
+/// Allows handling errors programmatically without matching on their messages;
+/// `error_kind` in the JSON output and the exit code of the CLI are derived from it
+#[derive(Error, Debug)]
+pub enum SncastError {
+    /// Failed to communicate with the RPC node
+    #[error(transparent)]
+    Network(anyhow::Error),
+    /// Configuration (`snfoundry.toml`, CLI arguments) is invalid or could not be loaded
+    #[error(transparent)]
+    Config(anyhow::Error),
+    /// Account is not able to send the transaction (e.g. invalid nonce, insufficient balance)
+    #[error(transparent)]
+    Account(anyhow::Error),
+    /// Transaction or call failed during execution or validation
+    #[error(transparent)]
+    Execution(anyhow::Error),
+    /// Transaction was accepted, but its execution reverted
+    #[error(transparent)]
+    Revert(anyhow::Error),
+    /// Requested contract, class, block, transaction or artifact does not exist
+    #[error(transparent)]
+    NotFound(anyhow::Error),
+}
+
+impl SncastError {
+    #[must_use]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            SncastError::Network(_) => ErrorKind::Network,
+            SncastError::Config(_) => ErrorKind::Config,
+            SncastError::Account(_) => ErrorKind::Account,
+            SncastError::Execution(_) => ErrorKind::Execution,
+            SncastError::Revert(_) => ErrorKind::Revert,
+            SncastError::NotFound(_) => ErrorKind::NotFound,
+        }
+    }
+
+    /// Assigns the category to an error coming from a given source,
+    /// unless the error was already categorized more specifically where it occurred
+    #[must_use]
+    pub fn categorize(
+        error: anyhow::Error,
+        category: fn(anyhow::Error) -> SncastError,
+    ) -> anyhow::Error {
+        if error.chain().any(|cause| cause.is::<SncastError>()) {
+            error
+        } else {
+            category(error).into()
+        }
+    }
+}
+
+impl From<WaitForTransactionError> for SncastError {
+    fn from(error: WaitForTransactionError) -> Self {
+        match error {
+            error @ WaitForTransactionError::TransactionError(TransactionError::Reverted(_)) => {
+                SncastError::Revert(error.into())
+            }
+            error @ WaitForTransactionError::TransactionError(TransactionError::Rejected) => {
+                SncastError::Execution(error.into())
+            }
+            error @ WaitForTransactionError::TimedOut => SncastError::Network(error.into()),
+            WaitForTransactionError::ProviderError(error) => error.into(),
+        }
+    }
+}
+
+impl From<SNCastProviderError> for SncastError {
+    fn from(error: SNCastProviderError) -> Self {
+        let category = match &error {
+            SNCastProviderError::StarknetError(error) => match error {
+                SNCastStarknetError::ContractNotFound
+                | SNCastStarknetError::BlockNotFound
+                | SNCastStarknetError::InvalidTransactionIndex
+                | SNCastStarknetError::ClassHashNotFound
+                | SNCastStarknetError::TransactionHashNotFound => SncastError::NotFound,
+                SNCastStarknetError::InvalidTransactionNonce
+                | SNCastStarknetError::InsufficientMaxFee
+                | SNCastStarknetError::InsufficientAccountBalance
+                | SNCastStarknetError::NonAccount => SncastError::Account,
+                SNCastStarknetError::FailedToReceiveTransaction
+                | SNCastStarknetError::UnexpectedError(_) => SncastError::Network,
+                SNCastStarknetError::ContractError(_)
+                | SNCastStarknetError::TransactionExecutionError(_)
+                | SNCastStarknetError::ClassAlreadyDeclared
+                | SNCastStarknetError::ValidationFailure(_)
+                | SNCastStarknetError::CompilationFailed
+                | SNCastStarknetError::ContractClassSizeIsTooLarge
+                | SNCastStarknetError::DuplicateTx
+                | SNCastStarknetError::CompiledClassHashMismatch
+                | SNCastStarknetError::UnsupportedTxVersion
+                | SNCastStarknetError::UnsupportedContractClassVersion => SncastError::Execution,
+            },
+            SNCastProviderError::RateLimited | SNCastProviderError::UnknownError(_) => {
+                SncastError::Network
+            }
+        };
+        category(error.into())
     }
 }
 
+/// Category of an error returned by a command. Included as `error_kind` in the JSON output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Network,
+    Config,
+    Account,
+    Execution,
+    Revert,
+    NotFound,
+    /// Error which was not categorized where it occurred
+    Unknown,
+}
+
+impl ErrorKind {
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Network => "network",
+            ErrorKind::Config => "config",
+            ErrorKind::Account => "account",
+            ErrorKind::Execution => "execution",
+            ErrorKind::Revert => "revert",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::Unknown => "unknown",
+        }
+    }
+
+    /// Exit code of the CLI when a command fails with an error of this kind.
+    /// `2` is not used, as it is returned by clap for invalid arguments
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Unknown => 1,
+            ErrorKind::Network => 3,
+            ErrorKind::Config => 4,
+            ErrorKind::Account => 5,
+            ErrorKind::Execution => 6,
+            ErrorKind::Revert => 7,
+            ErrorKind::NotFound => 8,
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Returns the kind of the outermost [`SncastError`] in the error chain
+#[must_use]
+pub fn error_kind(error: &anyhow::Error) -> ErrorKind {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<SncastError>())
+        .map_or(ErrorKind::Unknown, SncastError::kind)
+}
+
+/// Checks whether the error was caused by a transaction that was accepted, but whose execution reverted
+#[must_use]
+pub fn is_transaction_reverted_error(error: &anyhow::Error) -> bool {
+    error_kind(error) == ErrorKind::Revert
+}
+
 #[derive(Debug, Error, CairoSerialize)]
 pub enum SNCastProviderError {
     #[error(transparent)]
@@ -137,3 +309,39 @@ impl From<StarknetError> for SNCastStarknetError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_kind_of_categorized_error() {
+        let error: anyhow::Error = SncastError::from(SNCastProviderError::StarknetError(
+            SNCastStarknetError::ContractNotFound,
+        ))
+        .into();
+        let error = Err::<(), _>(error)
+            .context("Failed to call the contract")
+            .unwrap_err();
+
+        assert_eq!(error_kind(&error), ErrorKind::NotFound);
+        assert_eq!(
+            format!("{error:#}"),
+            "Failed to call the contract: There is no contract at the specified address"
+        );
+    }
+
+    #[test]
+    fn test_categorize_keeps_category_from_source() {
+        let error = handle_rpc_error(SNCastProviderError::RateLimited);
+        let error = SncastError::categorize(error, SncastError::Account);
+        assert_eq!(error_kind(&error), ErrorKind::Network);
+
+        let error = SncastError::categorize(anyhow!("Invalid profile"), SncastError::Config);
+        assert_eq!(error_kind(&error), ErrorKind::Config);
+        assert_eq!(error.to_string(), "Invalid profile");
+
+        assert_eq!(error_kind(&anyhow!("Other error")), ErrorKind::Unknown);
+    }
+}
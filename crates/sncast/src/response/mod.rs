@@ -1,3 +1,4 @@
 pub mod errors;
 pub mod print;
+pub mod schema;
 pub mod structs;
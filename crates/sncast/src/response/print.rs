@@ -1,37 +1,78 @@
-use anyhow::Result;
-use serde_json::Value;
+use anyhow::{anyhow, Result};
+use console::style;
+use serde_json::{Map, Value};
 use starknet::core::types::FieldElement;
 use std::{collections::HashMap, fmt::Display, str::FromStr};
 
+use serde::ser::SerializeMap;
 use serde::{Serialize, Serializer};
 
 use crate::NumbersFormat;
 
-use super::structs::CommandResponse;
+use super::errors::error_kind;
+use super::structs::{CommandResponse, Felt};
+
+/// Version of the JSON output format, included as `schema_version` in every JSON output.
+/// Has to be incremented whenever a breaking change (removing or renaming a field, changing its type
+/// or meaning) is made to any of the command responses
+pub const JSON_SCHEMA_VERSION: &str = "1";
 
 pub enum OutputFormat {
     Json,
     Human,
+    TransactionHashOnly,
+    /// Successful responses are printed in human readable format, errors in JSON
+    JsonErrorsOnly,
+    /// Fields of successful responses are interpolated into `{field}` placeholders of the template,
+    /// errors are printed in human readable format
+    Template(String),
 }
 
 impl OutputFormat {
     #[must_use]
-    pub fn from_flag(json: bool) -> Self {
-        if json {
+    pub fn from_flags(
+        json: bool,
+        transaction_hash_only: bool,
+        json_errors_only: bool,
+        template: Option<String>,
+    ) -> Self {
+        assert!(
+            [
+                json,
+                transaction_hash_only,
+                json_errors_only,
+                template.is_some()
+            ]
+            .iter()
+            .filter(|flag| **flag)
+            .count()
+                <= 1,
+            "Exclusivity should be validated by clap"
+        );
+        if let Some(template) = template {
+            OutputFormat::Template(template)
+        } else if json {
             OutputFormat::Json
+        } else if transaction_hash_only {
+            OutputFormat::TransactionHashOnly
+        } else if json_errors_only {
+            OutputFormat::JsonErrorsOnly
         } else {
             OutputFormat::Human
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone)]
 pub enum OutputValue {
     String(String),
+    Bool(bool),
     Array(Vec<OutputValue>),
+    /// Fields of a nested struct, e.g. an entry of a list of results
+    Object(Vec<(String, OutputValue)>),
 }
 
-/// Constrained subset of `serde::json`. No nested maps allowed.
+/// Constrained subset of `serde::json`. Numbers are represented as strings.
 type OutputData = Vec<(String, OutputValue)>;
 
 impl Serialize for OutputValue {
@@ -41,7 +82,15 @@ impl Serialize for OutputValue {
     {
         match &self {
             OutputValue::String(s) => serializer.serialize_str(s),
+            OutputValue::Bool(b) => serializer.serialize_bool(*b),
             OutputValue::Array(arr) => arr.serialize::<S>(serializer),
+            OutputValue::Object(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (key, value) in fields {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
         }
     }
 }
@@ -50,6 +99,7 @@ impl Display for OutputValue {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
             OutputValue::String(s) => s.fmt(fmt),
+            OutputValue::Bool(b) => b.fmt(fmt),
             OutputValue::Array(arr) => {
                 let arr_as_string = arr
                     .iter()
@@ -58,6 +108,14 @@ impl Display for OutputValue {
                     .join(", ");
                 write!(fmt, "[{arr_as_string}]")
             }
+            OutputValue::Object(fields) => {
+                let fields_as_string = fields
+                    .iter()
+                    .map(|(key, value)| format!("{key}: {value}"))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(fmt, "{{{fields_as_string}}}")
+            }
         }
     }
 }
@@ -68,18 +126,41 @@ pub fn print_command_result<T: CommandResponse>(
     numbers_format: NumbersFormat,
     output_format: &OutputFormat,
 ) -> Result<()> {
+    let output_format = match (output_format, &result) {
+        (OutputFormat::JsonErrorsOnly, Ok(_)) => &OutputFormat::Human,
+        (OutputFormat::JsonErrorsOnly, Err(_)) => &OutputFormat::Json,
+        (OutputFormat::Template(_), Err(_)) => &OutputFormat::Human,
+        (output_format, _) => output_format,
+    };
+
     let mut output: OutputData = vec![];
     output.push((
         String::from("command"),
         OutputValue::String(command.to_string()),
     ));
     output.extend(result_as_output_data(result));
+    if let (Err(error), OutputFormat::Json) = (&result, output_format) {
+        output.push((
+            String::from("error_kind"),
+            OutputValue::String(error_kind(error).to_string()),
+        ));
+    }
     let formatted_output = output
         .into_iter()
         .map(|(k, v)| (k, apply_numbers_formatting(v, numbers_format)))
         .collect();
 
-    for val in pretty_output(formatted_output, output_format)? {
+    let styled = match result {
+        Ok(_) => console::colors_enabled(),
+        Err(_) => console::colors_enabled_stderr(),
+    };
+    let lines = if styled && matches!(output_format, OutputFormat::Human) {
+        styled_human_output(&formatted_output)
+    } else {
+        pretty_output(formatted_output, output_format)?
+    };
+
+    for val in lines {
         match result {
             Ok(_) => println!("{val}"),
             Err(_) => eprintln!("{val}"),
@@ -88,14 +169,67 @@ pub fn print_command_result<T: CommandResponse>(
     Ok(())
 }
 
+/// Prints felts on a single line separated by spaces, so the output can be passed
+/// as calldata to another command
+pub fn print_raw_felts(felts: &[Felt], numbers_format: NumbersFormat) {
+    let values: Vec<String> = felts
+        .iter()
+        .map(|Felt(felt)| match numbers_format {
+            NumbersFormat::Decimal => format!("{felt:#}"),
+            NumbersFormat::Hex | NumbersFormat::Default => format!("{felt:#x}"),
+        })
+        .collect();
+    println!("{}", values.join(" "));
+}
+
+/// Human output for interactive use: values are aligned, keys are dimmed and hex values highlighted
+fn styled_human_output(output: &OutputData) -> Vec<String> {
+    let width = output.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+    output
+        .iter()
+        .map(|(key, value)| {
+            let padding = " ".repeat(width - key.len());
+            format!(
+                "{}{padding} {}",
+                style(format!("{key}:")).dim(),
+                styled_value(value)
+            )
+        })
+        .collect()
+}
+
+fn styled_value(value: &OutputValue) -> String {
+    match value {
+        OutputValue::String(s) if s.starts_with("0x") => style(s).cyan().to_string(),
+        OutputValue::String(s) => s.clone(),
+        OutputValue::Bool(b) => b.to_string(),
+        OutputValue::Array(arr) => {
+            let items = arr.iter().map(styled_value).collect::<Vec<_>>().join(", ");
+            format!("[{items}]")
+        }
+        OutputValue::Object(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(key, value)| format!("{}: {}", style(key).dim(), styled_value(value)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{{{fields}}}")
+        }
+    }
+}
+
 fn pretty_output(output: OutputData, output_format: &OutputFormat) -> Result<Vec<String>> {
     match output_format {
         OutputFormat::Json => {
-            let json_output: HashMap<String, OutputValue> = output.into_iter().collect();
+            let mut json_output: HashMap<String, OutputValue> = output.into_iter().collect();
+            json_output.insert(
+                String::from("schema_version"),
+                OutputValue::String(JSON_SCHEMA_VERSION.to_string()),
+            );
             let json_string = serde_json::to_string(&json_output)?;
             Ok(vec![json_string])
         }
-        OutputFormat::Human => {
+        OutputFormat::Human | OutputFormat::JsonErrorsOnly => {
             let mut result = vec![];
             for (key, value) in &output {
                 let value = value.to_string();
@@ -103,7 +237,68 @@ fn pretty_output(output: OutputData, output_format: &OutputFormat) -> Result<Vec
             }
             Ok(result)
         }
+        OutputFormat::TransactionHashOnly => {
+            if let Some((_, error)) = output.iter().find(|(key, _)| key == "error") {
+                return Ok(vec![format!("error: {error}")]);
+            }
+            output
+                .into_iter()
+                .find(|(key, _)| key == "transaction_hash")
+                .map(|(_, value)| vec![value.to_string()])
+                .ok_or_else(|| anyhow!("Command did not produce a transaction hash"))
+        }
+        OutputFormat::Template(template) => Ok(vec![render_template(template, &output)?]),
+    }
+}
+
+/// Checks that every `{field}` placeholder of the template refers to one of the given fields,
+/// so that an invalid template is reported before the command is executed
+pub fn validate_output_template(template: &str, fields: &[String]) -> Result<()> {
+    let output: OutputData = fields
+        .iter()
+        .map(|field| (field.clone(), OutputValue::String(String::new())))
+        .collect();
+    render_template(template, &output).map(|_| ())
+}
+
+/// Replaces `{field}` placeholders in the template with values of the response fields;
+/// `{{` and `}}` are printed as literal braces
+fn render_template(template: &str, output: &OutputData) -> Result<String> {
+    let mut rendered = String::new();
+    let mut chars = template.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                rendered.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                rendered.push('}');
+            }
+            '{' => {
+                let (field, rest) = chars.as_str().split_once('}').ok_or_else(|| {
+                    anyhow!("Unclosed placeholder in `--output-template` = {template}")
+                })?;
+                let value = output
+                    .iter()
+                    .find(|(key, _)| key == field)
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| {
+                        let available: Vec<&str> =
+                            output.iter().map(|(key, _)| key.as_str()).collect();
+                        anyhow!(
+                            "Unknown field = {field} in `--output-template`, available fields: {}",
+                            available.join(", ")
+                        )
+                    })?;
+                rendered.push_str(&value.to_string());
+                chars = rest.chars();
+            }
+            char => rendered.push(char),
+        }
     }
+    Ok(rendered)
 }
 
 fn result_as_output_data<T: CommandResponse>(result: &mut Result<T>) -> OutputData {
@@ -124,20 +319,26 @@ fn result_as_output_data<T: CommandResponse>(result: &mut Result<T>) -> OutputDa
 
 fn struct_value_to_output_data(struct_value: Value) -> OutputData {
     match struct_value {
-        Value::Object(obj) => obj
-            .into_iter()
-            .filter(|(_, v)| !(matches!(v, Value::Null)))
-            .map(|(k, v)| (k, value_to_output_value(v)))
-            .collect(),
+        Value::Object(obj) => object_to_output_data(obj),
         _ => panic!("Expected an object"),
     }
 }
 
+fn object_to_output_data(obj: Map<String, Value>) -> OutputData {
+    obj.into_iter()
+        .filter(|(_, v)| !(matches!(v, Value::Null)))
+        .map(|(k, v)| (k, value_to_output_value(v)))
+        .collect()
+}
+
 fn value_to_output_value(value: Value) -> OutputValue {
     match value {
         Value::Array(a) => OutputValue::Array(a.into_iter().map(value_to_output_value).collect()),
+        Value::Object(obj) => OutputValue::Object(object_to_output_data(obj)),
         Value::String(s) => OutputValue::String(s.to_string()),
-        s => panic!("{s:?} cannot be auto-serialized to output"),
+        Value::Number(n) => OutputValue::String(n.to_string()),
+        Value::Bool(b) => OutputValue::Bool(b),
+        Value::Null => panic!("Null cannot be auto-serialized to output"),
     }
 }
 
@@ -153,6 +354,7 @@ fn apply_numbers_formatting(value: OutputValue, formatting: NumbersFormat) -> Ou
             }
             OutputValue::String(input)
         }
+        OutputValue::Bool(b) => OutputValue::Bool(b),
         OutputValue::Array(arr) => {
             let formatted_arr = arr
                 .into_iter()
@@ -160,6 +362,12 @@ fn apply_numbers_formatting(value: OutputValue, formatting: NumbersFormat) -> Ou
                 .collect();
             OutputValue::Array(formatted_arr)
         }
+        OutputValue::Object(fields) => OutputValue::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, apply_numbers_formatting(value, formatting)))
+                .collect(),
+        ),
     }
 }
 
@@ -168,7 +376,9 @@ mod tests {
     use serde_json::{Map, Value};
 
     use crate::response::print::{
-        apply_numbers_formatting, struct_value_to_output_data, OutputData, OutputValue,
+        apply_numbers_formatting, pretty_output, render_template, struct_value_to_output_data,
+        styled_human_output, validate_output_template, OutputData, OutputFormat, OutputValue,
+        JSON_SCHEMA_VERSION,
     };
     use crate::NumbersFormat;
 
@@ -239,4 +449,150 @@ mod tests {
         )];
         assert_eq!(actual, json_value_exp);
     }
+
+    #[test]
+    fn test_struct_value_to_output_data_nested_objects() {
+        let json_value = serde_json::json!({
+            "contracts": [
+                {"name": "Map", "class_hash": "0x1", "declared": true, "error": null}
+            ]
+        });
+
+        let actual = struct_value_to_output_data(json_value);
+        let expected: OutputData = vec![(
+            String::from("contracts"),
+            OutputValue::Array(vec![OutputValue::Object(vec![
+                (
+                    String::from("class_hash"),
+                    OutputValue::String(String::from("0x1")),
+                ),
+                (String::from("declared"), OutputValue::Bool(true)),
+                (
+                    String::from("name"),
+                    OutputValue::String(String::from("Map")),
+                ),
+            ])]),
+        )];
+        assert_eq!(actual, expected);
+
+        let formatted = apply_numbers_formatting(actual[0].1.clone(), NumbersFormat::Decimal);
+        assert_eq!(
+            formatted.to_string(),
+            "[{class_hash: 1, declared: true, name: Map}]"
+        );
+
+        let json = pretty_output(actual, &OutputFormat::Json).unwrap();
+        let json: Value = serde_json::from_str(&json[0]).unwrap();
+        assert_eq!(json["contracts"][0]["declared"], true);
+        assert_eq!(json["contracts"][0]["class_hash"], "0x1");
+    }
+
+    #[test]
+    fn test_pretty_output_transaction_hash_only() {
+        let output: OutputData = vec![
+            (
+                String::from("command"),
+                OutputValue::String(String::from("invoke")),
+            ),
+            (
+                String::from("transaction_hash"),
+                OutputValue::String(String::from("0x123")),
+            ),
+        ];
+
+        let actual = pretty_output(output, &OutputFormat::TransactionHashOnly).unwrap();
+        assert_eq!(actual, vec![String::from("0x123")]);
+    }
+
+    #[test]
+    fn test_pretty_output_transaction_hash_only_missing_hash() {
+        let output: OutputData = vec![
+            (
+                String::from("command"),
+                OutputValue::String(String::from("call")),
+            ),
+            (
+                String::from("response"),
+                OutputValue::Array(vec![OutputValue::String(String::from("0x0"))]),
+            ),
+        ];
+
+        let err = pretty_output(output, &OutputFormat::TransactionHashOnly).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Command did not produce a transaction hash"));
+    }
+
+    #[test]
+    fn test_render_template() {
+        let output: OutputData = vec![
+            (
+                String::from("command"),
+                OutputValue::String(String::from("invoke")),
+            ),
+            (
+                String::from("transaction_hash"),
+                OutputValue::String(String::from("0x123")),
+            ),
+        ];
+
+        let actual = render_template("{{{command}}}: {transaction_hash}", &output).unwrap();
+        assert_eq!(actual, "{invoke}: 0x123");
+
+        let err = render_template("{tx_hash}", &output).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown field = tx_hash in `--output-template`, available fields: command, transaction_hash"
+        );
+    }
+
+    #[test]
+    fn test_validate_output_template() {
+        let fields = vec![String::from("command"), String::from("transaction_hash")];
+
+        assert!(validate_output_template("{command}: {transaction_hash} {{}}", &fields).is_ok());
+
+        let err = validate_output_template("{status}", &fields).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown field = status in `--output-template`, available fields: command, transaction_hash"
+        );
+    }
+
+    #[test]
+    fn test_pretty_output_json_contains_schema_version() {
+        let output: OutputData = vec![(
+            String::from("command"),
+            OutputValue::String(String::from("call")),
+        )];
+
+        let actual = pretty_output(output, &OutputFormat::Json).unwrap();
+        let json: Value = serde_json::from_str(&actual[0]).unwrap();
+        assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(json["command"], "call");
+    }
+
+    #[test]
+    fn test_styled_human_output_aligns_values() {
+        console::set_colors_enabled(false);
+        let output: OutputData = vec![
+            (
+                String::from("command"),
+                OutputValue::String(String::from("invoke")),
+            ),
+            (
+                String::from("transaction_hash"),
+                OutputValue::String(String::from("0x123")),
+            ),
+        ];
+
+        let actual = styled_human_output(&output);
+        assert_eq!(
+            actual,
+            vec![
+                String::from("command:          invoke"),
+                String::from("transaction_hash: 0x123"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,234 @@
+use anyhow::{anyhow, Result};
+use schemars::{schema_for, JsonSchema};
+use serde_json::{json, Map, Value};
+
+use super::print::JSON_SCHEMA_VERSION;
+use super::structs::{
+    AbiResponse, AccountAddResponse, AccountCreateResponse, AccountDeleteResponse,
+    AccountDeployMultipleResponse, AccountDeployResponse, AccountFixNonceResponse,
+    AccountMigrateFileResponse, AccountRenameResponse, AutoSaltResponse, CallBatchResponse,
+    CallResponse, ClassHashResponse, DeclareMultipleResponse, DeclareResponse, DecodedCallResponse,
+    DeployResponse, DoctorResponse, DumpConfigResponse, InvokeReplaceClassResponse, InvokeResponse,
+    MulticallCallExecutedResponse, MulticallInvokeResponse, MulticallNewResponse,
+    MulticallRunResponse, NonceResponse, ScriptInitResponse, ScriptRunResponse, ShowConfigResponse,
+    SizedResponse, SponsoredResponse, TransactionStatusResponse,
+};
+
+/// Names of commands, as printed in the `command` field of their output
+pub const SCHEMA_COMMANDS: [&str; 20] = [
+    "abi",
+    "account add",
+    "account create",
+    "account delete",
+    "account deploy",
+    "account fix-nonce",
+    "account migrate-file",
+    "account rename",
+    "call",
+    "class-hash",
+    "declare",
+    "deploy",
+    "doctor",
+    "invoke",
+    "multicall new",
+    "multicall run",
+    "script init",
+    "script run",
+    "show-config",
+    "tx-status",
+];
+
+/// Returns JSON Schema of the successful `--json` output of given command.
+/// Commands which can print different responses, depending on the passed flags, are described with `anyOf`
+pub fn command_output_schema(command: &str) -> Result<Value> {
+    let variants = match command {
+        "abi" => vec![response_schema::<AbiResponse>(command)],
+        "account add" => vec![response_schema::<AccountAddResponse>(command)],
+        "account create" => vec![response_schema::<AccountCreateResponse>(command)],
+        "account delete" => vec![response_schema::<AccountDeleteResponse>(command)],
+        "account deploy" => vec![
+            response_schema::<AccountDeployResponse>(command),
+            response_schema::<AccountDeployMultipleResponse>(command),
+        ],
+        "account fix-nonce" => vec![response_schema::<AccountFixNonceResponse>(command)],
+        "account migrate-file" => vec![response_schema::<AccountMigrateFileResponse>(command)],
+        "account rename" => vec![response_schema::<AccountRenameResponse>(command)],
+        "call" => vec![
+            response_schema::<CallResponse>(command),
+            response_schema::<DecodedCallResponse>(command),
+            response_schema::<CallBatchResponse>(command),
+        ],
+        "class-hash" => vec![response_schema::<ClassHashResponse>(command)],
+        "declare" => vec![
+            response_schema::<SizedResponse<DeclareResponse>>(command),
+            response_schema::<SizedResponse<ClassHashResponse>>(command),
+            response_schema::<DeclareMultipleResponse>(command),
+        ],
+        "deploy" => vec![
+            response_schema::<AutoSaltResponse<DeployResponse>>(command),
+            response_schema::<NonceResponse<AutoSaltResponse<DeployResponse>>>(command),
+            response_schema::<SponsoredResponse<AutoSaltResponse<DeployResponse>>>(command),
+        ],
+        "doctor" => vec![response_schema::<DoctorResponse>(command)],
+        "invoke" => vec![
+            response_schema::<InvokeResponse>(command),
+            response_schema::<InvokeReplaceClassResponse>(command),
+            response_schema::<NonceResponse<InvokeResponse>>(command),
+            response_schema::<NonceResponse<InvokeReplaceClassResponse>>(command),
+            response_schema::<SponsoredResponse<InvokeResponse>>(command),
+            response_schema::<SponsoredResponse<InvokeReplaceClassResponse>>(command),
+        ],
+        "multicall new" => vec![response_schema::<MulticallNewResponse>(command)],
+        "multicall run" => vec![
+            response_schema::<MulticallInvokeResponse>(command),
+            response_schema::<MulticallRunResponse>(command),
+            response_schema::<MulticallCallExecutedResponse>(command),
+        ],
+        "script init" => vec![response_schema::<ScriptInitResponse>(command)],
+        "script run" => vec![response_schema::<ScriptRunResponse>(command)],
+        "show-config" => vec![
+            response_schema::<ShowConfigResponse>(command),
+            response_schema::<DumpConfigResponse>(command),
+        ],
+        "tx-status" => vec![response_schema::<TransactionStatusResponse>(command)],
+        _ => {
+            return Err(anyhow!(
+                "Unknown command = {command}. Possible values: {}",
+                SCHEMA_COMMANDS.join(", ")
+            ))
+        }
+    };
+
+    Ok(combine_variants(command, variants))
+}
+
+/// Names of all fields which may appear in the output of the command, sorted;
+/// `schema_version` is omitted as it is only a part of the JSON output
+pub fn command_output_fields(command: &str) -> Result<Vec<String>> {
+    let schema = command_output_schema(command)?;
+    let variants = match schema.get("anyOf") {
+        Some(Value::Array(variants)) => variants.iter().collect(),
+        _ => vec![&schema],
+    };
+
+    let mut fields: Vec<String> = variants
+        .into_iter()
+        .filter_map(|variant| variant["properties"].as_object())
+        .flat_map(|properties| properties.keys().cloned())
+        .filter(|field| field != "schema_version")
+        .collect();
+    fields.sort();
+    fields.dedup();
+    Ok(fields)
+}
+
+/// Schema of the response extended with fields added to every JSON output
+fn response_schema<T: JsonSchema>(command: &str) -> Value {
+    let mut schema = serde_json::to_value(schema_for!(T)).expect("Failed to serialize schema");
+    let object = schema
+        .as_object_mut()
+        .expect("Schema of a response should be an object");
+
+    let properties = object
+        .entry("properties")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .expect("Schema properties should be an object");
+    properties.insert("command".to_string(), json!({ "const": command }));
+    properties.insert(
+        "schema_version".to_string(),
+        json!({ "const": JSON_SCHEMA_VERSION }),
+    );
+
+    let required = object
+        .entry("required")
+        .or_insert_with(|| Value::Array(vec![]))
+        .as_array_mut()
+        .expect("Schema required fields should be an array");
+    required.push(json!("command"));
+    required.push(json!("schema_version"));
+
+    schema
+}
+
+fn combine_variants(command: &str, mut variants: Vec<Value>) -> Value {
+    if variants.len() == 1 {
+        let mut schema = variants.remove(0);
+        schema["title"] = json!(command);
+        return schema;
+    }
+
+    // References to definitions are resolved against the root schema, so they are moved there
+    let mut definitions = Map::new();
+    for variant in &mut variants {
+        let variant = variant.as_object_mut().expect("Schema should be an object");
+        variant.remove("$schema");
+        if let Some(Value::Object(variant_definitions)) = variant.remove("definitions") {
+            definitions.extend(variant_definitions);
+        }
+    }
+
+    let mut schema = json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": command,
+        "anyOf": variants,
+    });
+    if !definitions.is_empty() {
+        schema["definitions"] = Value::Object(definitions);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_for_every_command() {
+        for command in SCHEMA_COMMANDS {
+            assert!(command_output_schema(command).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_single_response_schema() {
+        let schema = command_output_schema("invoke").unwrap();
+
+        assert_eq!(schema["title"], "invoke");
+        assert_eq!(schema["properties"]["transaction_hash"]["type"], "string");
+        assert_eq!(schema["properties"]["command"]["const"], "invoke");
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&json!("transaction_hash")));
+        assert!(required.contains(&json!("schema_version")));
+    }
+
+    #[test]
+    fn test_multiple_responses_schema() {
+        let schema = command_output_schema("declare").unwrap();
+
+        assert_eq!(schema["title"], "declare");
+        assert_eq!(schema["anyOf"].as_array().unwrap().len(), 2);
+        assert!(schema["anyOf"][0].get("$schema").is_none());
+    }
+
+    #[test]
+    fn test_command_output_fields() {
+        assert_eq!(
+            command_output_fields("invoke").unwrap(),
+            vec!["command", "transaction_hash"]
+        );
+        assert_eq!(
+            command_output_fields("call").unwrap(),
+            vec!["command", "decoded", "response", "responses"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_command() {
+        let err = command_output_schema("nonexistent").unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Unknown command = nonexistent. Possible values: account add"));
+    }
+}
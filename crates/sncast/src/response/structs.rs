@@ -1,5 +1,8 @@
 use camino::Utf8PathBuf;
 use conversions::serde::serialize::CairoSerialize;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, Serializer};
 use starknet::core::types::FieldElement;
 
@@ -27,6 +30,35 @@ impl Serialize for Felt {
     }
 }
 
+// Both types are serialized as strings, hex for `Felt` and decimal for `Decimal`
+impl JsonSchema for Decimal {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "Decimal".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl JsonSchema for Felt {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "Felt".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 fn serialize_as_decimal<S>(value: &Felt, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -35,95 +67,348 @@ where
     serializer.serialize_str(&format!("{val:#}"))
 }
 
-pub trait CommandResponse: Serialize {}
+fn serialize_optional_as_decimal<S>(value: &Option<Felt>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serialize_as_decimal(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub trait CommandResponse: Serialize + JsonSchema {}
 
-#[derive(Serialize, CairoSerialize, Clone)]
+#[derive(Serialize, CairoSerialize, Clone, JsonSchema)]
 pub struct CallResponse {
     pub response: Vec<Felt>,
 }
 impl CommandResponse for CallResponse {}
 
-#[derive(Serialize, Deserialize, CairoSerialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Clone, JsonSchema)]
+pub struct DecodedCallResponse {
+    pub response: Vec<Felt>,
+    pub decoded: serde_json::Value,
+}
+impl CommandResponse for DecodedCallResponse {}
+
+#[derive(Serialize, Clone, JsonSchema)]
+pub struct CallBatchResponse {
+    pub responses: Vec<Vec<Felt>>,
+    pub decoded: Option<Vec<serde_json::Value>>,
+}
+impl CommandResponse for CallBatchResponse {}
+
+#[derive(Serialize, Deserialize, CairoSerialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InvokeResponse {
     pub transaction_hash: Felt,
 }
 impl CommandResponse for InvokeResponse {}
 
-#[derive(Clone, Serialize, Deserialize, CairoSerialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InvokeReplaceClassResponse {
+    pub transaction_hash: Felt,
+    pub old_class_hash: Felt,
+    pub new_class_hash: Felt,
+}
+impl CommandResponse for InvokeReplaceClassResponse {}
+
+#[derive(Clone, Serialize, Deserialize, CairoSerialize, Debug, PartialEq, JsonSchema)]
 pub struct DeployResponse {
     pub contract_address: Felt,
     pub transaction_hash: Felt,
 }
 impl CommandResponse for DeployResponse {}
 
-#[derive(Clone, Serialize, Deserialize, CairoSerialize, Debug, PartialEq)]
+/// Response of `deploy`, extended with the salt chosen with `--salt auto`
+#[derive(Serialize, JsonSchema)]
+pub struct AutoSaltResponse<T> {
+    #[serde(flatten)]
+    pub response: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<Felt>,
+}
+impl<T: CommandResponse> CommandResponse for AutoSaltResponse<T> {}
+
+/// Response of a transaction sent with `--nonce next`, extended with the resolved nonce
+#[derive(Serialize, JsonSchema)]
+pub struct NonceResponse<T> {
+    #[serde(flatten)]
+    pub response: T,
+    pub nonce: Felt,
+}
+impl<T: CommandResponse> CommandResponse for NonceResponse<T> {}
+
+/// Response of a transaction submitted through a paymaster, extended with the paymaster URL
+#[derive(Serialize, JsonSchema)]
+pub struct SponsoredResponse<T> {
+    #[serde(flatten)]
+    pub response: T,
+    pub sponsored_by: String,
+}
+impl<T: CommandResponse> CommandResponse for SponsoredResponse<T> {}
+
+/// Response of `declare`, extended with sizes of the contract class
+#[derive(Serialize, JsonSchema)]
+pub struct SizedResponse<T> {
+    #[serde(flatten)]
+    pub response: T,
+    pub sierra_size: Decimal,
+    pub casm_size: Decimal,
+    pub bytecode_length: Decimal,
+}
+impl<T: CommandResponse> CommandResponse for SizedResponse<T> {}
+
+#[derive(Clone, Serialize, Deserialize, CairoSerialize, Debug, PartialEq, JsonSchema)]
 pub struct DeclareResponse {
     pub class_hash: Felt,
     pub transaction_hash: Felt,
 }
 impl CommandResponse for DeclareResponse {}
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
+pub struct ClassHashResponse {
+    pub class_hash: Felt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compiled_class_hash: Option<Felt>,
+}
+impl CommandResponse for ClassHashResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub enum DeclareStatus {
+    Declared,
+    AlreadyDeclared,
+    Failed,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct DeclaredContractEntry {
+    pub contract_name: String,
+    pub status: DeclareStatus,
+    pub class_hash: Option<Felt>,
+    pub transaction_hash: Option<Felt>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct DeclareMultipleResponse {
+    pub contracts: Vec<DeclaredContractEntry>,
+}
+
+impl DeclareMultipleResponse {
+    #[must_use]
+    pub fn has_failures(&self) -> bool {
+        self.contracts
+            .iter()
+            .any(|entry| matches!(entry.status, DeclareStatus::Failed))
+    }
+}
+impl CommandResponse for DeclareMultipleResponse {}
+
+#[derive(Serialize, JsonSchema)]
 pub struct AccountCreateResponse {
     pub address: Felt,
     #[serde(serialize_with = "crate::response::structs::serialize_as_decimal")]
+    #[schemars(with = "String")]
     pub max_fee: Felt,
     pub add_profile: String,
     pub message: String,
+    pub transaction_hash: Option<Felt>,
+    pub funding: Option<String>,
 }
 
 impl CommandResponse for AccountCreateResponse {}
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone, JsonSchema)]
+pub struct AccountDeployResponse {
+    pub transaction_hash: Felt,
+    pub address: Option<Felt>,
+}
+
+impl CommandResponse for AccountDeployResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub enum AccountDeployStatus {
+    Deployed,
+    AlreadyDeployed,
+    Failed,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct AccountDeployEntry {
+    pub name: String,
+    pub status: AccountDeployStatus,
+    pub transaction_hash: Option<Felt>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct AccountDeployMultipleResponse {
+    pub accounts: Vec<AccountDeployEntry>,
+}
+
+impl AccountDeployMultipleResponse {
+    #[must_use]
+    pub fn has_failures(&self) -> bool {
+        self.accounts
+            .iter()
+            .any(|entry| matches!(entry.status, AccountDeployStatus::Failed))
+    }
+}
+impl CommandResponse for AccountDeployMultipleResponse {}
+
+#[derive(Serialize, JsonSchema)]
 pub struct AccountAddResponse {
     pub add_profile: String,
 }
 
 impl CommandResponse for AccountAddResponse {}
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct AccountDeleteResponse {
     pub result: String,
 }
 
 impl CommandResponse for AccountDeleteResponse {}
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
+pub struct AccountRenameResponse {
+    pub result: String,
+    pub updated_profiles: Option<Vec<String>>,
+}
+
+impl CommandResponse for AccountRenameResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub struct AccountMigrateFileResponse {
+    pub changes: Vec<String>,
+    #[schemars(with = "Option<String>")]
+    pub backup_path: Option<Utf8PathBuf>,
+}
+
+impl CommandResponse for AccountMigrateFileResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub struct AccountFixNonceResponse {
+    pub latest_nonce: Felt,
+    pub pending_nonce: Felt,
+    pub filled_nonce: Option<Felt>,
+    pub transaction_hash: Option<Felt>,
+}
+
+impl CommandResponse for AccountFixNonceResponse {}
+
+#[derive(Serialize, JsonSchema)]
 pub struct MulticallNewResponse {
+    #[schemars(with = "String")]
     pub path: Utf8PathBuf,
     pub content: String,
 }
 impl CommandResponse for MulticallNewResponse {}
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
+pub struct MulticallRunResponse {
+    pub transaction_hashes: Vec<Felt>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployed_contracts: Option<Vec<MulticallDeployedContract>>,
+}
+impl CommandResponse for MulticallRunResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub struct MulticallDeployedContract {
+    pub id: String,
+    pub contract_address: Felt,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub struct MulticallInvokeResponse {
+    pub transaction_hash: Felt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployed_contracts: Option<Vec<MulticallDeployedContract>>,
+}
+impl CommandResponse for MulticallInvokeResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub struct MulticallCallExecutedResponse {
+    pub call_number: Decimal,
+    pub calls_count: Decimal,
+    pub transaction_hash: Felt,
+}
+impl CommandResponse for MulticallCallExecutedResponse {}
+
+#[derive(Serialize, JsonSchema)]
 pub struct ShowConfigResponse {
     pub profile: Option<String>,
+    pub resolved_profile: String,
     pub chain_id: String,
     pub rpc_url: Option<String>,
     pub account: Option<String>,
+    #[schemars(with = "Option<String>")]
     pub accounts_file_path: Option<Utf8PathBuf>,
+    #[schemars(with = "Option<String>")]
     pub keystore: Option<Utf8PathBuf>,
-    pub wait_timeout: Option<Decimal>,
-    pub wait_retry_interval: Option<Decimal>,
+    pub wait_timeout: Option<String>,
+    pub wait_retry_interval: Option<String>,
+    pub network_timeout_retries: Option<Decimal>,
+    pub sources: Vec<String>,
+    pub rpc_version: Option<String>,
+    pub account_address: Option<Felt>,
+    pub account_status: Option<String>,
+    #[serde(serialize_with = "crate::response::structs::serialize_optional_as_decimal")]
+    #[schemars(with = "Option<String>")]
+    pub eth_balance: Option<Felt>,
+    #[serde(serialize_with = "crate::response::structs::serialize_optional_as_decimal")]
+    #[schemars(with = "Option<String>")]
+    pub strk_balance: Option<Felt>,
 }
 impl CommandResponse for ShowConfigResponse {}
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, JsonSchema)]
+pub struct DumpConfigResponse {
+    #[schemars(with = "String")]
+    pub path: Utf8PathBuf,
+}
+impl CommandResponse for DumpConfigResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub struct AbiResponse {
+    #[schemars(with = "String")]
+    pub path: Utf8PathBuf,
+}
+impl CommandResponse for AbiResponse {}
+
+#[derive(Serialize, JsonSchema)]
+pub struct DoctorResponse {
+    pub scarb: String,
+    pub profile: String,
+    pub rpc: String,
+    pub accounts: String,
+    #[serde(skip)]
+    pub critical_checks_failed: bool,
+}
+impl CommandResponse for DoctorResponse {}
+
+#[derive(Serialize, Debug, JsonSchema)]
 pub struct ScriptRunResponse {
     pub status: String,
     pub message: Option<String>,
+    /// Fees paid for the transactions sent by the script per operation, present only with `--fee-report`
+    pub declare_fee: Option<Felt>,
+    pub deploy_fee: Option<Felt>,
+    pub invoke_fee: Option<Felt>,
+    pub total_fee: Option<Felt>,
 }
 
 impl CommandResponse for ScriptRunResponse {}
 
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct ScriptInitResponse {
     pub message: String,
 }
 
 impl CommandResponse for ScriptInitResponse {}
 
-#[derive(Serialize, CairoSerialize)]
+#[derive(Serialize, CairoSerialize, JsonSchema)]
 pub enum FinalityStatus {
     Received,
     Rejected,
@@ -131,13 +416,13 @@ pub enum FinalityStatus {
     AcceptedOnL1,
 }
 
-#[derive(Serialize, CairoSerialize)]
+#[derive(Serialize, CairoSerialize, JsonSchema)]
 pub enum ExecutionStatus {
     Succeeded,
     Reverted,
 }
 
-#[derive(Serialize, CairoSerialize)]
+#[derive(Serialize, CairoSerialize, JsonSchema)]
 pub struct TransactionStatusResponse {
     pub finality_status: FinalityStatus,
     pub execution_status: Option<ExecutionStatus>,
@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use sncast::helpers::abi::{find_event_entry, find_function_entry};
+use sncast::helpers::abi_cache::AbiCache;
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::{get_class_abi, get_contract_abi, resolve_block_id};
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::FieldElement;
+use starknet::providers::JsonRpcClient;
+
+#[derive(Args)]
+#[command(about = "Fetch the ABI of a contract or a class and print it as formatted JSON", long_about = None)]
+pub struct Abi {
+    /// Address of the contract
+    #[clap(
+        short = 'a',
+        long,
+        alias = "address",
+        required_unless_present = "class_hash",
+        conflicts_with = "class_hash"
+    )]
+    pub contract_address: Option<FieldElement>,
+
+    /// Class hash of the class
+    #[clap(short = 'g', long)]
+    pub class_hash: Option<FieldElement>,
+
+    /// Block identifier on which the class of the contract is fetched.
+    /// Possible values: pending, latest, block hash (0x prefixed string),
+    /// block number (u64) and `time:<unix_seconds>` (latest block created at or before the timestamp)
+    #[clap(short, long, default_value = "pending", requires = "contract_address")]
+    pub block_id: String,
+
+    /// Only show the entry of the function with given name
+    #[clap(short, long, conflicts_with = "event")]
+    pub function: Option<String>,
+
+    /// Only show the entry of the event with given name; either its full path or the last segment of it
+    #[clap(short, long)]
+    pub event: Option<String>,
+
+    /// Save the ABI to the file at given path instead of printing it; it can be later passed to `--abi-file` of other commands
+    #[clap(long)]
+    pub abi_file: Option<Utf8PathBuf>,
+}
+
+/// Returns the ABI, filtered to the requested function or event, serialized as formatted JSON
+pub async fn abi(
+    abi: &Abi,
+    provider: &JsonRpcClient<TracedTransport>,
+    abi_cache: Option<&AbiCache>,
+) -> Result<String> {
+    let entries = match (abi.contract_address, abi.class_hash) {
+        (Some(contract_address), _) => {
+            let block_id = resolve_block_id(provider, &abi.block_id).await?;
+            get_contract_abi(provider, contract_address, &block_id, abi_cache).await?
+        }
+        (None, Some(class_hash)) => get_class_abi(provider, class_hash, abi_cache).await?,
+        (None, None) => unreachable!(
+            "Either `--contract-address` or `--class-hash` should be validated by clap"
+        ),
+    };
+
+    // Filtered entries are still wrapped in an array, so the output is a valid ABI
+    let entries: Vec<&AbiEntry> = match (&abi.function, &abi.event) {
+        (Some(function), _) => vec![find_function_entry(&entries, function)?],
+        (None, Some(event)) => vec![find_event_entry(&entries, event)?],
+        (None, None) => entries.iter().collect(),
+    };
+
+    serde_json::to_string_pretty(&entries).context("Failed to serialize ABI")
+}
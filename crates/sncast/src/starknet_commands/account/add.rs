@@ -6,11 +6,12 @@ use anyhow::{ensure, Context, Result};
 use camino::Utf8PathBuf;
 use clap::Args;
 use sncast::helpers::configuration::CastConfig;
+use sncast::helpers::traced_transport::TracedTransport;
 use sncast::response::structs::AccountAddResponse;
 use sncast::{check_class_hash_exists, get_chain_id};
 use sncast::{check_if_legacy_contract, get_class_hash_by_address};
 use starknet::core::types::FieldElement;
-use starknet::providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use starknet::providers::jsonrpc::JsonRpcClient;
 use starknet::signers::SigningKey;
 
 #[derive(Args, Debug)]
@@ -58,7 +59,7 @@ pub async fn add(
     rpc_url: &str,
     account: &str,
     accounts_file: &Utf8PathBuf,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     add: &Add,
 ) -> Result<AccountAddResponse> {
     let private_key = match &add.private_key_file_path {
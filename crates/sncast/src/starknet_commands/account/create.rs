@@ -1,3 +1,4 @@
+use crate::starknet_commands::account::deploy::{deploy, Deploy};
 use crate::starknet_commands::account::{
     add_created_profile_to_configuration, prepare_account_json, write_account_to_accounts_file,
     AccountType,
@@ -5,6 +6,7 @@ use crate::starknet_commands::account::{
 use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::Args;
+use primitive_types::U256;
 use serde_json::json;
 use sncast::helpers::braavos::BraavosAccountFactory;
 use sncast::helpers::configuration::CastConfig;
@@ -12,18 +14,20 @@ use sncast::helpers::constants::{
     ARGENT_CLASS_HASH, BRAAVOS_BASE_ACCOUNT_CLASS_HASH, BRAAVOS_CLASS_HASH,
     CREATE_KEYSTORE_PASSWORD_ENV_VAR, OZ_CLASS_HASH,
 };
+use sncast::helpers::keystore::KeystoreFormat;
+use sncast::helpers::traced_transport::TracedTransport;
 use sncast::response::structs::{AccountCreateResponse, Felt};
 use sncast::{
     check_class_hash_exists, check_if_legacy_contract, extract_or_generate_salt, get_chain_id,
-    get_keystore_password, handle_account_factory_error,
+    get_eth_balance, get_keystore_password, handle_account_factory_error, WaitForTx,
 };
 use starknet::accounts::{
     AccountDeployment, AccountFactory, ArgentAccountFactory, OpenZeppelinAccountFactory,
 };
 use starknet::core::types::{FeeEstimate, FieldElement};
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use starknet::signers::{LocalWallet, SigningKey};
+use url::Url;
 
 #[derive(Args, Debug)]
 #[command(about = "Create an account with all important secrets")]
@@ -47,6 +51,15 @@ pub struct Create {
     /// Custom contract class hash of declared contract
     #[clap(short, long, requires = "account_type")]
     pub class_hash: Option<FieldElement>,
+
+    /// If passed, the account will be deployed right after it is created.
+    /// Generated address has to be prefunded beforehand
+    #[clap(long)]
+    pub deploy: bool,
+
+    /// URL of a faucet endpoint the generated address is sent to, in order to fund it
+    #[clap(long)]
+    pub fund_faucet: Option<Url>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -55,12 +68,15 @@ pub async fn create(
     account: &str,
     accounts_file: &Utf8PathBuf,
     keystore: Option<Utf8PathBuf>,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     chain_id: FieldElement,
     account_type: AccountType,
     salt: Option<FieldElement>,
     add_profile: Option<String>,
     class_hash: Option<FieldElement>,
+    deploy_account: bool,
+    fund_faucet: Option<Url>,
+    wait_config: WaitForTx,
 ) -> Result<AccountCreateResponse> {
     let salt = extract_or_generate_salt(salt);
     let class_hash = class_hash.unwrap_or(match account_type {
@@ -110,12 +126,52 @@ pub async fn create(
             url: rpc_url.into(),
             account: account.into(),
             accounts_file: accounts_file.into(),
-            keystore,
+            keystore: keystore.clone(),
             ..Default::default()
         };
         add_created_profile_to_configuration(&add_profile, &config, &None)?;
     }
 
+    // Account is already saved at this point, so a faucet failure is only reported
+    let funding = match fund_faucet {
+        Some(faucet_url) => Some(
+            match fund_from_faucet(provider, &faucet_url, address, max_fee, wait_config).await {
+                Ok(balance) => format!("Address funded from the faucet, balance = {balance}"),
+                Err(err) => format!("Failed to fund address from the faucet: {err:#}"),
+            },
+        ),
+        None => None,
+    };
+
+    let transaction_hash = if deploy_account {
+        let balance = get_eth_balance(provider, address).await?;
+        if balance < U256::from_big_endian(&max_fee.to_bytes_be()) {
+            bail!(
+                "Account created, but it could not be deployed. Prefund address {address:#x} with at least {max_fee} tokens and deploy it with `account deploy` command"
+            );
+        }
+
+        let deploy_result = deploy(
+            provider,
+            accounts_file.clone(),
+            Deploy {
+                name: Some(account.to_string()),
+                all_pending: false,
+                max_fee: Some(max_fee),
+            },
+            chain_id,
+            wait_config,
+            account,
+            keystore,
+            // The keystore was just created by `account create`, so its format is detected
+            KeystoreFormat::Auto,
+        )
+        .await?;
+        Some(deploy_result.transaction_hash)
+    } else {
+        None
+    };
+
     Ok(AccountCreateResponse {
         address: Felt(address),
         max_fee: Felt(max_fee),
@@ -127,16 +183,57 @@ pub async fn create(
         } else {
             "--add-profile flag was not set. No profile added to snfoundry.toml".to_string()
         },
-        message: if account_json["deployed"] == json!(false) {
+        message: if transaction_hash.is_some() {
+            "Account successfully created and deployed".to_string()
+        } else if account_json["deployed"] == json!(false) {
             "Account successfully created. Prefund generated address with at least <max_fee> tokens. It is good to send more in the case of higher demand.".to_string()
         } else {
             "Account already deployed".to_string()
         },
+        transaction_hash,
+        funding,
     })
 }
 
+async fn fund_from_faucet(
+    provider: &JsonRpcClient<TracedTransport>,
+    faucet_url: &Url,
+    address: FieldElement,
+    min_balance: FieldElement,
+    wait_config: WaitForTx,
+) -> Result<U256> {
+    let amount = u64::try_from(min_balance).context("Deployment fee is too high to be funded")?;
+    let body = json!({
+        "address": format!("{address:#x}"),
+        "amount": amount,
+    });
+    reqwest::Client::new()
+        .post(faucet_url.clone())
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .with_context(|| format!("Failed to request funds from faucet = {faucet_url}"))?;
+
+    let min_balance = U256::from_big_endian(&min_balance.to_bytes_be());
+    let wait_params = wait_config.wait_params;
+    for _ in 0..wait_params.get_retries() {
+        let balance = get_eth_balance(provider, address).await?;
+        if balance >= min_balance {
+            return Ok(balance);
+        }
+        tokio::time::sleep(wait_params.get_retry_interval()).await;
+    }
+
+    bail!(
+        "Balance of address {address:#x} did not reach {min_balance} within {:?}",
+        wait_params.get_timeout()
+    )
+}
+
 async fn generate_account(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     salt: FieldElement,
     class_hash: FieldElement,
     account_type: &AccountType,
@@ -236,7 +333,12 @@ fn create_to_keystore(
     }
     let password = get_keystore_password(CREATE_KEYSTORE_PASSWORD_ENV_VAR)?;
     let private_key = SigningKey::from_secret_scalar(private_key);
-    private_key.save_as_keystore(keystore_path, &password)?;
+    if let Some(parent) = keystore_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    private_key
+        .save_as_keystore(keystore_path, &password)
+        .with_context(|| format!("Failed to save keystore to {keystore_path}"))?;
 
     let account_json = match account_type {
         AccountType::Oz => {
@@ -2,22 +2,30 @@ use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::Args;
 use serde_json::Map;
-use sncast::helpers::constants::{BRAAVOS_BASE_ACCOUNT_CLASS_HASH, KEYSTORE_PASSWORD_ENV_VAR};
-use sncast::response::structs::{Felt, InvokeResponse};
+use sncast::helpers::constants::{
+    BRAAVOS_BASE_ACCOUNT_CLASS_HASH, KEYSTORE_PASSWORD_ENV_VAR, UDC_ADDRESS,
+};
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::response::structs::{
+    AccountDeployEntry, AccountDeployMultipleResponse, AccountDeployResponse, AccountDeployStatus,
+    Felt,
+};
 use starknet::accounts::{AccountFactory, OpenZeppelinAccountFactory};
 use starknet::accounts::{AccountFactoryError, ArgentAccountFactory};
 use starknet::core::types::BlockTag::Pending;
 use starknet::core::types::{BlockId, FieldElement, StarknetError::ClassHashNotFound};
 use starknet::core::utils::get_contract_address;
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::ProviderError::StarknetError;
 use starknet::providers::{JsonRpcClient, Provider};
 use starknet::signers::{LocalWallet, SigningKey};
 
 use sncast::helpers::braavos::BraavosAccountFactory;
+use sncast::helpers::keystore::KeystoreFormat;
+use sncast::helpers::numbers::parse_number;
 use sncast::{
     chain_id_to_network_name, check_account_file_exists, get_account_data_from_accounts_file,
-    get_account_data_from_keystore, get_keystore_password, handle_account_factory_error,
+    get_account_data_from_keystore, get_accounts_data_from_accounts_file,
+    get_deployed_contract_address, get_keystore_password, handle_account_factory_error,
     handle_rpc_error, handle_wait_for_tx, AccountType, WaitForTx,
 };
 
@@ -28,21 +36,26 @@ pub struct Deploy {
     #[clap(short, long)]
     pub name: Option<String>,
 
+    /// Deploy all accounts from the accounts file which are not deployed yet on the current network
+    #[clap(long, conflicts_with = "name")]
+    pub all_pending: bool,
+
     /// Max fee for the transaction
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = parse_number)]
     pub max_fee: Option<FieldElement>,
 }
 
 #[allow(clippy::too_many_arguments)]
 pub async fn deploy(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     accounts_file: Utf8PathBuf,
     deploy_args: Deploy,
     chain_id: FieldElement,
     wait_config: WaitForTx,
     account: &str,
     keystore_path: Option<Utf8PathBuf>,
-) -> Result<InvokeResponse> {
+    keystore_format: KeystoreFormat,
+) -> Result<AccountDeployResponse> {
     if let Some(keystore_path_) = keystore_path {
         deploy_from_keystore(
             provider,
@@ -51,6 +64,7 @@ pub async fn deploy(
             wait_config,
             account,
             keystore_path_,
+            keystore_format,
         )
         .await
     } else {
@@ -70,15 +84,73 @@ pub async fn deploy(
     }
 }
 
+/// Deploys every account from the accounts file which is not deployed on the network of `chain_id`.
+/// Failure to deploy one account does not stop deployment of the others, it is reported in its entry instead
+pub async fn deploy_all_pending(
+    provider: &JsonRpcClient<TracedTransport>,
+    accounts_file: Utf8PathBuf,
+    chain_id: FieldElement,
+    max_fee: Option<FieldElement>,
+    wait_config: WaitForTx,
+) -> Result<AccountDeployMultipleResponse> {
+    let network_name = chain_id_to_network_name(chain_id);
+    let accounts_data = get_accounts_data_from_accounts_file(chain_id, &accounts_file)?;
+    if accounts_data.is_empty() {
+        bail!(
+            "No accounts found under network = {network_name} in accounts file = {accounts_file}"
+        );
+    }
+
+    let mut accounts = vec![];
+    for (name, account_data) in accounts_data {
+        if account_data.deployed == Some(true) {
+            accounts.push(AccountDeployEntry {
+                name,
+                status: AccountDeployStatus::AlreadyDeployed,
+                transaction_hash: None,
+                error: None,
+            });
+            continue;
+        }
+
+        let result = deploy_from_accounts_file(
+            provider,
+            accounts_file.clone(),
+            name.clone(),
+            chain_id,
+            max_fee,
+            wait_config,
+        )
+        .await;
+        accounts.push(match result {
+            Ok(response) => AccountDeployEntry {
+                name,
+                status: AccountDeployStatus::Deployed,
+                transaction_hash: Some(response.transaction_hash),
+                error: None,
+            },
+            Err(error) => AccountDeployEntry {
+                name,
+                status: AccountDeployStatus::Failed,
+                transaction_hash: None,
+                error: Some(format!("{error:#}")),
+            },
+        });
+    }
+
+    Ok(AccountDeployMultipleResponse { accounts })
+}
+
 async fn deploy_from_keystore(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     chain_id: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
     account: &str,
     keystore_path: Utf8PathBuf,
-) -> Result<InvokeResponse> {
-    let account_data = get_account_data_from_keystore(account, &keystore_path)?;
+    keystore_format: KeystoreFormat,
+) -> Result<AccountDeployResponse> {
+    let account_data = get_account_data_from_keystore(account, &keystore_path, keystore_format)?;
 
     let is_deployed = account_data
         .deployed
@@ -119,13 +191,13 @@ async fn deploy_from_keystore(
             salt,
             class_hash,
             &[private_key.verifying_key().scalar()],
-            chain_id,
+            FieldElement::ZERO,
         ),
         AccountType::Braavos => get_contract_address(
             salt,
             BRAAVOS_BASE_ACCOUNT_CLASS_HASH,
             &[private_key.verifying_key().scalar()],
-            chain_id,
+            FieldElement::ZERO,
         ),
     };
 
@@ -134,11 +206,12 @@ async fn deploy_from_keystore(
         .await
         .is_ok()
     {
-        InvokeResponse {
+        AccountDeployResponse {
             transaction_hash: Felt(FieldElement::ZERO),
+            address: None,
         }
     } else {
-        get_deployment_result(
+        let mut result = get_deployment_result(
             provider,
             account_type,
             class_hash,
@@ -148,7 +221,9 @@ async fn deploy_from_keystore(
             max_fee,
             wait_config,
         )
-        .await?
+        .await?;
+        confirm_deployed_address(provider, &mut result, address, wait_config).await?;
+        result
     };
 
     update_keystore_account(account, address)?;
@@ -157,18 +232,18 @@ async fn deploy_from_keystore(
 }
 
 async fn deploy_from_accounts_file(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     accounts_file: Utf8PathBuf,
     name: String,
     chain_id: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
-) -> Result<InvokeResponse> {
+) -> Result<AccountDeployResponse> {
     let account_data = get_account_data_from_accounts_file(&name, chain_id, &accounts_file)?;
 
     let private_key = SigningKey::from_secret_scalar(account_data.private_key);
 
-    let result = get_deployment_result(
+    let mut result = get_deployment_result(
         provider,
         account_data
             .account_type
@@ -185,6 +260,9 @@ async fn deploy_from_accounts_file(
         wait_config,
     )
     .await?;
+    if let Some(address) = account_data.address {
+        confirm_deployed_address(provider, &mut result, address, wait_config).await?;
+    }
 
     update_account_in_accounts_file(accounts_file, &name, chain_id)?;
 
@@ -193,7 +271,7 @@ async fn deploy_from_accounts_file(
 
 #[allow(clippy::too_many_arguments)]
 async fn get_deployment_result(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     account_type: AccountType,
     class_hash: FieldElement,
     private_key: SigningKey,
@@ -201,7 +279,7 @@ async fn get_deployment_result(
     chain_id: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
-) -> Result<InvokeResponse> {
+) -> Result<AccountDeployResponse> {
     match account_type {
         AccountType::Argent => {
             deploy_argent_account(
@@ -243,14 +321,14 @@ async fn get_deployment_result(
 }
 
 async fn deploy_oz_account(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     class_hash: FieldElement,
     private_key: SigningKey,
     salt: FieldElement,
     chain_id: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
-) -> Result<InvokeResponse> {
+) -> Result<AccountDeployResponse> {
     let factory = OpenZeppelinAccountFactory::new(
         class_hash,
         chain_id,
@@ -263,14 +341,14 @@ async fn deploy_oz_account(
 }
 
 async fn deploy_argent_account(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     class_hash: FieldElement,
     private_key: SigningKey,
     salt: FieldElement,
     chain_id: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
-) -> Result<InvokeResponse> {
+) -> Result<AccountDeployResponse> {
     let factory = ArgentAccountFactory::new(
         class_hash,
         chain_id,
@@ -284,14 +362,14 @@ async fn deploy_argent_account(
 }
 
 async fn deploy_braavos_account(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     class_hash: FieldElement,
     private_key: SigningKey,
     salt: FieldElement,
     chain_id: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
-) -> Result<InvokeResponse> {
+) -> Result<AccountDeployResponse> {
     let factory = BraavosAccountFactory::new(
         class_hash,
         BRAAVOS_BASE_ACCOUNT_CLASS_HASH,
@@ -306,12 +384,12 @@ async fn deploy_braavos_account(
 
 async fn deploy_account<T>(
     account_factory: T,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     salt: FieldElement,
     max_fee: Option<FieldElement>,
     wait_config: WaitForTx,
     class_hash: FieldElement,
-) -> Result<InvokeResponse>
+) -> Result<AccountDeployResponse>
 where
     T: AccountFactory + Sync,
 {
@@ -337,8 +415,9 @@ where
         },
         Err(_) => Err(anyhow!("Unknown AccountFactoryError")),
         Ok(result) => {
-            let return_value = InvokeResponse {
+            let return_value = AccountDeployResponse {
                 transaction_hash: Felt(result.transaction_hash),
+                address: None,
             };
             if let Err(message) = handle_wait_for_tx(
                 provider,
@@ -356,6 +435,31 @@ where
     }
 }
 
+/// After waiting for the deployment, checks that the account was deployed to the address
+/// it was created with, which would not be the case e.g. for mismatched class hash or salt
+async fn confirm_deployed_address(
+    provider: &JsonRpcClient<TracedTransport>,
+    result: &mut AccountDeployResponse,
+    expected_address: FieldElement,
+    wait_config: WaitForTx,
+) -> Result<()> {
+    if !wait_config.wait {
+        return Ok(());
+    }
+
+    // Accounts are deployed with a deploy account transaction, not through the Universal Deployer Contract
+    let deployed_address =
+        get_deployed_contract_address(provider, result.transaction_hash.0, UDC_ADDRESS).await?;
+    if deployed_address != expected_address {
+        bail!(
+            "Account was deployed to address = {deployed_address:#x}, which does not match the expected address = {expected_address:#x}. Make sure the class hash and salt of the account are correct"
+        );
+    }
+    result.address = Some(Felt(deployed_address));
+
+    Ok(())
+}
+
 fn update_account_in_accounts_file(
     accounts_file: Utf8PathBuf,
     account_name: &str,
@@ -0,0 +1,109 @@
+use crate::confirm_transaction;
+use anyhow::{anyhow, Context, Result};
+use clap::Args;
+use shared::print::print_as_warning;
+use sncast::commands::invoke::execute_calls;
+use sncast::helpers::constants::ETH_ADDRESS;
+use sncast::helpers::numbers::parse_number;
+use sncast::helpers::signer::CastSigner;
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::response::errors::handle_starknet_command_error;
+use sncast::response::structs::{AccountFixNonceResponse, Felt};
+use sncast::{get_nonce, WaitForTx};
+use starknet::accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::JsonRpcClient;
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Report the latest and pending nonce of the account and fill a nonce gap of a stuck account"
+)]
+pub struct FixNonce {
+    /// Send a no-op transaction (a transfer of 0 ETH from the account to itself) with the nonce,
+    /// so that transactions queued behind it can be executed. The account pays its fee
+    #[clap(long)]
+    pub fill: bool,
+
+    /// Nonce of the filler transaction; defaults to the pending nonce of the account
+    #[clap(long, requires = "fill", value_parser = parse_number)]
+    pub resume_from_nonce: Option<FieldElement>,
+
+    /// Max fee for the filler transaction. If not provided, will be automatically estimated
+    #[clap(short, long, requires = "fill", value_parser = parse_number)]
+    pub max_fee: Option<FieldElement>,
+
+    /// Assume "yes" as answer to confirmation prompt and run non-interactively
+    #[clap(long, requires = "fill")]
+    pub yes: bool,
+}
+
+pub async fn fix_nonce(
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    fix_nonce: &FixNonce,
+    wait_config: WaitForTx,
+) -> Result<AccountFixNonceResponse> {
+    let latest_nonce = get_nonce(account.provider(), "latest", account.address()).await?;
+    let pending_nonce = get_nonce(account.provider(), "pending", account.address()).await?;
+    if !fix_nonce.fill {
+        return Ok(AccountFixNonceResponse {
+            latest_nonce: Felt(latest_nonce),
+            pending_nonce: Felt(pending_nonce),
+            filled_nonce: None,
+            transaction_hash: None,
+        });
+    }
+
+    let nonce = fix_nonce.resume_from_nonce.unwrap_or(pending_nonce);
+    let calls = vec![filler_call(account.address())?];
+    let fee = match fix_nonce.max_fee {
+        Some(max_fee) => format!("at most {max_fee} WEI"),
+        None => {
+            let estimate = account
+                .execute(calls.clone())
+                .nonce(nonce)
+                .estimate_fee()
+                .await
+                .context("Failed to estimate fee of the filler transaction")?;
+            format!("estimated {} WEI", estimate.overall_fee)
+        }
+    };
+    print_as_warning(&anyhow!(
+        "The filler transaction is a regular transaction of the account, which pays its fee ({fee})"
+    ));
+    if !fix_nonce.yes {
+        confirm_transaction(&[
+            format!("account: {:#x}", account.address()),
+            format!("action: fill nonce {nonce:#x} with a transfer of 0 ETH to the account"),
+            format!("fee: {fee}"),
+        ])?;
+    }
+
+    let response = execute_calls(
+        account,
+        calls,
+        fix_nonce.max_fee,
+        None,
+        Some(nonce),
+        wait_config,
+    )
+    .await
+    .map_err(handle_starknet_command_error)?;
+
+    Ok(AccountFixNonceResponse {
+        latest_nonce: Felt(latest_nonce),
+        pending_nonce: Felt(pending_nonce),
+        filled_nonce: Some(Felt(nonce)),
+        transaction_hash: Some(response.transaction_hash),
+    })
+}
+
+/// Transfer of 0 ETH from the account to itself, which changes nothing except the nonce
+fn filler_call(account_address: FieldElement) -> Result<Call> {
+    Ok(Call {
+        to: ETH_ADDRESS,
+        selector: get_selector_from_name("transfer")?,
+        // recipient, amount as u256
+        calldata: vec![account_address, FieldElement::ZERO, FieldElement::ZERO],
+    })
+}
@@ -0,0 +1,253 @@
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use serde_json::{Map, Value};
+use sncast::helpers::constants::{ARGENT_CLASS_HASH, BRAAVOS_CLASS_HASH, OZ_CLASS_HASH};
+use sncast::response::structs::AccountMigrateFileResponse;
+use sncast::AccountType;
+use starknet::core::types::FieldElement;
+use std::str::FromStr;
+
+#[derive(Args, Debug)]
+#[command(
+    about = "Upgrade the accounts file to the current format, keeping a backup of the original"
+)]
+pub struct MigrateFile {}
+
+const FELT_FIELDS: [&str; 5] = ["private_key", "public_key", "address", "salt", "class_hash"];
+const BOOL_FIELDS: [&str; 2] = ["deployed", "legacy"];
+
+/// Upgrades all accounts in the file to the format written by `account create` and `account add`.
+/// The original file is copied to `<path>.bak` (or `<path>.bak.<n>` if a backup already exists) if anything changed;
+/// running it again changes nothing
+pub fn migrate_file(path: &Utf8PathBuf) -> Result<AccountMigrateFileResponse> {
+    let contents = std::fs::read_to_string(path).context("Failed to read accounts file")?;
+    let mut items: Map<String, Value> = serde_json::from_str(&contents)
+        .map_err(|_| anyhow!("Failed to parse accounts file at {path}"))?;
+
+    let mut changes = vec![];
+    for (network, accounts) in &mut items {
+        let accounts = accounts
+            .as_object_mut()
+            .with_context(|| format!("Accounts of network = {network} must be an object"))?;
+        for (name, account) in accounts {
+            let account = account
+                .as_object_mut()
+                .with_context(|| format!("Account {network}.{name} must be an object"))?;
+            let account_changes = migrate_account(account)
+                .with_context(|| format!("Failed to migrate account {network}.{name}"))?;
+            changes.extend(
+                account_changes
+                    .into_iter()
+                    .map(|change| format!("{network}.{name}: {change}")),
+            );
+        }
+    }
+
+    let backup_path = if changes.is_empty() {
+        None
+    } else {
+        let backup_path = unique_backup_path(path);
+        std::fs::copy(path, &backup_path)
+            .with_context(|| format!("Failed to write backup of accounts file = {backup_path}"))?;
+        std::fs::write(path, serde_json::to_string_pretty(&items)?)
+            .context("Failed to write accounts file")?;
+        Some(backup_path)
+    };
+
+    Ok(AccountMigrateFileResponse {
+        changes,
+        backup_path,
+    })
+}
+
+/// First of `<path>.bak`, `<path>.bak.1`, `<path>.bak.2`, ... which does not exist yet,
+/// so backups of earlier migrations are never overwritten
+fn unique_backup_path(path: &Utf8PathBuf) -> Utf8PathBuf {
+    let backup_path = Utf8PathBuf::from(format!("{path}.bak"));
+    if !backup_path.exists() {
+        return backup_path;
+    }
+    (1..)
+        .map(|index| Utf8PathBuf::from(format!("{backup_path}.{index}")))
+        .find(|backup_path| !backup_path.exists())
+        .expect("There should be a free backup path")
+}
+
+/// Upgrades a single account entry in place, returning descriptions of the applied changes
+fn migrate_account(account: &mut Map<String, Value>) -> Result<Vec<String>> {
+    let mut changes = vec![];
+
+    for field in FELT_FIELDS {
+        let value = match account.get(field) {
+            Some(Value::String(value)) => value.clone(),
+            Some(Value::Number(value)) if !value.is_f64() => value.to_string(),
+            // Integers which do not fit in 64 bits are parsed as floats and already lost their precision
+            Some(Value::Number(_)) => bail!(
+                "Field `{field}` is a number which can not be read exactly, write it as a `0x` prefixed hex string"
+            ),
+            _ => continue,
+        };
+        // Values are not included in the messages, as the private key is one of them
+        let felt = FieldElement::from_str(&value)
+            .map_err(|_| anyhow!("Field `{field}` is not a valid felt"))?;
+        let normalized = Value::String(format!("{felt:#x}"));
+        if account.get(field) != Some(&normalized) {
+            account.insert(field.to_string(), normalized);
+            changes.push(format!("`{field}` written as hex"));
+        }
+    }
+
+    for field in BOOL_FIELDS {
+        if let Some(Value::String(value)) = account.get(field) {
+            let value: bool = value
+                .parse()
+                .map_err(|_| anyhow!("Field `{field}` = {value} is not a boolean"))?;
+            account.insert(field.to_string(), Value::Bool(value));
+            changes.push(format!("`{field}` written as boolean"));
+        }
+    }
+
+    match account.get("type") {
+        Some(Value::String(account_type)) => {
+            let canonical = serde_json::to_value(AccountType::from_str(account_type)?)?;
+            if account.get("type") != Some(&canonical) {
+                changes.push(format!("`type` {account_type:?} renamed to {canonical}"));
+                account.insert("type".to_string(), canonical);
+            }
+        }
+        Some(_) => bail!("Field `type` must be a string"),
+        None => {
+            if let Some(account_type) = infer_account_type(account) {
+                let account_type = serde_json::to_value(account_type)?;
+                changes.push(format!("`type` {account_type} inferred from `class_hash`"));
+                account.insert("type".to_string(), account_type);
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Type of the account, if its class hash is the default one of an account type
+fn infer_account_type(account: &Map<String, Value>) -> Option<AccountType> {
+    let class_hash = FieldElement::from_str(account.get("class_hash")?.as_str()?).ok()?;
+    [
+        (OZ_CLASS_HASH, AccountType::Oz),
+        (ARGENT_CLASS_HASH, AccountType::Argent),
+        (BRAAVOS_CLASS_HASH, AccountType::Braavos),
+    ]
+    .into_iter()
+    .find(|(default_class_hash, _)| *default_class_hash == class_hash)
+    .map(|(_, account_type)| account_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_account, migrate_file};
+    use camino::Utf8PathBuf;
+    use serde_json::{json, Map, Value};
+    use tempfile::tempdir;
+
+    fn account(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_migrate_account() {
+        let mut old = account(json!({
+            "private_key": "0x00ABC",
+            "public_key": "123",
+            "address": "0x1",
+            "class_hash": "0x061dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f",
+            "deployed": "true",
+        }));
+
+        let changes = migrate_account(&mut old).unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                "`private_key` written as hex",
+                "`public_key` written as hex",
+                "`class_hash` written as hex",
+                "`deployed` written as boolean",
+                "`type` \"open_zeppelin\" inferred from `class_hash`",
+            ]
+        );
+        assert_eq!(
+            Value::Object(old.clone()),
+            json!({
+                "private_key": "0xabc",
+                "public_key": "0x7b",
+                "address": "0x1",
+                "class_hash": "0x61dac032f228abef9c6626f995015233097ae253a7f72d68552db02f2971b8f",
+                "deployed": true,
+                "type": "open_zeppelin",
+            })
+        );
+        assert!(migrate_account(&mut old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_account_number_fields() {
+        let mut old: Map<String, Value> =
+            serde_json::from_str(r#"{"private_key": 123, "public_key": "0x2"}"#).unwrap();
+
+        let changes = migrate_account(&mut old).unwrap();
+
+        assert_eq!(changes, vec!["`private_key` written as hex"]);
+        assert_eq!(old["private_key"], "0x7b");
+    }
+
+    #[test]
+    fn test_migrate_account_float_number() {
+        let mut old: Map<String, Value> =
+            serde_json::from_str(r#"{"private_key": 1.0e40, "public_key": "0x2"}"#).unwrap();
+
+        let err = migrate_account(&mut old).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Field `private_key` is a number which can not be read exactly, write it as a `0x` prefixed hex string"
+        );
+    }
+
+    #[test]
+    fn test_migrate_file_keeps_existing_backup() {
+        let temp_dir = tempdir().unwrap();
+        let path = Utf8PathBuf::from_path_buf(temp_dir.path().join("accounts.json")).unwrap();
+        let backup_path = Utf8PathBuf::from(format!("{path}.bak"));
+        std::fs::write(
+            &path,
+            r#"{"network": {"user": {"private_key": "0x01", "public_key": "0x2"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(&backup_path, "earlier backup").unwrap();
+
+        let response = migrate_file(&path).unwrap();
+
+        assert_eq!(
+            response.backup_path,
+            Some(Utf8PathBuf::from(format!("{path}.bak.1")))
+        );
+        assert_eq!(
+            std::fs::read_to_string(&backup_path).unwrap(),
+            "earlier backup"
+        );
+    }
+
+    #[test]
+    fn test_migrate_account_type_alias() {
+        let mut old = account(json!({
+            "private_key": "0x1",
+            "public_key": "0x2",
+            "type": "oz",
+        }));
+
+        let changes = migrate_account(&mut old).unwrap();
+
+        assert_eq!(changes, vec!["`type` \"oz\" renamed to \"open_zeppelin\""]);
+        assert_eq!(old["type"], "open_zeppelin");
+    }
+}
@@ -2,6 +2,9 @@ use crate::starknet_commands::account::add::Add;
 use crate::starknet_commands::account::create::Create;
 use crate::starknet_commands::account::delete::Delete;
 use crate::starknet_commands::account::deploy::Deploy;
+use crate::starknet_commands::account::fix_nonce::FixNonce;
+use crate::starknet_commands::account::migrate_file::MigrateFile;
+use crate::starknet_commands::account::rename::Rename;
 use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::{Args, Subcommand, ValueEnum};
@@ -18,6 +21,9 @@ pub mod add;
 pub mod create;
 pub mod delete;
 pub mod deploy;
+pub mod fix_nonce;
+pub mod migrate_file;
+pub mod rename;
 
 #[derive(Args)]
 #[command(about = "Creates and deploys an account to the Starknet")]
@@ -32,6 +38,9 @@ pub enum Commands {
     Create(Create),
     Deploy(Deploy),
     Delete(Delete),
+    Rename(Rename),
+    MigrateFile(MigrateFile),
+    FixNonce(FixNonce),
 }
 
 #[allow(clippy::doc_markdown)]
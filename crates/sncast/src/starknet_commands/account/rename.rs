@@ -0,0 +1,140 @@
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use configuration::find_config_file;
+use serde_json::Map;
+use sncast::response::structs::AccountRenameResponse;
+use toml_edit::DocumentMut;
+
+#[derive(Args, Debug)]
+#[command(about = "Rename an account in the accounts file, keeping its keys")]
+pub struct Rename {
+    /// Current name of the account
+    #[clap(long)]
+    pub from: String,
+
+    /// New name of the account
+    #[clap(long)]
+    pub to: String,
+
+    /// Network where the account exists; defaults to network of rpc node
+    #[clap(long)]
+    pub network: Option<String>,
+
+    /// If passed, profiles in snfoundry.toml using the account are updated to the new name
+    #[clap(long)]
+    pub update_profiles: bool,
+}
+
+pub fn rename(
+    from: &str,
+    to: &str,
+    path: &Utf8PathBuf,
+    network_name: &str,
+    update_profiles: bool,
+) -> Result<AccountRenameResponse> {
+    let contents = std::fs::read_to_string(path).context("Failed to read accounts file")?;
+    let mut items: Map<String, serde_json::Value> = serde_json::from_str(&contents)
+        .map_err(|_| anyhow!("Failed to parse accounts file at {path}"))?;
+
+    let accounts = items
+        .get_mut(network_name)
+        .and_then(serde_json::Value::as_object_mut)
+        .ok_or_else(|| anyhow!("No accounts defined for network = {network_name}"))?;
+    if accounts.contains_key(to) {
+        bail!("Account with name {to} already exists in network = {network_name}");
+    }
+    let account = accounts
+        .remove(from)
+        .ok_or_else(|| anyhow!("Account with name {from} does not exist"))?;
+    accounts.insert(to.to_string(), account);
+
+    // Profiles are updated first, so a failure leaves the accounts file untouched
+    let updated_profiles = if update_profiles {
+        Some(rename_account_in_profiles(&find_config_file()?, from, to)?)
+    } else {
+        None
+    };
+
+    std::fs::write(path, serde_json::to_string_pretty(&items)?)
+        .context("Failed to write accounts file")?;
+
+    Ok(AccountRenameResponse {
+        result: format!("Account {from} successfully renamed to {to}"),
+        updated_profiles,
+    })
+}
+
+/// Replaces the account name in all `sncast` profiles using it, preserving formatting of the file.
+/// Returns names of the updated profiles
+fn rename_account_in_profiles(
+    config_path: &Utf8PathBuf,
+    from: &str,
+    to: &str,
+) -> Result<Vec<String>> {
+    let contents = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read config file = {config_path}"))?;
+    let mut document: DocumentMut = contents
+        .parse()
+        .with_context(|| format!("Failed to parse config file = {config_path}"))?;
+
+    let mut updated_profiles = vec![];
+    if let Some(profiles) = document
+        .get_mut("sncast")
+        .and_then(toml_edit::Item::as_table_like_mut)
+    {
+        for (name, profile) in profiles.iter_mut() {
+            let Some(profile) = profile.as_table_like_mut() else {
+                continue;
+            };
+            if profile.get("account").and_then(toml_edit::Item::as_str) == Some(from) {
+                profile.insert("account", toml_edit::value(to));
+                updated_profiles.push(name.get().to_string());
+            }
+        }
+    }
+
+    if !updated_profiles.is_empty() {
+        std::fs::write(config_path, document.to_string())
+            .with_context(|| format!("Failed to write config file = {config_path}"))?;
+    }
+
+    Ok(updated_profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rename_account_in_profiles;
+    use camino::Utf8PathBuf;
+    use indoc::indoc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rename_account_in_profiles() {
+        let tempdir = tempdir().expect("Failed to create a temporary directory");
+        let config_path =
+            Utf8PathBuf::try_from(tempdir.path().join("snfoundry.toml")).expect("Invalid path");
+        std::fs::write(
+            &config_path,
+            indoc! {r#"
+                # Comments are preserved
+                [sncast.default]
+                account = "user1"
+                url = "http://127.0.0.1:5055/rpc"
+
+                [sncast.other]
+                account = "user2"
+            "#},
+        )
+        .unwrap();
+
+        let updated = rename_account_in_profiles(&config_path, "user1", "user3").unwrap();
+
+        assert_eq!(updated, vec!["default".to_string()]);
+        let contents = std::fs::read_to_string(&config_path).unwrap();
+        assert!(contents.contains("# Comments are preserved"));
+        assert!(contents.contains("account = \"user3\""));
+        assert!(contents.contains("account = \"user2\""));
+        assert!(!contents.contains("account = \"user1\""));
+    }
+}
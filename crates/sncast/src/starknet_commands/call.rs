@@ -1,53 +1,256 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use camino::Utf8PathBuf;
 use clap::Args;
-use sncast::response::errors::StarknetCommandError;
-use sncast::response::structs::{CallResponse, Felt};
-use starknet::core::types::{BlockId, FieldElement, FunctionCall};
-use starknet::providers::jsonrpc::HttpTransport;
-use starknet::providers::{JsonRpcClient, Provider};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::Deserialize;
+use serde_json::Value;
+use shared::print::print_as_warning;
+use sncast::helpers::abi::decode_function_outputs;
+use sncast::helpers::abi_cache::AbiCache;
+use sncast::helpers::address_book::{parse_address, AddressArg, AddressBook};
+use sncast::helpers::duration::parse_duration;
+use sncast::helpers::numbers::parse_felt_strict;
+use sncast::helpers::proxy::parse_impl_slot;
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::response::errors::handle_starknet_command_error;
+use sncast::response::structs::{CallBatchResponse, Felt};
+use sncast::{get_contract_abi, get_function_selector};
+use starknet::core::types::{BlockId, FieldElement};
+use starknet::providers::JsonRpcClient;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Args)]
 #[command(about = "Call a contract instance on Starknet", long_about = None)]
 pub struct Call {
-    /// Address of the called contract (hex)
-    #[clap(short = 'a', long)]
-    pub contract_address: FieldElement,
+    /// Address of the called contract (hex), or `@<name>` of an address book entry
+    #[clap(
+        short = 'a',
+        long,
+        required_unless_present = "batch_file",
+        value_parser = parse_address
+    )]
+    pub contract_address: Option<AddressArg>,
 
-    /// Name of the contract function to be called
-    #[clap(short, long)]
-    pub function: String,
+    /// Name of the contract function to be called, its selector as a `0x` prefixed hex,
+    /// or `@<path>` of a file holding the selector
+    #[clap(
+        short,
+        long,
+        required_unless_present_any = ["batch_file", "selector_from_file"]
+    )]
+    pub function: Option<String>,
+
+    /// Path to a file holding the selector of the called function as a single felt
+    #[clap(long, conflicts_with = "function")]
+    pub selector_from_file: Option<Utf8PathBuf>,
 
     /// Arguments of the called function (list of hex)
     #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
-    pub calldata: Vec<FieldElement>,
+    pub calldata: Vec<String>,
+
+    /// Read arguments of the called function from stdin, as felts separated by whitespace
+    #[clap(long, conflicts_with = "calldata")]
+    pub calldata_stdin: bool,
 
     /// Block identifier on which call should be performed.
-    /// Possible values: pending, latest, block hash (0x prefixed string)
-    /// and block number (u64)
+    /// Possible values: pending, latest, block hash (0x prefixed string),
+    /// block number (u64) and `time:<unix_seconds>` (latest block created at or before the timestamp)
     #[clap(short, long, default_value = "pending")]
     pub block_id: String,
+
+    /// Path to a file with the contract ABI; used instead of fetching the ABI from the network
+    #[clap(long)]
+    pub abi_file: Option<Utf8PathBuf>,
+
+    /// Print only the returned felts separated by spaces, without any labels.
+    /// Cannot be used with `--json`
+    #[clap(long, conflicts_with = "decode")]
+    pub raw: bool,
+
+    /// Decode the returned value into JSON based on the contract ABI
+    #[clap(long)]
+    pub decode: bool,
+
+    /// Treat the contract as a proxy and use the ABI of its implementation
+    #[clap(long, conflicts_with = "abi_file")]
+    pub follow_proxy: bool,
+
+    /// Storage slot of the proxy holding the implementation, as an address or a storage variable name;
+    /// overrides detection of common proxy patterns
+    #[clap(long, requires = "follow_proxy", value_parser = parse_impl_slot)]
+    pub impl_slot: Option<FieldElement>,
+
+    /// Path to a JSON file with a list of calls to perform; results are returned in the same order
+    #[clap(
+        long,
+        conflicts_with_all = ["contract_address", "function", "selector_from_file", "calldata", "calldata_stdin", "abi_file", "raw", "follow_proxy"]
+    )]
+    pub batch_file: Option<Utf8PathBuf>,
+
+    /// Maximum number of calls from `--batch-file` sent to the provider at the same time
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+    pub concurrency: u16,
+
+    /// Reuse the result of an identical call made within the given time (e.g. `30s`), stored in a local cache.
+    /// Results at the `latest` and `pending` blocks are reused for at most 10 seconds
+    #[clap(long, value_name = "TTL", value_parser = parse_duration, conflicts_with = "batch_file")]
+    pub cache_reads: Option<Duration>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct BatchCall {
+    contract_address: String,
+    function: String,
+    #[serde(default)]
+    calldata: Vec<String>,
 }
 
-#[allow(clippy::ptr_arg)]
-pub async fn call(
+struct ParsedBatchCall {
     contract_address: FieldElement,
-    entry_point_selector: FieldElement,
+    function: String,
     calldata: Vec<FieldElement>,
-    provider: &JsonRpcClient<HttpTransport>,
-    block_id: &BlockId,
-) -> Result<CallResponse, StarknetCommandError> {
-    let function_call = FunctionCall {
+}
+
+fn read_batch_file(
+    path: &Utf8PathBuf,
+    address_book_path: Option<&Utf8PathBuf>,
+    strict_felt_parsing: bool,
+) -> Result<Vec<ParsedBatchCall>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read batch file = {path}"))?;
+    let calls: Vec<BatchCall> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse batch file = {path}"))?;
+
+    // Loaded once, when the first `@<name>` reference is found
+    let mut address_book = None;
+    calls
+        .into_iter()
+        .enumerate()
+        .map(|(index, call)| {
+            parse_batch_call(
+                call,
+                &mut address_book,
+                address_book_path,
+                strict_felt_parsing,
+            )
+            .with_context(|| format!("Invalid call number {} in batch file", index + 1))
+        })
+        .collect()
+}
+
+fn parse_batch_call(
+    call: BatchCall,
+    address_book: &mut Option<AddressBook>,
+    address_book_path: Option<&Utf8PathBuf>,
+    strict_felt_parsing: bool,
+) -> Result<ParsedBatchCall> {
+    let contract_address = match parse_address(&call.contract_address)? {
+        AddressArg::Value(address) => address,
+        AddressArg::Name(name) => {
+            if address_book.is_none() {
+                *address_book = Some(AddressBook::load(address_book_path)?);
+            }
+            address_book
+                .as_ref()
+                .expect("Address book should be loaded")
+                .get(&name)?
+        }
+    };
+
+    Ok(ParsedBatchCall {
         contract_address,
-        entry_point_selector,
-        calldata,
+        calldata: call
+            .calldata
+            .iter()
+            .map(|value| {
+                if strict_felt_parsing {
+                    parse_felt_strict(value)
+                } else {
+                    FieldElement::from_str(value).map_err(|err| anyhow!(err))
+                }
+            })
+            .collect::<Result<_>>()?,
+        function: call.function,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn call_batch(
+    path: &Utf8PathBuf,
+    provider: &JsonRpcClient<TracedTransport>,
+    block_id: &BlockId,
+    concurrency: u16,
+    decode: bool,
+    abi_cache: Option<&AbiCache>,
+    address_book_path: Option<&Utf8PathBuf>,
+    strict_felt_parsing: bool,
+) -> Result<CallBatchResponse> {
+    let calls = read_batch_file(path, address_book_path, strict_felt_parsing)?;
+
+    // `buffered` keeps the results in the order of the input stream
+    let responses: Vec<Vec<Felt>> = stream::iter(calls.iter().enumerate())
+        .map(|(index, call)| async move {
+            let selector = get_function_selector(&call.function)?;
+            sncast::commands::call::call(
+                call.contract_address,
+                selector,
+                call.calldata.clone(),
+                provider,
+                block_id,
+            )
+            .await
+            .map(|response| response.response)
+            .map_err(handle_starknet_command_error)
+            .with_context(|| format!("Failed to perform call number {}", index + 1))
+        })
+        .buffered(usize::from(concurrency))
+        .try_collect()
+        .await?;
+
+    let decoded = if decode {
+        match decode_responses(&calls, &responses, provider, block_id, abi_cache).await {
+            Ok(decoded) => Some(decoded),
+            Err(err) => {
+                print_as_warning(&anyhow!(
+                    "Failed to decode the call responses, printing raw responses instead: {err:#}"
+                ));
+                None
+            }
+        }
+    } else {
+        None
     };
-    let res = provider
-        .call(function_call, block_id)
-        .await
-        .map(|v| v.into_iter().map(Felt).collect());
-
-    match res {
-        Ok(response) => Ok(CallResponse { response }),
-        Err(error) => Err(StarknetCommandError::ProviderError(error.into())),
+
+    Ok(CallBatchResponse { responses, decoded })
+}
+
+async fn decode_responses(
+    calls: &[ParsedBatchCall],
+    responses: &[Vec<Felt>],
+    provider: &JsonRpcClient<TracedTransport>,
+    block_id: &BlockId,
+    abi_cache: Option<&AbiCache>,
+) -> Result<Vec<Value>> {
+    let mut abis = HashMap::new();
+    for call in calls {
+        if !abis.contains_key(&call.contract_address) {
+            let abi =
+                get_contract_abi(provider, call.contract_address, block_id, abi_cache).await?;
+            abis.insert(call.contract_address, abi);
+        }
     }
+
+    calls
+        .iter()
+        .zip(responses)
+        .enumerate()
+        .map(|(index, (call, response))| {
+            let felts: Vec<FieldElement> = response.iter().map(|felt| felt.0).collect();
+            decode_function_outputs(&abis[&call.contract_address], &call.function, &felts)
+                .with_context(|| format!("Failed to decode call number {}", index + 1))
+        })
+        .collect()
 }
@@ -0,0 +1,49 @@
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use sncast::response::structs::{ClassHashResponse, Felt};
+use starknet::core::types::contract::{CompiledClass, SierraClass};
+
+#[derive(Args)]
+#[command(about = "Compute the class hash of a contract from its local artifacts", long_about = None)]
+pub struct ClassHash {
+    /// Path to the Sierra artifact of the contract (`*.contract_class.json`)
+    #[clap(long)]
+    pub sierra_path: Utf8PathBuf,
+
+    /// Path to the CASM artifact of the contract (`*.compiled_contract_class.json`);
+    /// if passed, the compiled class hash is computed as well
+    #[clap(long)]
+    pub casm_path: Option<Utf8PathBuf>,
+}
+
+pub fn class_hash(class_hash: &ClassHash) -> Result<ClassHashResponse> {
+    let sierra_path = &class_hash.sierra_path;
+    let sierra = std::fs::read_to_string(sierra_path)
+        .with_context(|| format!("Failed to read Sierra artifact = {sierra_path}"))?;
+    let sierra_class: SierraClass = serde_json::from_str(&sierra)
+        .with_context(|| format!("Failed to parse Sierra artifact = {sierra_path}"))?;
+    let flattened_class = sierra_class
+        .flatten()
+        .context("Failed to flatten Sierra class")?;
+
+    let compiled_class_hash = match &class_hash.casm_path {
+        Some(casm_path) => {
+            let casm = std::fs::read_to_string(casm_path)
+                .with_context(|| format!("Failed to read CASM artifact = {casm_path}"))?;
+            let casm_class: CompiledClass = serde_json::from_str(&casm)
+                .with_context(|| format!("Failed to parse CASM artifact = {casm_path}"))?;
+            Some(Felt(
+                casm_class
+                    .class_hash()
+                    .context("Failed to compute compiled class hash")?,
+            ))
+        }
+        None => None,
+    };
+
+    Ok(ClassHashResponse {
+        class_hash: Felt(flattened_class.class_hash()),
+        compiled_class_hash,
+    })
+}
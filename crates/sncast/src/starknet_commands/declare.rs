@@ -1,90 +1,82 @@
-use anyhow::{anyhow, Context, Result};
 use clap::Args;
-use scarb_api::StarknetContractArtifacts;
-use sncast::response::structs::DeclareResponse;
-use sncast::response::structs::Felt;
-use sncast::{apply_optional, handle_wait_for_tx, ErrorData, WaitForTx};
-use starknet::accounts::AccountError::Provider;
-use starknet::accounts::{ConnectedAccount, Declaration};
-
-use sncast::response::errors::StarknetCommandError;
+use semver::Version;
+use sncast::helpers::numbers::parse_number;
 use starknet::core::types::FieldElement;
-use starknet::{
-    accounts::{Account, SingleOwnerAccount},
-    core::types::contract::{CompiledClass, SierraClass},
-    providers::jsonrpc::{HttpTransport, JsonRpcClient},
-    signers::LocalWallet,
-};
-use std::collections::HashMap;
-use std::sync::Arc;
 
 #[derive(Args)]
 #[command(about = "Declare a contract to starknet", long_about = None)]
 pub struct Declare {
-    /// Contract name
-    #[clap(short = 'c', long = "contract-name")]
-    pub contract: String,
+    /// Contract name. Can be passed multiple times to declare several contracts at once
+    #[clap(
+        short = 'c',
+        long = "contract-name",
+        required_unless_present = "all",
+        conflicts_with = "all"
+    )]
+    pub contracts: Vec<String>,
+
+    /// Declare all contracts from the package
+    #[clap(long, conflicts_with = "class_hash")]
+    pub all: bool,
+
+    /// Report sizes of the contract class without declaring it
+    #[clap(long, conflicts_with_all = ["all", "max_fee", "nonce"])]
+    pub size_only: bool,
 
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = parse_number)]
     pub max_fee: Option<FieldElement>,
 
+    /// Abort if `--max-fee` is more than this many times higher than the estimated fee.
+    /// Guards against accidentally overpaying; not checked by default
+    #[clap(long, requires = "max_fee", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_fee_abort_ratio: Option<u64>,
+
     /// Nonce of the transaction. If not provided, nonce will be set automatically
     #[clap(short, long)]
     pub nonce: Option<FieldElement>,
 
+    /// If passed, explicitly provided nonce is not validated against the account nonce
+    #[clap(long, requires = "nonce")]
+    pub force_nonce: bool,
+
     /// Specifies scarb package to be used
     #[clap(long)]
     pub package: Option<String>,
-}
 
-#[allow(clippy::too_many_lines)]
-pub async fn declare(
-    contract_name: &str,
-    max_fee: Option<FieldElement>,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
-    nonce: Option<FieldElement>,
-    artifacts: &HashMap<String, StarknetContractArtifacts>,
-    wait_config: WaitForTx,
-) -> Result<DeclareResponse, StarknetCommandError> {
-    let contract_name: String = contract_name.to_string();
-    let contract_artifacts =
-        artifacts
-            .get(&contract_name)
-            .ok_or(StarknetCommandError::ContractArtifactsNotFound(
-                ErrorData::new(contract_name),
-            ))?;
+    /// Expected class hash of the contract. If provided, declaration is aborted when
+    /// class hash of the built artifact does not match it
+    #[clap(long)]
+    pub class_hash: Option<FieldElement>,
+
+    /// Version of Scarb used to build the contract, selected with asdf.
+    /// The command fails if the version is not installed
+    #[clap(long)]
+    pub scarb_version: Option<Version>,
 
-    let contract_definition: SierraClass = serde_json::from_str(&contract_artifacts.sierra)
-        .context("Failed to parse sierra artifact")?;
-    let casm_contract_definition: CompiledClass =
-        serde_json::from_str(&contract_artifacts.casm).context("Failed to parse casm artifact")?;
+    /// Scarb profile used to build the contract, e.g. `release`.
+    /// Defaults to the sncast profile name if such Scarb profile exists, `dev` otherwise
+    #[clap(long)]
+    pub build_profile: Option<String>,
+
+    /// Name of the package target to build; all targets of the package are built if not provided
+    #[clap(long)]
+    pub target: Option<String>,
 
-    let casm_class_hash = casm_contract_definition
-        .class_hash()
-        .map_err(anyhow::Error::from)?;
+    /// Remove artifacts of previous builds before building the contract
+    #[clap(long, conflicts_with = "no_build")]
+    pub force_rebuild: bool,
 
-    let declaration = account.declare(
-        Arc::new(contract_definition.flatten().map_err(anyhow::Error::from)?),
-        casm_class_hash,
-    );
+    /// Do not build the contract and use existing artifacts; fails if they are missing
+    #[clap(long, conflicts_with = "scarb_version")]
+    pub no_build: bool,
 
-    let declaration = apply_optional(declaration, max_fee, Declaration::max_fee);
-    let declaration = apply_optional(declaration, nonce, Declaration::nonce);
-    let declared = declaration.send().await;
-    match declared {
-        Ok(result) => handle_wait_for_tx(
-            account.provider(),
-            result.transaction_hash,
-            DeclareResponse {
-                class_hash: Felt(result.class_hash),
-                transaction_hash: Felt(result.transaction_hash),
-            },
-            wait_config,
-        )
-        .await
-        .map_err(StarknetCommandError::from),
-        Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
-        _ => Err(anyhow!("Unknown RPC error").into()),
-    }
+    /// Extra argument passed to `scarb build`, e.g. `--build-arg=--features=my_feature`; can be passed multiple times
+    #[clap(
+        long = "build-arg",
+        value_name = "ARG",
+        allow_hyphen_values = true,
+        conflicts_with = "no_build"
+    )]
+    pub build_args: Vec<String>,
 }
@@ -1,18 +1,28 @@
-use anyhow::{anyhow, Result};
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
 use clap::Args;
-use sncast::response::structs::{DeployResponse, Felt};
-use starknet::accounts::AccountError::Provider;
-use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
-use starknet::contract::ContractFactory;
+use sncast::helpers::numbers::parse_number;
+use sncast::{parse_nonce, NonceArg};
 use starknet::core::types::FieldElement;
-use starknet::core::utils::get_udc_deployed_address;
-use starknet::providers::jsonrpc::HttpTransport;
-use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
+use std::str::FromStr;
+use url::Url;
 
-use sncast::response::errors::StarknetCommandError;
-use sncast::{extract_or_generate_salt, udc_uniqueness};
-use sncast::{handle_wait_for_tx, WaitForTx};
+/// Salt passed with `--salt`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Salt {
+    Value(FieldElement),
+    /// The first salt yielding a free deployment address is searched for
+    Auto,
+}
+
+fn parse_salt(value: &str) -> Result<Salt> {
+    if value == "auto" {
+        return Ok(Salt::Auto);
+    }
+    FieldElement::from_str(value)
+        .map(Salt::Value)
+        .with_context(|| format!("Failed to parse salt = {value}, expected a felt or `auto`"))
+}
 
 #[derive(Args)]
 #[command(about = "Deploy a contract on Starknet")]
@@ -23,73 +33,67 @@ pub struct Deploy {
 
     /// Calldata for the contract constructor
     #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
-    pub constructor_calldata: Vec<FieldElement>,
+    pub constructor_calldata: Vec<String>,
 
-    /// Salt for the address
-    #[clap(short, long)]
-    pub salt: Option<FieldElement>,
+    /// Read calldata for the contract constructor from stdin, as felts separated by whitespace
+    #[clap(long, conflicts_with_all = ["constructor_calldata", "constructor_args"])]
+    pub constructor_calldata_stdin: bool,
+
+    /// Arguments for the contract constructor, serialized based on the class ABI.
+    /// Either all positional (`value`) or all named (`name=value`)
+    #[clap(long, num_args = 1.., conflicts_with = "constructor_calldata")]
+    pub constructor_args: Option<Vec<String>>,
+
+    /// Path to a file with the contract ABI used to serialize `--constructor-args`.
+    /// If not provided, ABI is fetched from the network
+    #[clap(long, requires = "constructor_args")]
+    pub abi_file: Option<Utf8PathBuf>,
+
+    /// Salt for the address. With `auto`, salts are checked one by one, starting from `--auto-salt-start`,
+    /// and the first one yielding an address with no contract deployed is used
+    #[clap(short, long, value_parser = parse_salt)]
+    pub salt: Option<Salt>,
+
+    /// Salt from which the search of `--salt auto` starts
+    #[clap(long, default_value = "0x0")]
+    pub auto_salt_start: FieldElement,
 
     /// If true, salt will be modified with an account address
     #[clap(short, long)]
     pub unique: bool,
 
+    /// If passed, it is not checked whether a contract is already deployed at the address
+    /// computed from `--salt` before sending the transaction
+    #[clap(long, requires = "salt")]
+    pub allow_redeploy: bool,
+
+    /// Address of the Universal Deployer Contract used for the deployment; overrides `udc-address` from snfoundry.toml.
+    /// Defaults to the address of the UDC on public Starknet networks
+    #[clap(long)]
+    pub udc_address: Option<FieldElement>,
+
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = parse_number)]
     pub max_fee: Option<FieldElement>,
 
-    /// Nonce of the transaction. If not provided, nonce will be set automatically
-    #[clap(short, long)]
-    pub nonce: Option<FieldElement>,
-}
+    /// Abort if `--max-fee` is more than this many times higher than the estimated fee.
+    /// Guards against accidentally overpaying; not checked by default
+    #[clap(long, requires = "max_fee", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_fee_abort_ratio: Option<u64>,
 
-#[allow(clippy::too_many_arguments)]
-pub async fn deploy(
-    class_hash: FieldElement,
-    constructor_calldata: Vec<FieldElement>,
-    salt: Option<FieldElement>,
-    unique: bool,
-    max_fee: Option<FieldElement>,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
-    nonce: Option<FieldElement>,
-    wait_config: WaitForTx,
-) -> Result<DeployResponse, StarknetCommandError> {
-    let salt = extract_or_generate_salt(salt);
-    let factory = ContractFactory::new(class_hash, account);
-    let execution = factory.deploy(constructor_calldata.clone(), salt, unique);
-
-    // TODO(#1396): use apply_optional here when `Deployment` in starknet-rs is public
-    //  otherwise we cannot pass the necessary reference to a function
-    let execution = if let Some(max_fee) = max_fee {
-        execution.max_fee(max_fee)
-    } else {
-        execution
-    };
-
-    let execution = if let Some(nonce) = nonce {
-        execution.nonce(nonce)
-    } else {
-        execution
-    };
-
-    let result = execution.send().await;
-    match result {
-        Ok(result) => handle_wait_for_tx(
-            account.provider(),
-            result.transaction_hash,
-            DeployResponse {
-                contract_address: Felt(get_udc_deployed_address(
-                    salt,
-                    class_hash,
-                    &udc_uniqueness(unique, account.address()),
-                    &constructor_calldata,
-                )),
-                transaction_hash: Felt(result.transaction_hash),
-            },
-            wait_config,
-        )
-        .await
-        .map_err(StarknetCommandError::from),
-        Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
-        _ => Err(anyhow!("Unknown RPC error").into()),
-    }
+    /// Nonce of the transaction, or `next` to use the pending nonce of the account. If not provided, nonce will be set automatically
+    #[clap(short, long, value_parser = parse_nonce)]
+    pub nonce: Option<NonceArg>,
+
+    /// If passed, explicitly provided nonce is not validated against the account nonce
+    #[clap(long, requires = "nonce")]
+    pub force_nonce: bool,
+
+    /// URL of a paymaster service (SNIP-29) submitting the transaction and paying its fee; requires an account supporting outside execution (SNIP-9, version 2)
+    #[clap(long, conflicts_with_all = ["max_fee", "max_fee_abort_ratio", "nonce"])]
+    pub paymaster_url: Option<Url>,
+
+    /// API key of the paymaster service
+    #[clap(long, requires = "paymaster_url")]
+    pub paymaster_token: Option<String>,
 }
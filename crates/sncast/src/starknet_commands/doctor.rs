@@ -0,0 +1,93 @@
+use anyhow::{anyhow, Error, Result};
+use clap::Args;
+use scarb_api::ScarbCommand;
+use sncast::helpers::configuration::CastConfig;
+use sncast::response::structs::DoctorResponse;
+use sncast::{chain_id_to_network_name, get_chain_id, get_provider};
+
+#[derive(Args)]
+#[command(about = "Check the environment and configuration for common problems", long_about = None)]
+pub struct Doctor {}
+
+pub async fn doctor(config: Result<CastConfig>, profile: Option<String>) -> DoctorResponse {
+    let scarb = match ScarbCommand::version().run() {
+        Ok(version) => passed(&format!(
+            "scarb {} (cairo {})",
+            version.scarb, version.cairo
+        )),
+        Err(err) => failed(
+            &err,
+            "Install Scarb following https://docs.swmansion.com/scarb/download.html",
+        ),
+    };
+
+    let (profile, profile_failed, config) = match config {
+        Ok(config) => (
+            passed(&profile.map_or_else(
+                || "using default profile".to_string(),
+                |profile| format!("using profile = {profile}"),
+            )),
+            false,
+            config,
+        ),
+        Err(err) => (
+            failed(
+                &err,
+                "Make sure the profile passed with `--profile` is defined in snfoundry.toml",
+            ),
+            true,
+            CastConfig::default(),
+        ),
+    };
+
+    let (rpc, rpc_failed) = match check_rpc(&config.url).await {
+        Ok(details) => (passed(&details), false),
+        Err(err) => (
+            failed(
+                &err,
+                "Pass a reachable RPC url with `--url` or set `url` in snfoundry.toml",
+            ),
+            true,
+        ),
+    };
+
+    let accounts = match &config.keystore {
+        Some(keystore) if keystore.exists() => passed(&format!("keystore = {keystore}")),
+        Some(keystore) => failed(
+            &anyhow!("Keystore file = {keystore} does not exist"),
+            "Pass an existing keystore file with `--keystore`",
+        ),
+        None if config.accounts_file.exists() => {
+            passed(&format!("accounts file = {}", config.accounts_file))
+        }
+        None => failed(
+            &anyhow!("Accounts file = {} does not exist", config.accounts_file),
+            "Create an account with `account create` or pass an existing file with `--accounts-file`",
+        ),
+    };
+
+    DoctorResponse {
+        scarb,
+        profile,
+        rpc,
+        accounts,
+        critical_checks_failed: profile_failed || rpc_failed,
+    }
+}
+
+async fn check_rpc(url: &str) -> Result<String> {
+    let provider = get_provider(url)?;
+    let chain_id = get_chain_id(&provider).await?;
+    Ok(format!(
+        "{url} is reachable, chain id = {}",
+        chain_id_to_network_name(chain_id)
+    ))
+}
+
+fn passed(details: &str) -> String {
+    format!("passed: {details}")
+}
+
+fn failed(error: &Error, hint: &str) -> String {
+    format!("failed: {error:#}; hint: {hint}")
+}
@@ -1,82 +1,124 @@
-use anyhow::{anyhow, Result};
+use camino::Utf8PathBuf;
 use clap::Args;
-
-use sncast::response::errors::StarknetCommandError;
-use sncast::response::structs::{Felt, InvokeResponse};
-use sncast::{apply_optional, handle_wait_for_tx, WaitForTx};
-use starknet::accounts::AccountError::Provider;
-use starknet::accounts::{Account, Call, ConnectedAccount, Execution, SingleOwnerAccount};
+use sncast::commands::invoke::ValueCall;
+use sncast::helpers::address_book::{parse_address, AddressArg};
+use sncast::helpers::numbers::parse_number;
+use sncast::helpers::proxy::parse_impl_slot;
+use sncast::{parse_nonce, NonceArg};
 use starknet::core::types::FieldElement;
-use starknet::providers::jsonrpc::HttpTransport;
-use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
+use url::Url;
 
 #[derive(Args)]
 #[command(about = "Invoke a contract on Starknet")]
 pub struct Invoke {
-    /// Address of contract to invoke
-    #[clap(short = 'a', long)]
-    pub contract_address: FieldElement,
+    /// Address of contract to invoke, or `@<name>` of an address book entry
+    #[clap(
+        short = 'a',
+        long,
+        required_unless_present = "from_deploy",
+        value_parser = parse_address
+    )]
+    pub contract_address: Option<AddressArg>,
+
+    /// Hash of a deploy transaction; address of the contract deployed in it will be invoked
+    #[clap(long, conflicts_with = "contract_address")]
+    pub from_deploy: Option<FieldElement>,
+
+    /// Name of the function to invoke, its selector as a `0x` prefixed hex, or `@<path>` of a file holding the selector
+    #[clap(
+        short,
+        long,
+        required_unless_present_any = ["replace_class", "selector_from_file"]
+    )]
+    pub function: Option<String>,
+
+    /// Path to a file holding the selector of the invoked function as a single felt
+    #[clap(long, conflicts_with = "function")]
+    pub selector_from_file: Option<Utf8PathBuf>,
+
+    /// Upgrade the contract to the class with given hash, by invoking `--upgrade-function` with it as the only argument
+    #[clap(long, conflicts_with_all = ["function", "selector_from_file", "calldata", "calldata_stdin"])]
+    pub replace_class: Option<FieldElement>,
 
-    /// Name of the function to invoke
-    #[clap(short, long)]
-    pub function: String,
+    /// Name of the function invoked by `--replace-class`, or its selector as a `0x` prefixed hex
+    #[clap(long, default_value = "upgrade", requires = "replace_class")]
+    pub upgrade_function: String,
 
     /// Calldata for the invoked function
     #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
-    pub calldata: Vec<FieldElement>,
+    pub calldata: Vec<String>,
+
+    /// Read calldata for the invoked function from stdin, as felts separated by whitespace
+    #[clap(long, conflicts_with = "calldata")]
+    pub calldata_stdin: bool,
+
+    /// Amount of `--token` moved to the invoked contract in the same transaction, e.g. `100`, `0x64` or `0.5eth`
+    #[clap(long, requires = "token", value_parser = parse_number)]
+    pub value: Option<FieldElement>,
+
+    /// Address of the ERC-20 token in which `--value` is paid
+    #[clap(long, requires = "value")]
+    pub token: Option<FieldElement>,
+
+    /// ERC-20 call prepended to the invoke to move `--value`
+    #[clap(long, value_enum, default_value_t = ValueCall::Approve, requires = "value")]
+    pub value_call: ValueCall,
 
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = parse_number)]
     pub max_fee: Option<FieldElement>,
 
-    /// Nonce of the transaction. If not provided, nonce will be set automatically
-    #[clap(short, long)]
-    pub nonce: Option<FieldElement>,
-}
+    /// Abort if `--max-fee` is more than this many times higher than the estimated fee.
+    /// Guards against accidentally overpaying; not checked by default
+    #[clap(long, requires = "max_fee", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_fee_abort_ratio: Option<u64>,
+
+    /// Nonce of the transaction, or `next` to use the pending nonce of the account. If not provided, nonce will be set automatically
+    #[clap(short, long, value_parser = parse_nonce)]
+    pub nonce: Option<NonceArg>,
+
+    /// If passed, explicitly provided nonce is not validated against the account nonce
+    #[clap(long, requires = "nonce")]
+    pub force_nonce: bool,
+
+    /// Path to a file with the contract ABI; the invoked function is looked up in it and `--calldata`
+    /// is encoded according to its inputs, the same way as `--constructor-args` in deploy
+    #[clap(long, conflicts_with_all = ["calldata_stdin", "replace_class"])]
+    pub abi_file: Option<Utf8PathBuf>,
+
+    /// Treat the contract as a proxy and check the invoked function against the ABI of its implementation
+    #[clap(long, conflicts_with = "abi_file")]
+    pub follow_proxy: bool,
+
+    /// Storage slot of the proxy holding the implementation, as an address or a storage variable name;
+    /// overrides detection of common proxy patterns
+    #[clap(long, requires = "follow_proxy", value_parser = parse_impl_slot)]
+    pub impl_slot: Option<FieldElement>,
+
+    /// If passed, it is not checked whether the account is deployed before sending the transaction
+    #[clap(long)]
+    pub skip_deploy_check: bool,
+
+    /// URL of a paymaster service (SNIP-29) submitting the transaction and paying its fee; requires an account supporting outside execution (SNIP-9, version 2)
+    #[clap(long, conflicts_with_all = ["max_fee", "max_fee_abort_ratio", "nonce"])]
+    pub paymaster_url: Option<Url>,
 
-pub async fn invoke(
-    contract_address: FieldElement,
-    function_selector: FieldElement,
-    calldata: Vec<FieldElement>,
-    max_fee: Option<FieldElement>,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
-    nonce: Option<FieldElement>,
-    wait_config: WaitForTx,
-) -> Result<InvokeResponse, StarknetCommandError> {
-    let call = Call {
-        to: contract_address,
-        selector: function_selector,
-        calldata,
-    };
-
-    execute_calls(account, vec![call], max_fee, nonce, wait_config).await
+    /// API key of the paymaster service
+    #[clap(long, requires = "paymaster_url")]
+    pub paymaster_token: Option<String>,
 }
 
-pub async fn execute_calls(
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
-    calls: Vec<Call>,
-    max_fee: Option<FieldElement>,
-    nonce: Option<FieldElement>,
-    wait_config: WaitForTx,
-) -> Result<InvokeResponse, StarknetCommandError> {
-    let execution_calls = account.execute(calls);
-
-    let execution = apply_optional(execution_calls, max_fee, Execution::max_fee);
-    let execution = apply_optional(execution, nonce, Execution::nonce);
-
-    match execution.send().await {
-        Ok(result) => handle_wait_for_tx(
-            account.provider(),
-            result.transaction_hash,
-            InvokeResponse {
-                transaction_hash: Felt(result.transaction_hash),
-            },
-            wait_config,
-        )
-        .await
-        .map_err(StarknetCommandError::from),
-        Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
-        _ => Err(anyhow!("Unknown RPC error").into()),
+impl Invoke {
+    /// Name or selector of the invoked function, including the one invoked by `--replace-class`.
+    /// A selector passed with `--selector-from-file` is returned as an `@<path>` reference
+    pub fn function_name(&self) -> String {
+        match (&self.function, &self.selector_from_file, self.replace_class) {
+            (_, _, Some(_)) => self.upgrade_function.clone(),
+            (_, Some(path), None) => format!("@{path}"),
+            (Some(function), None, None) => function.clone(),
+            (None, None, None) => unreachable!(
+                "Either `--function`, `--selector-from-file` or `--replace-class` should be validated by clap"
+            ),
+        }
     }
 }
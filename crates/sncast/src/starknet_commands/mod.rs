@@ -1,9 +1,13 @@
+pub mod abi;
 pub mod account;
 pub mod call;
+pub mod class_hash;
 pub mod declare;
 pub mod deploy;
+pub mod doctor;
 pub mod invoke;
 pub mod multicall;
+pub mod schema;
 pub mod script;
 pub mod show_config;
 pub mod tx_status;
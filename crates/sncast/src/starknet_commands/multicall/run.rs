@@ -1,30 +1,52 @@
-use crate::starknet_commands::invoke::execute_calls;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::Args;
 use serde::Deserialize;
-use sncast::helpers::constants::UDC_ADDRESS;
+use sncast::commands::invoke::execute_calls;
+use sncast::helpers::numbers::{deserialize_optional_number, parse_felt_strict, parse_number};
+use sncast::helpers::signer::CastSigner;
+use sncast::helpers::traced_transport::TracedTransport;
 use sncast::response::errors::handle_starknet_command_error;
-use sncast::response::structs::InvokeResponse;
-use sncast::{extract_or_generate_salt, udc_uniqueness, WaitForTx};
-use starknet::accounts::{Account, Call, SingleOwnerAccount};
+use sncast::response::structs::{
+    Decimal, Felt, MulticallCallExecutedResponse, MulticallDeployedContract,
+    MulticallInvokeResponse, MulticallRunResponse,
+};
+use sncast::{
+    check_calldata_len, check_calls_calldata_len, extract_or_generate_salt, get_function_selector,
+    udc_uniqueness, WaitForTx,
+};
+use starknet::accounts::{Account, Call, ConnectedAccount, SingleOwnerAccount};
 use starknet::core::types::FieldElement;
 use starknet::core::utils::{get_selector_from_name, get_udc_deployed_address};
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
 use std::collections::HashMap;
+use url::Url;
+
+const ALLOWED_CONTENT_TYPES: [&str; 4] = [
+    "text/",
+    "application/toml",
+    "application/x-toml",
+    "application/octet-stream",
+];
 
 #[derive(Args, Debug)]
 #[command(about = "Execute a multicall from a .toml file", long_about = None)]
 pub struct Run {
-    /// Path to the toml file with declared operations
+    /// Path to the toml file with declared operations; can also be an http(s) URL the file will be downloaded from
     #[clap(short = 'p', long = "path")]
     pub path: Utf8PathBuf,
 
     /// Max fee for the transaction. If not provided, max fee will be automatically estimated
-    #[clap(short, long)]
+    #[clap(short, long, value_parser = parse_number)]
     pub max_fee: Option<FieldElement>,
+
+    /// If passed, each call will be sent as a separate transaction
+    #[clap(long)]
+    pub non_atomic: bool,
+
+    /// If passed, result of each call is printed as soon as it is executed
+    #[clap(long, requires = "non_atomic")]
+    pub stream: bool,
 }
 
 #[allow(dead_code)]
@@ -33,9 +55,16 @@ struct DeployCall {
     call_type: String,
     class_hash: FieldElement,
     inputs: Vec<String>,
+    #[serde(default)]
     unique: bool,
     salt: Option<FieldElement>,
     id: String,
+    #[serde(
+        default,
+        alias = "max-fee",
+        deserialize_with = "deserialize_optional_number"
+    )]
+    max_fee: Option<FieldElement>,
 }
 
 #[allow(dead_code)]
@@ -45,20 +74,161 @@ struct InvokeCall {
     contract_address: String,
     function: String,
     inputs: Vec<String>,
+    #[serde(
+        default,
+        alias = "max-fee",
+        deserialize_with = "deserialize_optional_number"
+    )]
+    max_fee: Option<FieldElement>,
 }
 
 pub async fn run(
     path: &Utf8PathBuf,
-    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    udc_address: FieldElement,
+    max_fee: Option<FieldElement>,
+    max_calldata_len: usize,
+    strict_felt_parsing: bool,
+    wait_config: WaitForTx,
+) -> Result<MulticallInvokeResponse> {
+    let contents = read_calls_file(path).await?;
+    let (parsed_calls, deployed_contracts) = parse_calls(
+        path,
+        &contents,
+        account.address(),
+        udc_address,
+        strict_felt_parsing,
+    )?;
+    if parsed_calls
+        .iter()
+        .any(|(_, call_max_fee)| call_max_fee.is_some())
+    {
+        bail!("Per-call `max_fee` can only be used together with `--non-atomic` flag");
+    }
+    let calls: Vec<Call> = parsed_calls.into_iter().map(|(call, _)| call).collect();
+    check_calls_calldata_len(&calls, max_calldata_len)?;
+
+    let result = execute_calls(account, calls, max_fee, None, None, wait_config)
+        .await
+        .map_err(handle_starknet_command_error)?;
+
+    Ok(MulticallInvokeResponse {
+        transaction_hash: result.transaction_hash,
+        deployed_contracts: format_deployed_contracts(deployed_contracts),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run_non_atomic(
+    path: &Utf8PathBuf,
+    account: &SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>,
+    udc_address: FieldElement,
     max_fee: Option<FieldElement>,
+    max_calldata_len: usize,
+    strict_felt_parsing: bool,
     wait_config: WaitForTx,
-) -> Result<InvokeResponse> {
-    let contents = std::fs::read_to_string(path)?;
+    mut on_call_executed: impl FnMut(MulticallCallExecutedResponse),
+) -> Result<MulticallRunResponse> {
+    let contents = read_calls_file(path).await?;
+    let (parsed_calls, deployed_contracts) = parse_calls(
+        path,
+        &contents,
+        account.address(),
+        udc_address,
+        strict_felt_parsing,
+    )?;
+    let calls_count = parsed_calls.len() as u64;
+    for (index, (call, _)) in parsed_calls.iter().enumerate() {
+        check_calldata_len(&call.calldata, max_calldata_len)
+            .with_context(|| format!("Call number {} cannot be sent", index + 1))?;
+    }
+
+    let mut nonce = account
+        .get_nonce()
+        .await
+        .context("Failed to get account nonce")?;
+    let mut transaction_hashes = vec![];
+    for (index, (call, call_max_fee)) in parsed_calls.into_iter().enumerate() {
+        let result = execute_calls(
+            account,
+            vec![call],
+            call_max_fee.or(max_fee),
+            None,
+            Some(nonce),
+            wait_config,
+        )
+        .await
+        .map_err(handle_starknet_command_error)
+        .with_context(|| format!("Failed to execute call number {}", index + 1))?;
+
+        on_call_executed(MulticallCallExecutedResponse {
+            call_number: Decimal(index as u64 + 1),
+            calls_count: Decimal(calls_count),
+            transaction_hash: result.transaction_hash.clone(),
+        });
+        transaction_hashes.push(result.transaction_hash);
+        nonce += FieldElement::ONE;
+    }
+
+    Ok(MulticallRunResponse {
+        transaction_hashes,
+        deployed_contracts: format_deployed_contracts(deployed_contracts),
+    })
+}
+
+/// Returns the contracts deployed by the deploy calls, if there were any
+fn format_deployed_contracts(
+    deployed_contracts: Vec<MulticallDeployedContract>,
+) -> Option<Vec<MulticallDeployedContract>> {
+    (!deployed_contracts.is_empty()).then_some(deployed_contracts)
+}
+
+/// Calls with their optional max fees, and contracts deployed by the deploy calls
+type ParsedCalls = (
+    Vec<(Call, Option<FieldElement>)>,
+    Vec<MulticallDeployedContract>,
+);
+
+async fn read_calls_file(path: &Utf8PathBuf) -> Result<String> {
+    let url = match Url::parse(path.as_str()) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+        _ => return Ok(std::fs::read_to_string(path)?),
+    };
+
+    let response = reqwest::get(url.clone())
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .with_context(|| format!("Failed to download multicall file from {url}"))?;
+
+    if let Some(content_type) = response.headers().get(reqwest::header::CONTENT_TYPE) {
+        let content_type = content_type.to_str().unwrap_or_default();
+        if !ALLOWED_CONTENT_TYPES
+            .iter()
+            .any(|allowed| content_type.starts_with(allowed))
+        {
+            bail!("Multicall file downloaded from {url} has unsupported content type = {content_type}");
+        }
+    }
+
+    response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read multicall file downloaded from {url}"))
+}
+
+fn parse_calls(
+    path: &Utf8PathBuf,
+    contents: &str,
+    account_address: FieldElement,
+    udc_address: FieldElement,
+    strict_felt_parsing: bool,
+) -> Result<ParsedCalls> {
     let items_map: HashMap<String, Vec<toml::Value>> =
-        toml::from_str(&contents).with_context(|| format!("Failed to parse {path}"))?;
+        toml::from_str(contents).with_context(|| format!("Failed to parse {path}"))?;
 
     let mut contracts = HashMap::new();
-    let mut parsed_calls: Vec<Call> = vec![];
+    let mut parsed_calls: Vec<(Call, Option<FieldElement>)> = vec![];
+    let mut deployed_contracts = vec![];
 
     for call in items_map.get("call").unwrap_or(&vec![]) {
         let call_type = call.get("call_type");
@@ -79,22 +249,30 @@ pub async fn run(
                     deploy_call.inputs.len().into(),
                 ];
 
-                let parsed_inputs = parse_inputs(&deploy_call.inputs, &contracts)?;
+                let parsed_inputs =
+                    parse_inputs(&deploy_call.inputs, &contracts, strict_felt_parsing)?;
                 calldata.extend(&parsed_inputs);
 
-                parsed_calls.push(Call {
-                    to: UDC_ADDRESS,
-                    selector: get_selector_from_name("deployContract")?,
-                    calldata,
-                });
+                parsed_calls.push((
+                    Call {
+                        to: udc_address,
+                        selector: get_selector_from_name("deployContract")?,
+                        calldata,
+                    },
+                    deploy_call.max_fee,
+                ));
 
                 let contract_address = get_udc_deployed_address(
                     salt,
                     deploy_call.class_hash,
-                    &udc_uniqueness(deploy_call.unique, account.address()),
+                    &udc_uniqueness(deploy_call.unique, account_address, udc_address),
                     &parsed_inputs,
                 );
-                contracts.insert(deploy_call.id, contract_address.to_string());
+                contracts.insert(deploy_call.id.clone(), contract_address.to_string());
+                deployed_contracts.push(MulticallDeployedContract {
+                    id: deploy_call.id,
+                    contract_address: Felt(contract_address),
+                });
             }
             Some("invoke") => {
                 let invoke_call: InvokeCall = toml::from_str(toml::to_string(&call)?.as_str())
@@ -104,15 +282,18 @@ pub async fn run(
                     contract_address = addr;
                 }
 
-                let calldata = parse_inputs(&invoke_call.inputs, &contracts)?;
+                let calldata = parse_inputs(&invoke_call.inputs, &contracts, strict_felt_parsing)?;
 
-                parsed_calls.push(Call {
-                    to: contract_address
-                        .parse()
-                        .context("Failed to parse contract address to FieldElement")?,
-                    selector: get_selector_from_name(&invoke_call.function)?,
-                    calldata,
-                });
+                parsed_calls.push((
+                    Call {
+                        to: contract_address
+                            .parse()
+                            .context("Failed to parse contract address to FieldElement")?,
+                        selector: get_function_selector(&invoke_call.function)?,
+                        calldata,
+                    },
+                    invoke_call.max_fee,
+                ));
             }
             Some(unsupported) => {
                 anyhow::bail!("Unsupported call type found = {}", unsupported);
@@ -121,24 +302,91 @@ pub async fn run(
         }
     }
 
-    execute_calls(account, parsed_calls, max_fee, None, wait_config)
-        .await
-        .map_err(handle_starknet_command_error)
+    Ok((parsed_calls, deployed_contracts))
 }
 
 fn parse_inputs(
     inputs: &Vec<String>,
     contracts: &HashMap<String, String>,
+    strict_felt_parsing: bool,
 ) -> Result<Vec<FieldElement>> {
     let mut parsed_inputs = Vec::new();
     for input in inputs {
         let current_input = contracts.get(input).unwrap_or(input);
-        parsed_inputs.push(
-            current_input
-                .parse()
-                .context("Failed to parse input to FieldElement")?,
-        );
+        let parsed_input = if strict_felt_parsing {
+            parse_felt_strict(current_input)
+        } else {
+            current_input.parse().map_err(|err| anyhow!(err))
+        };
+        parsed_inputs.push(parsed_input.context("Failed to parse input to FieldElement")?);
     }
 
     Ok(parsed_inputs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_calls;
+    use camino::Utf8PathBuf;
+    use indoc::indoc;
+    use sncast::udc_uniqueness;
+    use starknet::core::types::FieldElement;
+    use starknet::core::utils::get_udc_deployed_address;
+
+    #[test]
+    fn test_deploy_calls_use_udc_address() {
+        let contents = indoc! {r#"
+            [[call]]
+            call_type = "deploy"
+            class_hash = "0x123"
+            inputs = ["0x1"]
+            id = "contract"
+            salt = "0x2"
+            unique = true
+        "#};
+        let account_address = FieldElement::from(0x456_u32);
+        let udc_address = FieldElement::from(0x789_u32);
+
+        let (calls, deployed_contracts) = parse_calls(
+            &Utf8PathBuf::from("multicall.toml"),
+            contents,
+            account_address,
+            udc_address,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(calls[0].0.to, udc_address);
+        let expected_address = get_udc_deployed_address(
+            FieldElement::TWO,
+            FieldElement::from(0x123_u32),
+            &udc_uniqueness(true, account_address, udc_address),
+            &[FieldElement::ONE],
+        );
+        assert_eq!(deployed_contracts[0].contract_address.0, expected_address);
+    }
+
+    #[test]
+    fn test_strict_felt_parsing_rejects_ambiguous_inputs() {
+        let contents = indoc! {r#"
+            [[call]]
+            call_type = "invoke"
+            contract_address = "0x123"
+            function = "put"
+            inputs = ["0x1", "ff"]
+        "#};
+
+        let err = parse_calls(
+            &Utf8PathBuf::from("multicall.toml"),
+            contents,
+            FieldElement::ONE,
+            FieldElement::TWO,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{err:#}"),
+            "Failed to parse input to FieldElement: Felt = ff is ambiguous, hex values have to be prefixed with `0x` and decimal values may only contain digits"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use clap::Args;
+use serde_json::Value;
+use sncast::response::schema::command_output_schema;
+
+#[derive(Args)]
+#[command(about = "Print JSON Schema of the `--json` output of a command", long_about = None)]
+pub struct Schema {
+    /// Name of the command, e.g. `call` or `account create`
+    #[clap(required = true, num_args = 1..)]
+    pub command: Vec<String>,
+}
+
+pub fn schema(schema: &Schema) -> Result<Value> {
+    command_output_schema(&schema.command.join(" "))
+}
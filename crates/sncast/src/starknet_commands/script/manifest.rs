@@ -0,0 +1,67 @@
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use serde::Serialize;
+use sncast::response::structs::Felt;
+use starknet::core::types::FieldElement;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Debug)]
+pub struct DeclaredContract {
+    pub contract_name: String,
+    pub class_hash: Felt,
+    pub transaction_hash: Felt,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DeployedContract {
+    /// Known only if the class was declared by the script or belongs to a contract of the package
+    pub contract_name: Option<String>,
+    pub class_hash: Felt,
+    pub contract_address: Felt,
+    pub transaction_hash: Felt,
+}
+
+/// Contracts declared and deployed by a script, including those recorded in the state file by previous runs
+#[derive(Serialize, Debug, Default)]
+pub struct DeploymentManifest {
+    pub declarations: Vec<DeclaredContract>,
+    pub deployments: Vec<DeployedContract>,
+}
+
+impl DeploymentManifest {
+    /// Name of the contract of the class, if the script declared it
+    pub fn declared_contract_name(&self, class_hash: FieldElement) -> Option<String> {
+        self.declarations
+            .iter()
+            .find(|declaration| declaration.class_hash == Felt(class_hash))
+            .map(|declaration| declaration.contract_name.clone())
+    }
+}
+
+#[derive(Serialize)]
+struct ManifestFile<'a> {
+    network: &'a str,
+    /// Unix time the manifest was written at
+    timestamp: u64,
+    #[serde(flatten)]
+    manifest: &'a DeploymentManifest,
+}
+
+pub fn write_manifest(
+    path: &Utf8PathBuf,
+    network: &str,
+    manifest: &DeploymentManifest,
+) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System time is before the Unix epoch")?
+        .as_secs();
+    let manifest = serde_json::to_string_pretty(&ManifestFile {
+        network,
+        timestamp,
+        manifest,
+    })?;
+    fs::write(path, manifest)
+        .with_context(|| format!("Failed to write deployment manifest to file = {path}"))
+}
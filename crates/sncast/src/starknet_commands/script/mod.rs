@@ -3,6 +3,8 @@ use crate::starknet_commands::script::run::Run;
 use clap::{Args, Subcommand};
 
 pub mod init;
+pub mod manifest;
+pub mod plan;
 pub mod run;
 
 #[derive(Args)]
@@ -0,0 +1,38 @@
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use serde::Serialize;
+use sncast::response::structs::Felt;
+use std::fs;
+
+/// Transaction a script would send, recorded instead of being executed when planning
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlannedStep {
+    Declare {
+        contract_name: String,
+        class_hash: Felt,
+    },
+    Deploy {
+        class_hash: Felt,
+        constructor_calldata: Vec<Felt>,
+        salt: Option<Felt>,
+        unique: bool,
+        /// Known only if the salt is provided, otherwise a random one is generated when deploying
+        contract_address: Option<Felt>,
+    },
+    Invoke {
+        contract_address: Felt,
+        function_selector: Felt,
+        calldata: Vec<Felt>,
+    },
+}
+
+#[derive(Serialize)]
+struct Plan<'a> {
+    steps: &'a [PlannedStep],
+}
+
+pub fn write_plan(path: &Utf8PathBuf, steps: &[PlannedStep]) -> Result<()> {
+    let plan = serde_json::to_string_pretty(&Plan { steps })?;
+    fs::write(path, plan).with_context(|| format!("Failed to write plan to file = {path}"))
+}
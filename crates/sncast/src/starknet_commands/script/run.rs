@@ -1,7 +1,11 @@
-use std::collections::HashMap;
-use std::fs;
+use std::collections::{HashMap, HashSet};
+use std::{env, fs};
 
-use crate::starknet_commands::{call, declare, deploy, invoke, tx_status};
+use crate::starknet_commands::script::manifest::{
+    write_manifest, DeclaredContract, DeployedContract, DeploymentManifest,
+};
+use crate::starknet_commands::script::plan::{write_plan, PlannedStep};
+use crate::starknet_commands::tx_status;
 use crate::{get_account, get_nonce, WaitForTx};
 use anyhow::{anyhow, Context, Result};
 use blockifier::execution::deprecated_syscalls::DeprecatedSyscallSelector;
@@ -36,18 +40,25 @@ use scarb_metadata::{Metadata, PackageMetadata};
 use semver::{Comparator, Op, Version, VersionReq};
 use shared::print::print_as_warning;
 use shared::utils::build_readable_text;
+use sncast::commands::{call, declare, deploy, invoke};
 use sncast::helpers::configuration::CastConfig;
-use sncast::helpers::constants::SCRIPT_LIB_ARTIFACT_NAME;
-use sncast::response::structs::ScriptRunResponse;
+use sncast::helpers::constants::{SCRIPT_LIB_ARTIFACT_NAME, UDC_ADDRESS};
+use sncast::helpers::scarb_utils::{get_allowed_env_vars_from_package, get_contracts_from_package};
+use sncast::helpers::signer::CastSigner;
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::response::errors::StarknetCommandError;
+use sncast::response::structs::{
+    DeclareResponse, DeployResponse, Felt, InvokeResponse, ScriptRunResponse,
+};
 use sncast::state::hashing::{
     generate_declare_tx_id, generate_deploy_tx_id, generate_invoke_tx_id,
 };
-use sncast::state::state_file::StateManager;
+use sncast::state::state_file::{ScriptTransactionOutput, StateManager};
+use sncast::{get_actual_fee, udc_uniqueness};
 use starknet::accounts::{Account, SingleOwnerAccount};
-use starknet::core::types::{BlockId, BlockTag::Pending};
-use starknet::providers::jsonrpc::HttpTransport;
+use starknet::core::types::{BlockId, BlockTag::Pending, FieldElement};
+use starknet::core::utils::get_udc_deployed_address;
 use starknet::providers::JsonRpcClient;
-use starknet::signers::LocalWallet;
 use tokio::runtime::Runtime;
 
 type ScriptStarknetContractArtifacts = StarknetContractArtifacts;
@@ -65,23 +76,136 @@ pub struct Run {
     /// Do not use the state file
     #[clap(long)]
     pub no_state_file: bool,
+
+    /// Do not send any transactions; instead, write the declarations, deployments and invocations
+    /// the script would perform to the file at given path as JSON
+    #[clap(long, value_name = "PATH")]
+    pub plan: Option<Utf8PathBuf>,
+
+    /// Write the contracts declared and deployed by the script, with their class hashes, addresses
+    /// and transaction hashes, to the file at given path as JSON
+    #[clap(long, value_name = "PATH", conflicts_with = "plan")]
+    pub manifest_out: Option<Utf8PathBuf>,
+    /// Include the fees paid for the transactions sent by the script, per operation and in total, in the output
+    #[clap(long, conflicts_with = "plan")]
+    pub fee_report: bool,
 }
 
 pub struct CastScriptExtension<'a> {
-    pub provider: &'a JsonRpcClient<HttpTransport>,
-    pub account: Option<&'a SingleOwnerAccount<&'a JsonRpcClient<HttpTransport>, LocalWallet>>,
+    pub provider: &'a JsonRpcClient<TracedTransport>,
+    pub account: Option<&'a SingleOwnerAccount<&'a JsonRpcClient<TracedTransport>, CastSigner>>,
     pub tokio_runtime: Runtime,
     pub config: &'a CastConfig,
     pub artifacts: &'a HashMap<String, StarknetContractArtifacts>,
     pub state: StateManager,
+    pub contracts: HashMap<String, FieldElement>,
+    /// Environment variables the script can read, from `[tool.sncast]` section of the package manifest
+    pub allowed_env_vars: HashSet<String>,
+    /// Transactions recorded instead of being sent, present only when planning
+    pub plan: Option<Vec<PlannedStep>>,
+    /// Contracts declared and deployed by the script, present only when writing a manifest
+    pub manifest: Option<DeploymentManifest>,
+    /// Fees of the transactions sent by the script, present only when reporting fees
+    pub fees: Option<FeeReport>,
+}
+
+/// Fees paid for the transactions sent by a script, summed per operation
+#[derive(Default)]
+pub struct FeeReport {
+    declare: FieldElement,
+    deploy: FieldElement,
+    invoke: FieldElement,
+}
+
+impl FeeReport {
+    fn total(&self) -> FieldElement {
+        self.declare + self.deploy + self.invoke
+    }
 }
 
 impl<'a> CastScriptExtension<'a> {
     pub fn account(
         &self,
-    ) -> Result<&SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>> {
+    ) -> Result<&SingleOwnerAccount<&JsonRpcClient<TracedTransport>, CastSigner>> {
         self.account.ok_or_else(|| anyhow!("Account not defined. Please ensure the correct account is passed to `script run` command"))
     }
+
+    fn udc_address(&self) -> FieldElement {
+        self.config.udc_address.unwrap_or(UDC_ADDRESS)
+    }
+
+    fn record_step(&mut self, step: PlannedStep) {
+        self.plan
+            .as_mut()
+            .expect("Steps should be recorded only when planning")
+            .push(step);
+    }
+
+    fn record_declaration(&mut self, contract_name: &str, response: &DeclareResponse) {
+        if let Some(manifest) = &mut self.manifest {
+            manifest.declarations.push(DeclaredContract {
+                contract_name: contract_name.to_string(),
+                class_hash: response.class_hash.clone(),
+                transaction_hash: response.transaction_hash.clone(),
+            });
+        }
+    }
+
+    fn record_deployment(&mut self, class_hash: FieldElement, response: &DeployResponse) {
+        let Some(manifest) = &self.manifest else {
+            return;
+        };
+        let contract_name = manifest
+            .declared_contract_name(class_hash)
+            .or_else(|| self.package_contract_name(class_hash));
+        if let Some(manifest) = &mut self.manifest {
+            manifest.deployments.push(DeployedContract {
+                contract_name,
+                class_hash: Felt(class_hash),
+                contract_address: response.contract_address.clone(),
+                transaction_hash: response.transaction_hash.clone(),
+            });
+        }
+    }
+
+    /// Adds the fee of the sent transaction to the report. Failing to fetch it does not stop the script
+    fn record_fee(&mut self, selector: &str, transaction_hash: FieldElement) {
+        if self.fees.is_none() {
+            return;
+        }
+        let fee = match self
+            .tokio_runtime
+            .block_on(get_actual_fee(self.provider, transaction_hash))
+        {
+            Ok(fee) => fee,
+            Err(error) => {
+                print_as_warning(&anyhow!(
+                    "Failed to get fee of transaction = {transaction_hash:#x}, it is not included in the fee report: {error}"
+                ));
+                return;
+            }
+        };
+        let fees = self
+            .fees
+            .as_mut()
+            .expect("Fees should be recorded only when reporting them");
+        match selector {
+            "declare" => fees.declare += fee,
+            "deploy" => fees.deploy += fee,
+            "invoke" => fees.invoke += fee,
+            _ => unreachable!("Only transactions have fees"),
+        }
+    }
+
+    /// Name of the contract of the package with the class, for classes declared outside the script
+    fn package_contract_name(&self, class_hash: FieldElement) -> Option<String> {
+        declare::contract_names(self.artifacts)
+            .into_iter()
+            .filter(|name| name.as_str() != SCRIPT_LIB_ARTIFACT_NAME)
+            .find(|name| {
+                declare::get_class_hash(name, self.artifacts).is_ok_and(|hash| hash == class_hash)
+            })
+    }
 }
 
 impl<'a> ExtensionLogic for CastScriptExtension<'a> {
@@ -119,15 +243,35 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                 if let Some(success_output) =
                     self.state.get_output_if_success(declare_tx_id.as_str())
                 {
+                    if let ScriptTransactionOutput::DeclareResponse(response) = &success_output {
+                        self.record_declaration(&contract_name, response);
+                    }
                     return Ok(CheatcodeHandlingResult::from_serializable(success_output));
                 }
 
+                if self.plan.is_some() {
+                    let declare_result = declare::get_class_hash(&contract_name, self.artifacts)
+                        .map(|class_hash| DeclareResponse {
+                            class_hash: Felt(class_hash),
+                            transaction_hash: Felt(FieldElement::ZERO),
+                        });
+                    if let Ok(response) = &declare_result {
+                        self.record_step(PlannedStep::Declare {
+                            contract_name,
+                            class_hash: response.class_hash.clone(),
+                        });
+                    }
+                    return Ok(CheatcodeHandlingResult::from_serializable(declare_result));
+                }
+
                 let declare_result = self.tokio_runtime.block_on(declare::declare(
                     &contract_name,
                     max_fee,
+                    None,
                     self.account()?,
                     nonce,
                     self.artifacts,
+                    None,
                     WaitForTx {
                         wait: true,
                         wait_params: self.config.wait_params,
@@ -139,6 +283,10 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     selector,
                     &declare_result,
                 )?;
+                if let Ok(response) = &declare_result {
+                    self.record_declaration(&contract_name, response);
+                    self.record_fee(selector, response.transaction_hash.0);
+                }
                 Ok(CheatcodeHandlingResult::from_serializable(declare_result))
             }
             "deploy" => {
@@ -156,15 +304,48 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                 if let Some(success_output) =
                     self.state.get_output_if_success(deploy_tx_id.as_str())
                 {
+                    if let ScriptTransactionOutput::DeployResponse(response) = &success_output {
+                        self.record_deployment(class_hash, response);
+                    }
                     return Ok(CheatcodeHandlingResult::from_serializable(success_output));
                 }
 
+                if self.plan.is_some() {
+                    let deployer_address = if unique {
+                        self.account()?.address()
+                    } else {
+                        FieldElement::ZERO
+                    };
+                    let contract_address = salt.map(|salt| {
+                        get_udc_deployed_address(
+                            salt,
+                            class_hash,
+                            &udc_uniqueness(unique, deployer_address, self.udc_address()),
+                            &constructor_calldata,
+                        )
+                    });
+                    self.record_step(PlannedStep::Deploy {
+                        class_hash: Felt(class_hash),
+                        constructor_calldata: constructor_calldata.into_iter().map(Felt).collect(),
+                        salt: salt.map(Felt),
+                        unique,
+                        contract_address: contract_address.map(Felt),
+                    });
+                    let deploy_result: Result<_, StarknetCommandError> = Ok(DeployResponse {
+                        contract_address: Felt(contract_address.unwrap_or(FieldElement::ZERO)),
+                        transaction_hash: Felt(FieldElement::ZERO),
+                    });
+                    return Ok(CheatcodeHandlingResult::from_serializable(deploy_result));
+                }
+
                 let deploy_result = self.tokio_runtime.block_on(deploy::deploy(
                     class_hash,
                     constructor_calldata,
                     salt,
                     unique,
+                    self.udc_address(),
                     max_fee,
+                    None,
                     self.account()?,
                     nonce,
                     WaitForTx {
@@ -178,6 +359,10 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     selector,
                     &deploy_result,
                 )?;
+                if let Ok(response) = &deploy_result {
+                    self.record_deployment(class_hash, response);
+                    self.record_fee(selector, response.transaction_hash.0);
+                }
 
                 Ok(CheatcodeHandlingResult::from_serializable(deploy_result))
             }
@@ -197,11 +382,24 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     return Ok(CheatcodeHandlingResult::from_serializable(success_output));
                 }
 
+                if self.plan.is_some() {
+                    self.record_step(PlannedStep::Invoke {
+                        contract_address: Felt(contract_address),
+                        function_selector: Felt(function_selector),
+                        calldata: calldata.into_iter().map(Felt).collect(),
+                    });
+                    let invoke_result: Result<_, StarknetCommandError> = Ok(InvokeResponse {
+                        transaction_hash: Felt(FieldElement::ZERO),
+                    });
+                    return Ok(CheatcodeHandlingResult::from_serializable(invoke_result));
+                }
+
                 let invoke_result = self.tokio_runtime.block_on(invoke::invoke(
                     contract_address,
                     function_selector,
                     calldata,
                     max_fee,
+                    None,
                     self.account()?,
                     nonce,
                     WaitForTx {
@@ -215,6 +413,9 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     selector,
                     &invoke_result,
                 )?;
+                if let Ok(response) = &invoke_result {
+                    self.record_fee(selector, response.transaction_hash.0);
+                }
 
                 Ok(CheatcodeHandlingResult::from_serializable(invoke_result))
             }
@@ -230,6 +431,33 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
 
                 Ok(CheatcodeHandlingResult::from_serializable(nonce))
             }
+            "get_contract" => {
+                let name: String = input_reader.read::<ByteArray>()?.into();
+                let contract = self.contracts.get(&name).copied();
+
+                Ok(CheatcodeHandlingResult::from_serializable(contract))
+            }
+            "get_env" => {
+                let name: String = input_reader.read::<ByteArray>()?.into();
+                let required: bool = input_reader.read()?;
+                if !self.allowed_env_vars.contains(&name) {
+                    return Err(anyhow!(
+                        "Environment variable = {name} is not allowed in scripts, add it to `allowed-env-vars` in `[tool.sncast]` section of Scarb.toml"
+                    )
+                    .into());
+                }
+                let value = env::var(&name).ok();
+                if value.is_none() && required {
+                    return Err(anyhow!(
+                        "Environment variable = {name} required by the script is not set"
+                    )
+                    .into());
+                }
+
+                Ok(CheatcodeHandlingResult::from_serializable(
+                    value.as_deref().map(ByteArray::from),
+                ))
+            }
             "tx_status" => {
                 let transaction_hash = input_reader.read()?;
 
@@ -263,10 +491,14 @@ pub fn run(
     metadata: &Metadata,
     package_metadata: &PackageMetadata,
     artifacts: &mut HashMap<String, StarknetContractArtifacts>,
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     tokio_runtime: Runtime,
     config: &CastConfig,
     state_file_path: Option<Utf8PathBuf>,
+    plan_path: Option<&Utf8PathBuf>,
+    manifest_path: Option<&Utf8PathBuf>,
+    network: &str,
+    fee_report: bool,
 ) -> Result<ScriptRunResponse> {
     warn_if_sncast_std_not_compatible(metadata)?;
     let artifacts = inject_lib_artifact(metadata, package_metadata, artifacts)?;
@@ -338,9 +570,13 @@ pub fn run(
             &config.accounts_file,
             provider,
             config.keystore.clone(),
+            config.keystore_format,
+            config.signer_command.as_deref(),
         ))?)
     };
     let state = StateManager::from(state_file_path)?;
+    let contracts = get_contracts_from_package(package_metadata)?;
+    let allowed_env_vars = get_allowed_env_vars_from_package(package_metadata)?;
 
     let cast_extension = CastScriptExtension {
         provider,
@@ -349,6 +585,11 @@ pub fn run(
         artifacts: &artifacts,
         account: account.as_ref(),
         state,
+        contracts,
+        allowed_env_vars,
+        plan: plan_path.map(|_| vec![]),
+        manifest: manifest_path.map(|_| DeploymentManifest::default()),
+        fees: fee_report.then(FeeReport::default),
     };
 
     let mut cast_runtime = ExtendedRuntime {
@@ -359,26 +600,46 @@ pub fn run(
     };
 
     let mut vm = VirtualMachine::new(true);
-    match runner.run_function_with_vm(
+    let run_result = runner.run_function_with_vm(
         func,
         &mut vm,
         &mut cast_runtime,
         hints_dict,
         assembled_program.bytecode.iter(),
         builtins,
-    ) {
-        Ok(result) => match result.value {
-            RunResultValue::Success(data) => Ok(ScriptRunResponse {
-                status: "success".to_string(),
-                message: build_readable_text(&data),
-            }),
-            RunResultValue::Panic(panic_data) => Ok(ScriptRunResponse {
-                status: "script panicked".to_string(),
-                message: build_readable_text(&panic_data),
-            }),
-        },
-        Err(err) => Err(err.into()),
+    );
+    if let (Some(plan_path), Some(steps)) = (plan_path, &cast_runtime.extension.plan) {
+        write_plan(plan_path, steps)?;
+    }
+    if let (Some(manifest_path), Some(manifest)) = (manifest_path, &cast_runtime.extension.manifest)
+    {
+        write_manifest(manifest_path, network, manifest)?;
     }
+
+    let fees = cast_runtime.extension.fees.take();
+    let (status, message) = match run_result?.value {
+        RunResultValue::Success(data) => (
+            if plan_path.is_some() {
+                "planned".to_string()
+            } else {
+                "success".to_string()
+            },
+            build_readable_text(&data),
+        ),
+        RunResultValue::Panic(panic_data) => (
+            "script panicked".to_string(),
+            build_readable_text(&panic_data),
+        ),
+    };
+
+    Ok(ScriptRunResponse {
+        status,
+        message,
+        declare_fee: fees.as_ref().map(|fees| Felt(fees.declare)),
+        deploy_fee: fees.as_ref().map(|fees| Felt(fees.deploy)),
+        invoke_fee: fees.as_ref().map(|fees| Felt(fees.invoke)),
+        total_fee: fees.as_ref().map(|fees| Felt(fees.total())),
+    })
 }
 
 fn sncast_std_version_requirement() -> VersionReq {
@@ -1,24 +1,80 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use camino::Utf8PathBuf;
 use clap::Args;
+use configuration::load_raw_global_config_profile;
+use primitive_types::U256;
+use serde_json::Value;
+use shared::print::print_as_warning;
+use shared::rpc::get_rpc_version;
 use sncast::helpers::configuration::CastConfig;
-use sncast::response::structs::{Decimal, ShowConfigResponse};
-use sncast::{chain_id_to_network_name, get_chain_id};
-use starknet::providers::jsonrpc::HttpTransport;
+use sncast::helpers::constants::{ETH_ADDRESS, STRK_ADDRESS};
+use sncast::helpers::duration::format_duration;
+use sncast::helpers::traced_transport::TracedTransport;
+use sncast::response::structs::{Decimal, Felt, ShowConfigResponse};
+use sncast::{
+    chain_id_to_network_name, get_account_data_from_accounts_file, get_chain_id,
+    get_class_hash_by_address, get_token_balance,
+};
+use starknet::core::types::FieldElement;
 use starknet::providers::JsonRpcClient;
+use url::Url;
 
 #[derive(Args)]
 #[command(about = "Show current configuration being used", long_about = None)]
-pub struct ShowConfig {}
+pub struct ShowConfig {
+    /// If passed, the RPC version of the node is additionally checked and shown
+    #[clap(long)]
+    pub check: bool,
+
+    /// Print the resolved configuration as a snfoundry.toml profile, or write it to the file at given path.
+    /// Values referencing environmental variables are kept as references and credentials in the url are redacted
+    #[clap(long, value_name = "PATH", num_args = 0..=1, conflicts_with_all = ["check", "balance"])]
+    pub dump_config: Option<Option<Utf8PathBuf>>,
+
+    /// If passed, ETH and STRK balances of the account are additionally fetched and shown
+    #[clap(long)]
+    pub balance: bool,
+}
+
+struct AccountBalance {
+    address: FieldElement,
+    status: &'static str,
+    eth_balance: FieldElement,
+    strk_balance: FieldElement,
+}
+
+/// Response fields paired with the keys they are read from in snfoundry.toml
+const CONFIG_KEYS: [(&str, &[&str]); 7] = [
+    ("rpc_url", &["url"]),
+    ("account", &["account"]),
+    ("accounts_file_path", &["accounts-file"]),
+    ("keystore", &["keystore"]),
+    ("wait_timeout", &["wait-params", "timeout"]),
+    ("wait_retry_interval", &["wait-params", "retry-interval"]),
+    ("network_timeout_retries", &["network-timeout-retries"]),
+];
 
 #[allow(clippy::ptr_arg)]
 pub async fn show_config(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     cast_config: CastConfig,
     profile: Option<String>,
+    cli_overrides: &[&str],
+    check: bool,
+    balance: bool,
 ) -> Result<ShowConfigResponse> {
     let chain_id_field = get_chain_id(provider).await?;
     let chain_id = chain_id_to_network_name(chain_id_field);
+    let rpc_version = if check {
+        Some(get_rpc_version(provider).await?.to_string())
+    } else {
+        None
+    };
+    let account_balance = if balance {
+        Some(get_account_balance(provider, &cast_config, chain_id_field).await?)
+    } else {
+        None
+    };
     let rpc_url = Some(cast_config.url).filter(|p| !p.is_empty());
     let account = Some(cast_config.account).filter(|p| !p.is_empty());
     let mut accounts_file_path =
@@ -29,15 +85,194 @@ pub async fn show_config(
     }
     let wait_timeout = Some(cast_config.wait_params.get_timeout());
     let wait_retry_interval = Some(cast_config.wait_params.get_retry_interval());
+    let network_timeout_retries = Some(cast_config.network_timeout_retries);
+
+    let raw_profile = load_raw_global_config_profile::<CastConfig>(&None, &profile)?;
+    let present_fields = [
+        ("rpc_url", rpc_url.is_some()),
+        ("account", account.is_some()),
+        ("accounts_file_path", accounts_file_path.is_some()),
+        ("keystore", keystore.is_some()),
+        ("wait_timeout", wait_timeout.is_some()),
+        ("wait_retry_interval", wait_retry_interval.is_some()),
+        ("network_timeout_retries", network_timeout_retries.is_some()),
+    ];
+    let sources = present_fields
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(field, _)| {
+            let source = value_source(field, cli_overrides, raw_profile.as_ref());
+            format!("{field}: {source}")
+        })
+        .collect();
 
     Ok(ShowConfigResponse {
+        resolved_profile: profile.clone().unwrap_or_else(|| "default".to_string()),
         profile,
         chain_id,
         rpc_url,
         account,
         accounts_file_path,
         keystore,
-        wait_timeout: wait_timeout.map(|x| Decimal(u64::from(x))),
-        wait_retry_interval: wait_retry_interval.map(|x| Decimal(u64::from(x))),
+        wait_timeout: wait_timeout.map(format_duration),
+        wait_retry_interval: wait_retry_interval.map(format_duration),
+        network_timeout_retries: network_timeout_retries.map(|x| Decimal(x.into())),
+        sources,
+        rpc_version,
+        account_address: account_balance
+            .as_ref()
+            .map(|balance| Felt(balance.address)),
+        account_status: account_balance
+            .as_ref()
+            .map(|balance| balance.status.to_string()),
+        eth_balance: account_balance
+            .as_ref()
+            .map(|balance| Felt(balance.eth_balance)),
+        strk_balance: account_balance.map(|balance| Felt(balance.strk_balance)),
+    })
+}
+
+async fn get_account_balance(
+    provider: &JsonRpcClient<TracedTransport>,
+    cast_config: &CastConfig,
+    chain_id: FieldElement,
+) -> Result<AccountBalance> {
+    let address = get_account_address(cast_config, chain_id)?;
+    let eth_balance = get_token_balance(provider, ETH_ADDRESS, address).await?;
+    let strk_balance = get_token_balance(provider, STRK_ADDRESS, address).await?;
+    let deployed = get_class_hash_by_address(provider, address)
+        .await?
+        .is_some();
+
+    if eth_balance.is_zero() && strk_balance.is_zero() {
+        print_as_warning(&anyhow!(
+            "Account = {} has no ETH nor STRK, fund it before sending transactions",
+            cast_config.account
+        ));
+    }
+
+    Ok(AccountBalance {
+        address,
+        status: if deployed { "deployed" } else { "not deployed" },
+        eth_balance: u256_to_felt(eth_balance)?,
+        strk_balance: u256_to_felt(strk_balance)?,
     })
 }
+
+fn u256_to_felt(value: U256) -> Result<FieldElement> {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    FieldElement::from_bytes_be(&bytes)
+        .map_err(|_| anyhow!("Balance = {value} does not fit into a felt"))
+}
+
+/// Reads the address of the account from the accounts file or, when using keystore, from the account file,
+/// which does not require decrypting the keystore
+fn get_account_address(cast_config: &CastConfig, chain_id: FieldElement) -> Result<FieldElement> {
+    let account = &cast_config.account;
+    if account.is_empty() {
+        bail!("Account is required to check its balance, pass it with `--account` or set `account` in snfoundry.toml");
+    }
+
+    let address = if cast_config.keystore.is_some() {
+        let contents = std::fs::read_to_string(account)
+            .with_context(|| format!("Failed to read account file = {account}"))?;
+        let account_info: Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse account file = {account}"))?;
+        account_info
+            .pointer("/deployment/address")
+            .and_then(Value::as_str)
+            .and_then(|address| address.parse().ok())
+    } else {
+        get_account_data_from_accounts_file(account, chain_id, &cast_config.accounts_file)?.address
+    };
+
+    address.with_context(|| format!("Failed to get address of account = {account}"))
+}
+
+/// Returns where the value of given response field comes from: `cli`, `env` (profile value
+/// referencing an environmental variable), `profile` or `default`
+fn value_source(field: &str, cli_overrides: &[&str], raw_profile: Option<&Value>) -> &'static str {
+    if cli_overrides.contains(&field) {
+        return "cli";
+    }
+
+    let keys = CONFIG_KEYS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map_or(&[][..], |(_, keys)| *keys);
+    let mut raw_value = raw_profile;
+    for key in keys {
+        raw_value = raw_value.and_then(|value| value.get(key));
+    }
+
+    match raw_value {
+        Some(Value::String(value)) if value.starts_with('$') || value.contains("${") => "env",
+        Some(_) => "profile",
+        None => "default",
+    }
+}
+
+/// Serializes the resolved configuration to a snfoundry.toml profile
+pub fn dump_config(
+    cast_config: &CastConfig,
+    profile: Option<&str>,
+    cli_overrides: &[&str],
+) -> Result<String> {
+    let raw_profile =
+        load_raw_global_config_profile::<CastConfig>(&None, &profile.map(String::from))?;
+
+    let mut config = toml::Value::try_from(cast_config).context("Failed to serialize config")?;
+    let table = config
+        .as_table_mut()
+        .expect("Config should be serialized to a table");
+    if let Some(toml::Value::String(url)) = table.get_mut("url") {
+        *url = redact_url(url);
+    }
+    // Values read from environmental variables are written as references, so they are not revealed
+    for (field, key) in [
+        ("rpc_url", "url"),
+        ("accounts_file_path", "accounts-file"),
+        ("keystore", "keystore"),
+    ] {
+        if value_source(field, cli_overrides, raw_profile.as_ref()) == "env" {
+            if let Some(Value::String(raw_value)) =
+                raw_profile.as_ref().and_then(|raw| raw.get(key))
+            {
+                table.insert(key.to_string(), toml::Value::String(raw_value.clone()));
+            }
+        }
+    }
+
+    let mut profiles = toml::Table::new();
+    profiles.insert(profile.unwrap_or("default").to_string(), config);
+    let mut root = toml::Table::new();
+    root.insert("sncast".to_string(), toml::Value::Table(profiles));
+
+    toml::to_string(&root).context("Failed to serialize config")
+}
+
+/// Replaces the password and values of query parameters in the url, which commonly hold API keys
+fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    if parsed.password().is_some() {
+        parsed
+            .set_password(Some(REDACTED))
+            .expect("Url with a password can have it replaced");
+    }
+    if parsed.query().is_some() {
+        let keys: Vec<String> = parsed
+            .query_pairs()
+            .map(|(key, _)| key.into_owned())
+            .collect();
+        parsed
+            .query_pairs_mut()
+            .clear()
+            .extend_pairs(keys.iter().map(|key| (key, REDACTED)));
+    }
+    parsed.to_string()
+}
+
+const REDACTED: &str = "redacted";
@@ -1,8 +1,8 @@
 use clap::Args;
+use sncast::helpers::traced_transport::TracedTransport;
 use sncast::response::errors::StarknetCommandError;
 use sncast::response::structs::{ExecutionStatus, FinalityStatus, TransactionStatusResponse};
 use starknet::core::types::{FieldElement, TransactionExecutionStatus, TransactionStatus};
-use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider};
 
 #[derive(Args)]
@@ -13,7 +13,7 @@ pub struct TxStatus {
 }
 
 pub async fn tx_status(
-    provider: &JsonRpcClient<HttpTransport>,
+    provider: &JsonRpcClient<TracedTransport>,
     transaction_hash: FieldElement,
 ) -> Result<TransactionStatusResponse, StarknetCommandError> {
     provider
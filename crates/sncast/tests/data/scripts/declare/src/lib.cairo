@@ -5,3 +5,4 @@ mod with_invalid_nonce;
 mod insufficient_account_balance;
 mod time_out;
 
+mod plan;
@@ -0,0 +1,20 @@
+use sncast_std::{declare, deploy, invoke, DeclareResult, DeployResult, InvokeResult};
+
+fn main() {
+    let declare_result = declare("Mapa", Option::None, Option::None).expect('declare failed');
+
+    let deploy_result = deploy(
+        declare_result.class_hash,
+        ArrayTrait::new(),
+        Option::Some(0x3),
+        false,
+        Option::None,
+        Option::None
+    )
+        .expect('deploy failed');
+
+    invoke(
+        deploy_result.contract_address, selector!("put"), array![0x1, 0x2], Option::None, Option::None
+    )
+        .expect('invoke failed');
+}
@@ -0,0 +1,10 @@
+use sncast_std::{call, get_contract};
+
+fn main() {
+    let eth = get_contract("eth").unwrap();
+    let call_result = call(eth.try_into().unwrap(), selector!("decimals"), array![]).unwrap();
+    let call_result = *call_result.data[0];
+    assert(call_result == 18, call_result);
+
+    assert(get_contract("nonexistent").is_none(), 'unexpected contract');
+}
@@ -0,0 +1,8 @@
+use sncast_std::{get_env, require_env};
+
+fn main() {
+    let secret = require_env("SNCAST_SCRIPT_TEST_SECRET");
+    assert(secret == "abc", 'unexpected secret');
+
+    assert(get_env("SNCAST_SCRIPT_TEST_MISSING").is_none(), 'unexpected variable');
+}
@@ -0,0 +1,5 @@
+use sncast_std::get_env;
+
+fn main() {
+    get_env("HOME");
+}
@@ -1,3 +1,6 @@
 mod call_happy;
 mod call_fail;
+mod get_contract;
+mod get_env;
+mod get_env_not_allowed;
 mod using_starknet_syscall;
@@ -0,0 +1,106 @@
+use crate::helpers::constants::{MAP_CONTRACT_ADDRESS_SEPOLIA, MAP_CONTRACT_CLASS_HASH_SEPOLIA};
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+use camino::Utf8PathBuf;
+use indoc::indoc;
+use serde_json::{json, Value};
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use tempfile::tempdir;
+
+#[test]
+fn test_happy_case_function() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "abi",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let abi: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        abi,
+        json!([{
+            "type": "function",
+            "name": "get",
+            "inputs": [{ "name": "key", "type": "core::felt252" }],
+            "outputs": [{ "type": "core::felt252" }],
+            "state_mutability": "view"
+        }])
+    );
+}
+
+#[test]
+fn test_happy_case_class_hash_event() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "abi",
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--event",
+        "Event",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let abi: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(abi[0]["type"], "event");
+    assert_eq!(abi[0]["name"], "map::Map::Event");
+}
+
+#[test]
+fn test_save_to_abi_file() {
+    let tempdir = tempdir().expect("Failed to create a temporary directory");
+    let abi_file = Utf8PathBuf::try_from(tempdir.path().join("map_abi.json")).unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "abi",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--abi-file",
+        abi_file.as_str(),
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: abi
+        path: [..]map_abi.json
+        "},
+    );
+    let abi: Value = serde_json::from_str(&std::fs::read_to_string(abi_file).unwrap()).unwrap();
+    assert!(abi
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|entry| entry["name"] == "map::IMap"));
+}
+
+#[test]
+fn test_function_not_found() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "abi",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "nonexistent",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Function = nonexistent not found in the contract ABI",
+    );
+}
@@ -318,6 +318,38 @@ pub async fn test_happy_case_keystore(account_type: &str) {
     );
 }
 
+#[tokio::test]
+pub async fn test_happy_case_keystore_in_nested_directory() {
+    let temp_dir = tempdir().expect("Unable to create a temporary directory");
+    let keystore_file = "keys/nested/my_key.json";
+    let account_file = "accounts/my_account.json";
+    env::set_var(CREATE_KEYSTORE_PASSWORD_ENV_VAR, "123");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--keystore",
+        keystore_file,
+        "--account",
+        account_file,
+        "account",
+        "create",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+
+    snapbox.assert().stdout_matches(indoc! {r"
+        command: account create
+        add_profile: --add-profile flag was not set. No profile added to snfoundry.toml
+        address: 0x[..]
+        max_fee: [..]
+        message: Account successfully created[..]
+    "});
+
+    assert!(temp_dir.path().join(keystore_file).exists());
+    assert!(temp_dir.path().join(account_file).exists());
+}
+
 #[tokio::test]
 pub async fn test_happy_case_keystore_add_profile() {
     let tempdir = copy_config_to_tempdir("tests/data/files/correct_snfoundry.toml", None).unwrap();
@@ -545,6 +577,80 @@ pub async fn test_happy_case_keystore_hex_format() {
     assert!(contents.contains("\"legacy\": false"));
 }
 
+#[tokio::test]
+pub async fn test_happy_case_deploy() {
+    let temp_dir = tempdir().expect("Unable to create a temporary directory");
+    let accounts_file = "accounts.json";
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "create",
+        "--name",
+        "my_account",
+        "--fund-faucet",
+        "http://127.0.0.1:5055/mint",
+        "--deploy",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: account create
+        address: 0x[..]
+        funding: Address funded from the faucet, balance = [..]
+        transaction_hash: 0x[..]
+        "},
+    );
+
+    let contents = fs::read_to_string(temp_dir.path().join(accounts_file))
+        .expect("Unable to read created file");
+    let items: serde_json::Value = serde_json::from_str(&contents).unwrap();
+    assert_eq!(
+        items["alpha-sepolia"]["my_account"]["deployed"],
+        json!(true)
+    );
+}
+
+#[tokio::test]
+pub async fn test_deploy_not_funded() {
+    let temp_dir = tempdir().expect("Unable to create a temporary directory");
+    let accounts_file = "accounts.json";
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "create",
+        "--name",
+        "my_account",
+        "--deploy",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: account create
+        error: Account created, but it could not be deployed. Prefund address 0x[..] with at least [..] tokens and deploy it with `account deploy` command
+        "},
+    );
+
+    let contents = fs::read_to_string(temp_dir.path().join(accounts_file))
+        .expect("Unable to read created file");
+    assert!(contents.contains("my_account"));
+}
+
 fn get_formatted_account_type(account_type: &str) -> &str {
     match account_type {
         "oz" => "open_zeppelin",
@@ -623,3 +729,73 @@ fn get_keystore_account_pattern(account_type: &AccountType, class_hash: Option<&
 
     to_string_pretty(&account_json).unwrap()
 }
+
+#[tokio::test]
+pub async fn test_happy_case_fund_from_faucet() {
+    let temp_dir = tempdir().expect("Unable to create a temporary directory");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "accounts.json",
+        "account",
+        "create",
+        "--name",
+        "my_account",
+        "--salt",
+        "0x2",
+        "--fund-faucet",
+        "http://127.0.0.1:5055/mint",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: account create
+        add_profile: --add-profile flag was not set. No profile added to snfoundry.toml
+        address: 0x[..]
+        funding: Address funded from the faucet, balance = [..]
+        max_fee: [..]
+        "},
+    );
+}
+
+#[tokio::test]
+pub async fn test_fund_from_faucet_fails() {
+    let temp_dir = tempdir().expect("Unable to create a temporary directory");
+    let accounts_file = "accounts.json";
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "create",
+        "--name",
+        "my_account",
+        "--fund-faucet",
+        "http://127.0.0.1:5055/nonexistent-faucet",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: account create
+        add_profile: --add-profile flag was not set. No profile added to snfoundry.toml
+        address: 0x[..]
+        funding: Failed to fund address from the faucet: Failed to request funds from faucet = http://127.0.0.1:5055/nonexistent-faucet[..]
+        "},
+    );
+
+    let contents = fs::read_to_string(temp_dir.path().join(accounts_file))
+        .expect("Unable to read created file");
+    assert!(contents.contains("my_account"));
+}
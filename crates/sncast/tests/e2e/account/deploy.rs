@@ -8,7 +8,7 @@ use configuration::copy_config_to_tempdir;
 use conversions::string::IntoHexStr;
 use indoc::indoc;
 use serde_json::Value;
-use shared::test_utils::output_assert::{assert_stderr_contains, AsOutput};
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains, AsOutput};
 use sncast::helpers::constants::{
     ARGENT_CLASS_HASH, BRAAVOS_CLASS_HASH, KEYSTORE_PASSWORD_ENV_VAR, OZ_CLASS_HASH,
 };
@@ -240,6 +240,121 @@ pub async fn create_account(add_profile: bool, class_hash: &str, account_type: &
     tempdir
 }
 
+#[tokio::test]
+pub async fn test_happy_case_wait_confirms_address() {
+    let tempdir = create_account(false, &OZ_CLASS_HASH.into_hex_string(), "oz").await;
+    let accounts_file = "accounts.json";
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "--wait",
+        "account",
+        "deploy",
+        "--name",
+        "my_account",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    let contents = fs::read_to_string(tempdir.path().join(accounts_file)).unwrap();
+    let items: serde_json::Value =
+        serde_json::from_str(&contents).expect("Failed to parse accounts file at ");
+    let address = items["alpha-sepolia"]["my_account"]["address"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    assert_stdout_contains(
+        output,
+        format!("command: account deploy\naddress: {address}\ntransaction_hash: 0x[..]"),
+    );
+}
+
+#[tokio::test]
+pub async fn test_deploy_all_pending() {
+    let tempdir = create_account(false, &OZ_CLASS_HASH.into_hex_string(), "oz").await;
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        "accounts.json",
+        "--wait",
+        "account",
+        "deploy",
+        "--all-pending",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+    assert_stdout_contains(
+        output,
+        "accounts: [{name: my_account, status: Deployed, transaction_hash: 0x[..]}]",
+    );
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+    assert_stdout_contains(
+        output,
+        "accounts: [{name: my_account, status: AlreadyDeployed}]",
+    );
+}
+
+#[tokio::test]
+pub async fn test_deploy_all_pending_with_failure() {
+    let tempdir = create_account(false, &OZ_CLASS_HASH.into_hex_string(), "oz").await;
+    let accounts_file = "accounts.json";
+
+    // Not funded, so its deployment fails
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "account",
+        "create",
+        "--name",
+        "unfunded_account",
+    ];
+    runner(&args).current_dir(tempdir.path()).assert().success();
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_file,
+        "--json",
+        "--wait",
+        "account",
+        "deploy",
+        "--all-pending",
+        "--max-fee",
+        "99999999999999999",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().code(1).get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output).unwrap();
+    let accounts = json["accounts"].as_array().unwrap();
+    assert_eq!(accounts.len(), 2);
+
+    assert_eq!(accounts[0]["name"], "my_account");
+    assert_eq!(accounts[0]["status"], "Deployed");
+    assert!(accounts[0]["transaction_hash"].is_string());
+
+    assert_eq!(accounts[1]["name"], "unfunded_account");
+    assert_eq!(accounts[1]["status"], "Failed");
+    assert!(accounts[1]["error"].is_string());
+}
+
 #[test_case("oz"; "open_zeppelin_account")]
 #[test_case("argent"; "argent_account")]
 #[test_case("braavos"; "braavos_account")]
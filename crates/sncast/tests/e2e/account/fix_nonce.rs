@@ -0,0 +1,67 @@
+use crate::helpers::fixtures::default_cli_args;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+
+#[test]
+fn test_report_nonces() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user17", "account", "fix-nonce"]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account fix-nonce
+        latest_nonce: 0x[..]
+        pending_nonce: 0x[..]
+    "});
+}
+
+#[test]
+fn test_resume_from_nonce_requires_fill() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user17",
+        "account",
+        "fix-nonce",
+        "--resume-from-nonce",
+        "0x1",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: the following required arguments were not provided:",
+    );
+}
+
+#[test]
+fn test_fill_nonce() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user17",
+        "account",
+        "fix-nonce",
+        "--fill",
+        "--yes",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [WARNING] The filler transaction is a regular transaction of the account, which pays its fee (estimated [..] WEI)
+        command: account fix-nonce
+        latest_nonce: 0x[..]
+        pending_nonce: 0x[..]
+        filled_nonce: 0x[..]
+        transaction_hash: 0x[..]
+    "},
+    );
+}
@@ -0,0 +1,60 @@
+use crate::helpers::constants::URL;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::Value;
+use tempfile::tempdir;
+
+const ACCOUNTS_FILE_NAME: &str = "temp_accounts.json";
+
+#[test]
+pub fn test_happy_case() {
+    let temp_dir = tempdir().expect("Unable to create temporary directory");
+    let old_accounts = indoc! {r#"
+    {
+        "custom-network": {
+            "user3": {
+                "private_key": "0x00E3E70682C2094CAC629F6FBED82C07CD",
+                "public_key": "0x7e52885445756b313ea16849145363ccb73fb4ab0440dbac333cf9d13de82b9",
+                "address": "0x7e00d496e324876bbc8531f2d9a82bf154d1a04a50218ee74cdd372f75a551a",
+                "deployed": "true",
+                "type": "oz"
+            }
+        }
+    }
+    "#};
+    std::fs::write(temp_dir.path().join(ACCOUNTS_FILE_NAME), old_accounts)
+        .expect("Could not write temporary testing accounts");
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        ACCOUNTS_FILE_NAME,
+        "account",
+        "migrate-file",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r#"
+        backup_path: [..]temp_accounts.json.bak
+        changes: [custom-network.user3: `private_key` written as hex, custom-network.user3: `deployed` written as boolean, custom-network.user3: `type` "oz" renamed to "open_zeppelin"]
+        command: account migrate-file
+    "#});
+
+    let contents = std::fs::read_to_string(temp_dir.path().join(ACCOUNTS_FILE_NAME)).unwrap();
+    let items: Value = serde_json::from_str(&contents).unwrap();
+    let account = &items["custom-network"]["user3"];
+    assert_eq!(account["private_key"], "0xe3e70682c2094cac629f6fbed82c07cd");
+    assert_eq!(account["deployed"], true);
+    assert_eq!(account["type"], "open_zeppelin");
+    let backup =
+        std::fs::read_to_string(temp_dir.path().join(format!("{ACCOUNTS_FILE_NAME}.bak"))).unwrap();
+    assert_eq!(backup, old_accounts);
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        changes: []
+        command: account migrate-file
+    "});
+}
@@ -2,3 +2,6 @@ mod add;
 mod create;
 mod delete;
 mod deploy;
+mod fix_nonce;
+mod migrate_file;
+mod rename;
@@ -0,0 +1,135 @@
+use crate::helpers::constants::URL;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::assert_stderr_contains;
+use tempfile::{tempdir, TempDir};
+
+const ACCOUNTS_FILE_NAME: &str = "temp_accounts.json";
+
+#[test]
+pub fn test_happy_case() {
+    let temp_dir = create_tempdir_with_accounts_file();
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        ACCOUNTS_FILE_NAME,
+        "account",
+        "rename",
+        "--from",
+        "user3",
+        "--to",
+        "user5",
+        "--network",
+        "custom-network",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account rename
+        result: Account user3 successfully renamed to user5
+    "});
+
+    let contents = std::fs::read_to_string(temp_dir.path().join(ACCOUNTS_FILE_NAME)).unwrap();
+    let items: Value = serde_json::from_str(&contents).unwrap();
+    assert!(items["custom-network"]["user3"].is_null());
+    assert_eq!(
+        items["custom-network"]["user5"]["private_key"],
+        "0xe3e70682c2094cac629f6fbed82c07cd"
+    );
+}
+
+#[test]
+pub fn test_target_name_exists() {
+    let temp_dir = create_tempdir_with_accounts_file();
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        ACCOUNTS_FILE_NAME,
+        "account",
+        "rename",
+        "--from",
+        "user3",
+        "--to",
+        "user4",
+        "--network",
+        "custom-network",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: account rename
+        error: Account with name user4 already exists in network = custom-network
+        "},
+    );
+}
+
+#[test]
+pub fn test_update_profiles() {
+    let temp_dir = create_tempdir_with_accounts_file();
+    std::fs::write(
+        temp_dir.path().join("snfoundry.toml"),
+        indoc! {r#"
+            [sncast.default]
+            account = "user3"
+            accounts-file = "temp_accounts.json"
+        "#},
+    )
+    .unwrap();
+    let args = vec![
+        "--url",
+        URL,
+        "account",
+        "rename",
+        "--from",
+        "user3",
+        "--to",
+        "user5",
+        "--network",
+        "custom-network",
+        "--update-profiles",
+    ];
+
+    let snapbox = runner(&args).current_dir(temp_dir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: account rename
+        result: Account user3 successfully renamed to user5
+        updated_profiles: [default]
+    "});
+
+    let config = std::fs::read_to_string(temp_dir.path().join("snfoundry.toml")).unwrap();
+    assert!(config.contains("account = \"user5\""));
+}
+
+fn create_tempdir_with_accounts_file() -> TempDir {
+    let tempdir = tempdir().expect("Unable to create temporary directory");
+
+    let json_data = indoc! {r#"
+    {
+        "custom-network": {
+            "user3": {
+                "private_key": "0xe3e70682c2094cac629f6fbed82c07cd",
+                "public_key": "0x7e52885445756b313ea16849145363ccb73fb4ab0440dbac333cf9d13de82b9",
+                "address": "0x7e00d496e324876bbc8531f2d9a82bf154d1a04a50218ee74cdd372f75a551a"
+            },
+            "user4": {
+                "private_key": "0x73fbb3c1eff11167598455d0408f3932e42c678bd8f7fbc6028c716867cc01f",
+                "public_key": "0x43a74f86b7e204f1ba081636c9d4015e1f54f5bb03a4ae8741602a15ffbb182",
+                "address": "0x7ccdf182d27c7aaa2e733b94db4a3f7b28ff56336b34abf43c15e3a9edfbe91"
+            }
+        }
+    }
+    "#};
+    std::fs::write(tempdir.path().join(ACCOUNTS_FILE_NAME), json_data)
+        .expect("Could not write temporary testing accounts");
+
+    tempdir
+}
@@ -1,8 +1,10 @@
 use crate::helpers::constants::MAP_CONTRACT_ADDRESS_SEPOLIA;
 use crate::helpers::fixtures::{default_cli_args, invoke_contract};
 use crate::helpers::runner::runner;
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use shared::test_utils::output_assert::assert_stderr_contains;
+use std::fs;
+use tempfile::tempdir;
 
 #[test]
 fn test_happy_case() {
@@ -27,6 +29,65 @@ fn test_happy_case() {
     "});
 }
 
+#[test]
+fn test_selector_from_file() {
+    let tempdir = tempdir().unwrap();
+    let selector_file = tempdir.path().join("selector");
+    // selector of `get`
+    fs::write(
+        &selector_file,
+        "0x17c00f03de8b5bd58d2016b59d251c13056b989171c5852949903bc043bc27\n",
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--selector-from-file",
+        selector_file.to_str().unwrap(),
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_selector_from_file_invalid() {
+    let tempdir = tempdir().unwrap();
+    let selector_file = tempdir.path().join("selector");
+    fs::write(&selector_file, "get").unwrap();
+    let function = format!("@{}", selector_file.to_str().unwrap());
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        &function,
+        "--calldata",
+        "0x0",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Failed to parse selector = get from file = [..]",
+    );
+}
+
 #[tokio::test]
 async fn test_call_after_storage_changed() {
     invoke_contract(
@@ -181,3 +242,662 @@ fn test_wrong_block_id() {
         "},
     );
 }
+
+#[test]
+fn test_function_not_in_abi_file() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "nonexistent",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Function = nonexistent not found in the contract ABI
+        "},
+    );
+}
+
+#[test]
+fn test_block_id_by_timestamp() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "time:99999999999",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_block_id_timestamp_before_genesis() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--block-id",
+        "time:0",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Timestamp = 0 predates the genesis block
+        "},
+    );
+}
+
+#[test]
+fn test_raw_output() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--int-format",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+        "--raw",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        0
+    "});
+}
+
+#[test]
+fn test_raw_output_with_json() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--raw",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(output, "Error: `--raw` cannot be used with `--json`");
+}
+
+#[test]
+fn test_decode_response() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+        "--decode",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        decoded: 0x0
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_decode_response_json() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+        "--decode",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["decoded"], serde_json::json!("0x0"));
+    assert_eq!(json["response"], serde_json::json!(["0x0"]));
+}
+
+#[test]
+fn test_batch_file() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--batch-file",
+        "tests/data/files/call_batch.json",
+        "--block-id",
+        "latest",
+        "--concurrency",
+        "2",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        responses: [[0x12], [0x0]]
+    "});
+}
+
+#[test]
+fn test_batch_file_decode_json() {
+    let tempdir = tempdir().unwrap();
+    let batch_file = tempdir.path().join("batch.json");
+    fs::write(
+        &batch_file,
+        serde_json::json!([
+            {"contract_address": MAP_CONTRACT_ADDRESS_SEPOLIA, "function": "get", "calldata": ["0x0"]},
+            {"contract_address": MAP_CONTRACT_ADDRESS_SEPOLIA, "function": "get", "calldata": ["0x1"]}
+        ])
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json",
+        "call",
+        "--batch-file",
+        batch_file.to_str().unwrap(),
+        "--block-id",
+        "latest",
+        "--decode",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let decoded = json["decoded"].as_array().unwrap();
+    assert_eq!(decoded.len(), 2);
+    assert!(decoded.iter().all(serde_json::Value::is_string));
+}
+
+#[test]
+fn test_batch_file_calldata_not_scaled() {
+    let tempdir = tempdir().unwrap();
+    let batch_file = tempdir.path().join("batch.json");
+    fs::write(
+        &batch_file,
+        serde_json::json!([
+            {"contract_address": MAP_CONTRACT_ADDRESS_SEPOLIA, "function": "get", "calldata": ["1e18"]}
+        ])
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--batch-file",
+        batch_file.to_str().unwrap(),
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: call
+        error: Invalid call number 1 in batch file: [..]
+        "},
+    );
+}
+
+#[test]
+fn test_batch_file_strict_felt_parsing() {
+    let tempdir = tempdir().unwrap();
+    let batch_file = tempdir.path().join("batch.json");
+    fs::write(
+        &batch_file,
+        serde_json::json!([
+            {"contract_address": MAP_CONTRACT_ADDRESS_SEPOLIA, "function": "get", "calldata": ["1_000"]}
+        ])
+        .to_string(),
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--strict-felt-parsing",
+        "call",
+        "--batch-file",
+        batch_file.to_str().unwrap(),
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: call
+        error: Invalid call number 1 in batch file: Felt = 1_000 is ambiguous, hex values have to be prefixed with `0x` and decimal values may only contain digits
+        "},
+    );
+}
+
+#[test]
+fn test_batch_file_conflicts_with_contract_address() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--batch-file",
+        "tests/data/files/call_batch.json",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: the argument '--batch-file <BATCH_FILE>' cannot be used with '--contract-address <CONTRACT_ADDRESS>'",
+    );
+}
+
+#[test]
+fn test_follow_proxy_not_a_proxy() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--follow-proxy",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Failed to resolve the implementation of proxy = [..]: none of the common proxy patterns matched, pass the storage slot holding the implementation with `--impl-slot`",
+    );
+}
+
+#[test]
+fn test_calldata_from_stdin() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata-stdin",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args).stdin("0x0\n");
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_calldata_from_empty_stdin() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata-stdin",
+    ]);
+
+    let snapbox = runner(&args).stdin("");
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(output, "Error: No calldata was passed on stdin");
+}
+
+#[test]
+fn test_strict_felt_parsing_rejects_ambiguous_calldata() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--strict-felt-parsing",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata-stdin",
+    ]);
+
+    let snapbox = runner(&args).stdin("1_000\n");
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Invalid felt = 1_000 at position 1 of calldata passed on stdin
+
+        Caused by:
+            Felt = 1_000 is ambiguous, hex values have to be prefixed with `0x` and decimal values may only contain digits
+        "},
+    );
+}
+
+#[test]
+fn test_json_errors_only_success_is_human_readable() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json-errors-only",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_json_errors_only_error_is_json() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--json-errors-only",
+        "call",
+        "--contract-address",
+        "0x1",
+        "--function",
+        "get",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stderr.clone();
+
+    let error: serde_json::Value =
+        serde_json::from_slice(&output).expect("Error should be printed as JSON");
+    assert_eq!(error["command"], "call");
+    assert_eq!(
+        error["error"],
+        "There is no contract at the specified address"
+    );
+    assert_eq!(error["error_kind"], "not_found");
+}
+
+#[test]
+fn test_function_as_raw_selector() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        // selector of `get`
+        "0x17c00f03de8b5bd58d2016b59d251c13056b989171c5852949903bc043bc27",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_output_template() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--output-template",
+        "{command} returned {response}",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        call returned [0x0]
+    "});
+}
+
+#[test]
+fn test_output_template_unknown_field() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--output-template",
+        "{transaction_hash}",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Unknown field = transaction_hash in `--output-template`, available fields: command, decoded, response, responses",
+    );
+}
+
+#[test]
+fn test_contract_address_from_address_book() {
+    let tempdir = tempdir().unwrap();
+    fs::write(
+        tempdir.path().join("Scarb.toml"),
+        formatdoc! {r#"
+            [package]
+            name = "address_book"
+
+            [tool.sncast.addresses]
+            map = "{MAP_CONTRACT_ADDRESS_SEPOLIA}"
+        "#},
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "call",
+        "--contract-address",
+        "@map",
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_unknown_address_book_entry() {
+    let tempdir = tempdir().unwrap();
+    let address_book = tempdir.path().join("addresses.toml");
+    fs::write(
+        &address_book,
+        format!("map = \"{MAP_CONTRACT_ADDRESS_SEPOLIA}\"\n"),
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--address-book",
+        address_book.to_str().unwrap(),
+        "call",
+        "--contract-address",
+        "@token",
+        "--function",
+        "get",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Unknown address book entry = token, known entries: map",
+    );
+}
+
+#[test]
+fn test_batch_file_with_address_book() {
+    let tempdir = tempdir().unwrap();
+    let address_book = tempdir.path().join("addresses.toml");
+    fs::write(
+        &address_book,
+        format!("map = \"{MAP_CONTRACT_ADDRESS_SEPOLIA}\"\n"),
+    )
+    .unwrap();
+    let batch_file = tempdir.path().join("batch.json");
+    fs::write(
+        &batch_file,
+        formatdoc! {r#"
+            [
+                {{ "contract_address": "@map", "function": "get", "calldata": ["0x0"] }},
+                {{ "contract_address": "{MAP_CONTRACT_ADDRESS_SEPOLIA}", "function": "get", "calldata": ["0x0"] }}
+            ]
+        "#},
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--address-book",
+        address_book.to_str().unwrap(),
+        "call",
+        "--batch-file",
+        batch_file.to_str().unwrap(),
+        "--block-id",
+        "latest",
+    ]);
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        responses: [[0x0], [0x0]]
+    "});
+}
+
+#[test]
+fn test_cache_reads_reuses_result() {
+    // Key not used by other tests, so that the first call is not served from the cache
+    let key = format!("{:#x}", rand::random::<u64>());
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--log-level",
+        "info",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        key.as_str(),
+        "--cache-reads",
+        "1m",
+    ]);
+
+    let output = runner(&args).assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+    assert!(!String::from_utf8_lossy(&output.get_output().stderr)
+        .contains("Reusing the result of the call from the cache"));
+
+    let output = runner(&args).assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+    assert_stderr_contains(
+        output,
+        "[..]INFO sncast::helpers::call_cache: Reusing the result of the call from the cache[..]",
+    );
+}
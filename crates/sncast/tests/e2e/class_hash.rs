@@ -0,0 +1,50 @@
+use crate::helpers::constants::CONTRACTS_DIR;
+use crate::helpers::fixtures::copy_directory_to_tempdir;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use std::process::Command;
+
+#[test]
+fn test_happy_case() {
+    let contract_path = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let status = Command::new("scarb")
+        .arg("build")
+        .current_dir(contract_path.path())
+        .status()
+        .expect("Failed to run scarb build");
+    assert!(status.success());
+
+    let args = vec![
+        "class-hash",
+        "--sierra-path",
+        "target/dev/map_Map.contract_class.json",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: class-hash
+        class_hash: 0x[..]
+        "},
+    );
+}
+
+#[test]
+fn test_sierra_file_does_not_exist() {
+    let args = vec!["class-hash", "--sierra-path", "nonexistent.json"];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: class-hash
+        error: Failed to read Sierra artifact = nonexistent.json[..]
+        "},
+    );
+}
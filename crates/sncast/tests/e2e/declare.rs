@@ -106,6 +106,133 @@ async fn test_contract_already_declared() {
     );
 }
 
+#[tokio::test]
+async fn test_contract_already_declared_error_kind() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "--json",
+        "declare",
+        "--contract-name",
+        "Map",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stderr.clone();
+
+    let error: serde_json::Value = std::str::from_utf8(&output)
+        .unwrap()
+        .lines()
+        .find_map(|line| serde_json::from_str(line).ok())
+        .expect("No JSON error printed");
+    assert_eq!(error["error_kind"], "execution");
+}
+
+#[tokio::test]
+async fn test_declare_all_skips_already_declared() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--all",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: declare
+        contracts: [{class_hash: 0x[..], contract_name: Map, status: AlreadyDeclared}]
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_declare_multiple_deduplicates_and_reports_partial_results() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "--json",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--contract-name",
+        "Map",
+        "--contract-name",
+        "NonExistent",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure().get_output().stdout.clone();
+
+    // Scarb build output precedes the command output
+    let stdout = String::from_utf8(output).unwrap();
+    let json: serde_json::Value = serde_json::from_str(stdout.lines().last().unwrap()).unwrap();
+    let contracts = json["contracts"].as_array().unwrap();
+    assert_eq!(contracts.len(), 2);
+    assert_eq!(contracts[0]["contract_name"], "Map");
+    assert_eq!(contracts[0]["status"], "AlreadyDeclared");
+    assert_eq!(contracts[1]["contract_name"], "NonExistent");
+    assert_eq!(contracts[1]["status"], "Failed");
+    assert!(contracts[1]["error"]
+        .as_str()
+        .unwrap()
+        .contains("Failed to find NonExistent artifact"));
+}
+
+#[test]
+fn test_declare_multiple_with_class_hash() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--contract-name",
+        "NonExistent",
+        "--class-hash",
+        "0x1",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: `--class-hash` can only be used when declaring a single contract",
+    );
+}
+
 #[tokio::test]
 async fn test_invalid_nonce() {
     let contract_path =
@@ -126,6 +253,7 @@ async fn test_invalid_nonce() {
         "99999999999999999",
         "--nonce",
         "12345",
+        "--force-nonce",
     ];
 
     let snapbox = runner(&args).current_dir(contract_path.path());
@@ -168,6 +296,95 @@ async fn test_wrong_contract_name_passed() {
     );
 }
 
+#[test]
+fn test_build_profile_does_not_exist() {
+    let contract_path = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--build-profile",
+        "nonexistent",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().failure();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Failed to build contract
+        [..]Profile = nonexistent does not exist in Scarb. Available profiles: [..]
+        "},
+    );
+}
+
+#[test]
+fn test_target_does_not_exist() {
+    let contract_path = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--target",
+        "nonexistent",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().failure();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Failed to build contract
+        [..]Target = nonexistent does not exist in package = map. Available targets: [..]
+        "},
+    );
+}
+
+#[test]
+fn test_no_build_without_artifacts() {
+    let contract_path = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--no-build",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().failure();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Failed to build contract
+        [..]Failed to load artifacts of the package, build it first or run without `--no-build`
+        "},
+    );
+}
+
 #[test]
 fn test_scarb_build_fails_when_wrong_cairo_path() {
     let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/build_fails");
@@ -189,10 +406,65 @@ fn test_scarb_build_fails_when_wrong_cairo_path() {
     let output = snapbox.assert().failure();
     assert_stderr_contains(
         output,
-        "Failed to build contract: Failed to build using scarb; `scarb` exited with error",
+        indoc! {r"
+        Error: Failed to build contract
+        [..]Failed to build using scarb; `scarb` exited with error
+        "},
+    );
+}
+
+#[test]
+fn test_scarb_build_diagnostics_shown() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/build_fails");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "BuildFails",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        [..]`scarb` exited with error
+        [..]Type not found[..]
+        "},
     );
 }
 
+#[test]
+fn test_scarb_version_not_installed() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--scarb-version",
+        "0.0.1",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+    assert_stderr_contains(output, "Error: Scarb version = 0.0.1 is not [..]");
+}
+
 #[should_panic(expected = "Path to Scarb.toml manifest does not exist")]
 #[test]
 fn test_scarb_build_fails_scarb_toml_does_not_exist() {
@@ -275,6 +547,63 @@ fn test_too_low_max_fee() {
     );
 }
 
+#[test]
+fn test_class_hash_mismatch() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--class-hash",
+        "0x1",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: declare
+        error: Class hash of the built artifact = [..] does not match the expected class hash = 0x1
+        "},
+    );
+}
+
+#[test]
+fn test_size_only() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+
+    let args = vec![
+        "--url",
+        URL,
+        "--json",
+        "declare",
+        "--contract-name",
+        "Map",
+        "--size-only",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let response: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(response["command"], "declare");
+    assert!(response.get("transaction_hash").is_none());
+    for field in ["sierra_size", "casm_size", "bytecode_length"] {
+        let size: u64 = response[field].as_str().unwrap().parse().unwrap();
+        assert!(size > 0);
+    }
+}
+
 #[should_panic(expected = "Make sure you have enabled sierra code generation in Scarb.toml")]
 #[test]
 fn test_scarb_no_sierra_artifact() {
@@ -448,7 +777,10 @@ async fn test_no_scarb_profile() {
         ...
         [WARNING] Profile profile5 does not exist in scarb, using default 'dev' profile.
         command: declare
+        bytecode_length: [..]
+        casm_size: [..]
         class_hash: [..]
+        sierra_size: [..]
         transaction_hash: [..]
     "});
 }
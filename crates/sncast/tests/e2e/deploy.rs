@@ -3,8 +3,9 @@ use crate::helpers::constants::{
 };
 use crate::helpers::fixtures::{default_cli_args, get_transaction_hash, get_transaction_receipt};
 use crate::helpers::runner::runner;
-use indoc::indoc;
-use shared::test_utils::output_assert::assert_stderr_contains;
+use indoc::{formatdoc, indoc};
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use starknet::core::types::FieldElement;
 use starknet::core::types::TransactionReceipt::Deploy;
 use test_case::test_case;
 
@@ -65,6 +66,54 @@ async fn test_happy_case_with_constructor() {
     assert!(matches!(receipt, Deploy(_)));
 }
 
+#[tokio::test]
+async fn test_happy_case_with_constructor_args() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user4",
+        "--int-format",
+        "--json",
+        "deploy",
+        "--class-hash",
+        CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--constructor-args",
+        "second=1",
+        "first=0x1",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Deploy(_)));
+}
+
+#[test]
+fn test_constructor_args_type_mismatch() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user9",
+        "deploy",
+        "--class-hash",
+        CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--constructor-args",
+        "0x1",
+        "[1, 2]",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Failed to encode constructor argument = second",
+    );
+}
+
 #[test]
 fn test_wrong_calldata() {
     let mut args = default_cli_args();
@@ -126,6 +175,31 @@ fn test_contract_already_deployed() {
         "0x1",
     ]);
 
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Contract of class = [..] is already deployed at address = [..]. Use a different `--salt` or pass `--allow-redeploy` to send the transaction anyway
+        "},
+    );
+}
+
+#[test]
+fn test_contract_already_deployed_allow_redeploy() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user1",
+        "deploy",
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "0x1",
+        "--allow-redeploy",
+    ]);
+
     let snapbox = runner(&args);
     let output = snapbox.assert().success();
 
@@ -138,6 +212,75 @@ fn test_contract_already_deployed() {
     );
 }
 
+#[test]
+fn test_salt_auto_skips_deployed_address() {
+    // A random salt makes sure the contract was not deployed by a previous run
+    let salt = FieldElement::from(rand::random::<u64>());
+    let salt_hex = format!("{salt:#x}");
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user14",
+        "deploy",
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        &salt_hex,
+    ]);
+    runner(&args).assert().success();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user14",
+        "deploy",
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "auto",
+        "--auto-salt-start",
+        &salt_hex,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        formatdoc! {r"
+        command: deploy
+        contract_address: 0x[..]
+        salt: {:#x}
+        transaction_hash: 0x[..]
+        ", salt + FieldElement::ONE},
+    );
+}
+
+#[test]
+fn test_salt_invalid() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user14",
+        "deploy",
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "random",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        [..]Failed to parse salt = random, expected a felt or `auto`[..]
+        "},
+    );
+}
+
 #[test]
 fn test_too_low_max_fee() {
     let mut args = default_cli_args();
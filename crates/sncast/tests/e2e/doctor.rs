@@ -0,0 +1,35 @@
+use crate::helpers::runner::runner;
+use configuration::copy_config_to_tempdir;
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stderr_contains;
+
+#[tokio::test]
+async fn test_happy_case() {
+    let tempdir = copy_config_to_tempdir("tests/data/files/correct_snfoundry.toml", None).unwrap();
+    let args = vec!["doctor"];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        command: doctor
+        accounts: failed: Accounts file = [..] does not exist; hint: [..]
+        profile: passed: using default profile
+        rpc: passed: http://127.0.0.1:5055/rpc is reachable, chain id = alpha-sepolia
+        scarb: passed: scarb [..] (cairo [..])
+    "});
+}
+
+#[tokio::test]
+async fn test_rpc_not_reachable() {
+    let args = vec!["--url", "http://127.0.0.1:1/rpc", "doctor"];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Some of the critical checks failed
+        "},
+    );
+}
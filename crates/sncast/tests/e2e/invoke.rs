@@ -1,9 +1,19 @@
-use crate::helpers::constants::{ACCOUNT, MAP_CONTRACT_ADDRESS_SEPOLIA};
-use crate::helpers::fixtures::{default_cli_args, get_transaction_hash, get_transaction_receipt};
+use crate::helpers::constants::{
+    ACCOUNT, CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA, ETH_TOKEN_ADDRESS,
+    MAP_CONTRACT_ADDRESS_SEPOLIA, MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+    MAP_CONTRACT_DECLARE_TX_HASH_SEPOLIA, URL,
+};
+use crate::helpers::fixtures::{
+    default_cli_args, get_accounts_path, get_transaction_hash, get_transaction_receipt,
+};
 use crate::helpers::runner::runner;
 use indoc::indoc;
-use shared::test_utils::output_assert::assert_stderr_contains;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use starknet::core::types::FieldElement;
 use starknet::core::types::TransactionReceipt::Invoke;
+use starknet::core::utils::{get_selector_from_name, get_storage_var_address};
+use std::fs;
+use tempfile::tempdir;
 use test_case::test_case;
 
 #[test_case("oz_cairo_0"; "cairo_0_account")]
@@ -38,6 +48,67 @@ async fn test_happy_case(account: &str) {
     assert!(matches!(receipt, Invoke(_)));
 }
 
+#[tokio::test]
+async fn test_happy_case_selector_from_file() {
+    let tempdir = tempdir().unwrap();
+    let selector_file = tempdir.path().join("selector");
+    fs::write(
+        &selector_file,
+        format!("{:#x}\n", get_selector_from_name("put").unwrap()),
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--json",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--selector-from-file",
+        selector_file.to_str().unwrap(),
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Invoke(_)));
+}
+
+#[test]
+fn test_output_template_unknown_field_checked_before_sending() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--output-template",
+        "{tx_hash}",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Unknown field = tx_hash in `--output-template`, available fields: command, new_class_hash, nonce, old_class_hash, sponsored_by, transaction_hash",
+    );
+}
+
 #[tokio::test]
 async fn test_contract_does_not_exist() {
     let mut args = default_cli_args();
@@ -145,3 +216,622 @@ fn test_too_low_max_fee() {
         "},
     );
 }
+
+#[test]
+fn test_max_fee_above_abort_ratio() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user11",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1",
+        "0x2",
+        "--max-fee",
+        "99999999999999999",
+        "--max-fee-abort-ratio",
+        "10",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: invoke
+        error: Max fee = 99999999999999999 is [..]x the estimated fee = [..], above the allowed ratio = 10. Lower `--max-fee` or raise `--max-fee-abort-ratio` if this is intended
+        "},
+    );
+}
+
+#[test]
+fn test_reverted_transaction_with_wait() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--wait",
+        "invoke",
+        "--contract-address",
+        "0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf",
+        "--function",
+        "deployContract",
+        "--calldata",
+        CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA,
+        "0x3f1c5a2d9e8b7",
+        "0x1",
+        "0x3",
+        "0x43",
+        "0x41",
+        "0x1",
+        "--max-fee",
+        "43399999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: invoke
+        error: Transaction has been reverted = Insufficient max fee[..]
+        "},
+    );
+}
+
+#[test]
+fn test_from_deploy_not_a_deploy_transaction() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "invoke",
+        "--from-deploy",
+        MAP_CONTRACT_DECLARE_TX_HASH_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Transaction with hash = [..] is not a deploy transaction
+        "},
+    );
+}
+
+#[test]
+fn test_from_deploy_transaction_not_found() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "invoke",
+        "--from-deploy",
+        "0x1",
+        "--function",
+        "put",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Transaction with hash = 0x1 not found
+        "},
+    );
+}
+
+#[test]
+fn test_nonce_file_allocates_consecutive_nonces() {
+    let tempdir = tempdir().expect("Failed to create a temporary directory");
+    let nonce_file = tempdir.path().join("nonces.json");
+    let nonce_file = nonce_file.to_str().unwrap();
+
+    for value in ["0x1", "0x2"] {
+        let mut args = default_cli_args();
+        args.append(&mut vec![
+            "--account",
+            "user10",
+            "--nonce-file",
+            nonce_file,
+            "invoke",
+            "--contract-address",
+            MAP_CONTRACT_ADDRESS_SEPOLIA,
+            "--function",
+            "put",
+            "--calldata",
+            "0x1",
+            value,
+        ]);
+
+        let snapbox = runner(&args);
+        let output = snapbox.assert().success();
+        assert_stdout_contains(output, "transaction_hash: 0x[..]");
+    }
+
+    let contents: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(nonce_file).unwrap()).unwrap();
+    let nonces = contents["alpha-sepolia"].as_object().unwrap();
+    assert_eq!(nonces.len(), 1);
+}
+
+#[test]
+fn test_nonce_next() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user15",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--nonce",
+        "next",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: invoke
+        nonce: 0x[..]
+        transaction_hash: 0x[..]
+        "},
+    );
+}
+
+#[test]
+fn test_nonce_invalid() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user15",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--nonce",
+        "random",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        [..]Failed to parse nonce = random, expected a felt or `next`[..]
+        "},
+    );
+}
+
+#[test]
+fn test_paymaster_url_conflicts_with_max_fee() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user15",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--max-fee",
+        "99999999999999999",
+        "--paymaster-url",
+        "http://127.0.0.1:12345",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        error: the argument '--max-fee <MAX_FEE>' cannot be used with '--paymaster-url <PAYMASTER_URL>'
+        "},
+    );
+}
+
+#[test]
+fn test_nonce_does_not_match_account_nonce() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--nonce",
+        "0x123456",
+    ]);
+
+    let snapbox = runner(&args);
+    // Exit code of account errors
+    let output = snapbox.assert().code(5);
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Provided nonce = 0x123456 does not match the account nonce = [..]. Use `--force-nonce` to send the transaction anyway
+        "},
+    );
+}
+
+#[test]
+fn test_skip_deploy_check() {
+    let accounts_json_path = get_accounts_path("tests/data/accounts/faulty_accounts.json");
+    let args = vec![
+        "--url",
+        URL,
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "with_nonexistent_address",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--skip-deploy-check",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    let stderr = std::str::from_utf8(&output.get_output().stderr).unwrap();
+    assert!(!stderr.contains("Make sure the account is deployed"));
+}
+
+#[test]
+fn test_confirm_aborted() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--confirm",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args).stdin("n");
+    let output = snapbox.assert().failure();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        About to send a transaction:
+          network: alpha-sepolia
+          account: user2
+          action: invoke put on [..]
+          max fee: estimated automatically
+        "},
+    );
+}
+
+#[test]
+fn test_confirm_with_json_requires_yes() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--confirm",
+        "--json",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Confirmation prompt is not shown in JSON mode, pass `--yes` to send the transaction",
+    );
+}
+
+#[test]
+fn test_value_approve() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user13",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--value",
+        "1gwei",
+        "--token",
+        ETH_TOKEN_ADDRESS,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(output, "transaction_hash: 0x[..]");
+}
+
+#[test]
+fn test_value_exceeds_balance() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user13",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--value",
+        "0xffffffffffffffffffffffffffffffff",
+        "--token",
+        ETH_TOKEN_ADDRESS,
+        "--value-call",
+        "transfer",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Account balance = [..] of token = 0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7 is lower than `--value` = 340282366920938463463374607431768211455",
+    );
+}
+
+#[test]
+fn test_calldata_exceeds_max_calldata_len() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--max-calldata-len",
+        "1",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Calldata length = 2 exceeds the maximum = 1. Pass a higher `--max-calldata-len` if this is intended",
+    );
+}
+
+#[test]
+fn test_value_call_counts_towards_max_calldata_len() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user13",
+        "--max-calldata-len",
+        "2",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--value",
+        "1gwei",
+        "--token",
+        ETH_TOKEN_ADDRESS,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Calldata length = 5 exceeds the maximum = 2. Pass a higher `--max-calldata-len` if this is intended",
+    );
+}
+
+#[test]
+fn test_replace_class_not_declared() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--replace-class",
+        "0x1",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Class with hash = 0x1 is not declared, declare it before passing it to `--replace-class`",
+    );
+}
+
+#[test]
+fn test_replace_class_same_class() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--replace-class",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Contract at address [..] already has class = [..]",
+    );
+}
+
+#[tokio::test]
+async fn test_happy_case_abi_file_named_args() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--json",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--calldata",
+        "value=0x2 key=0x1",
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Invoke(_)));
+}
+
+#[test]
+fn test_abi_file_calldata_mismatch() {
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--calldata",
+        "0x1",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Expected 2 function arguments, got 1
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_happy_case_follow_proxy_impl_slot() {
+    // Use the map contract as a proxy storing the address of its implementation, itself, under this key
+    let key = "0x70726f7879";
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--json",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        key,
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--max-fee",
+        "99999999999999999",
+    ]);
+    let output = runner(&args).assert().success().get_output().stdout.clone();
+    get_transaction_receipt(get_transaction_hash(&output)).await;
+
+    let impl_slot =
+        get_storage_var_address("storage", &[FieldElement::from_hex_be(key).unwrap()]).unwrap();
+    let impl_slot = format!("{impl_slot:#x}");
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        ACCOUNT,
+        "--json",
+        "invoke",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1 0x2",
+        "--follow-proxy",
+        "--impl-slot",
+        &impl_slot,
+        "--max-fee",
+        "99999999999999999",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Invoke(_)));
+}
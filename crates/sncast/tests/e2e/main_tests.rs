@@ -144,7 +144,7 @@ async fn test_nonexistent_account_address() {
 
     assert_stderr_contains(
         output,
-        "Error: Account with address 0x1010101010011aaabbcc not found on network SN_SEPOLIA",
+        "Error: Account with address 0x1010101010011aaabbcc not found on network SN_SEPOLIA. Make sure the account is deployed, e.g. with `sncast account deploy` command",
     );
 }
 
@@ -302,3 +302,51 @@ async fn test_keystore_declare() {
 
     assert!(snapbox.assert().success().get_output().stderr.is_empty());
 }
+
+#[test]
+fn test_log_level() {
+    let args = vec![
+        "--url",
+        URL,
+        "--log-level",
+        "debug",
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        "DEBUG sncast::helpers::traced_transport: Sending JSON-RPC request method=Call",
+    );
+}
+
+#[test]
+fn test_log_level_from_env() {
+    let args = vec![
+        "--url",
+        URL,
+        "call",
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+    ];
+
+    let snapbox = runner(&args).env("SNCAST_LOG", "debug");
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        "DEBUG sncast::helpers::traced_transport: Sending JSON-RPC request method=Call",
+    );
+}
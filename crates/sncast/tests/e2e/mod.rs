@@ -1,10 +1,14 @@
+mod abi;
 mod account;
 mod call;
+mod class_hash;
 mod declare;
 mod deploy;
+mod doctor;
 mod invoke;
 mod main_tests;
 mod multicall;
+mod schema;
 mod script;
 mod show_config;
 mod tx_status;
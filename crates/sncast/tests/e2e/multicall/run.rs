@@ -1,9 +1,14 @@
 use crate::helpers::constants::MULTICALL_CONFIGS_DIR;
 use crate::helpers::fixtures::default_cli_args;
 use crate::helpers::runner::runner;
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use shared::test_utils::output_assert::{assert_stderr_contains, AsOutput};
+use starknet::core::types::FieldElement;
+use starknet::core::utils::{get_udc_deployed_address, UdcUniqueness};
+use starknet::macros::felt;
+use std::fs;
 use std::path::Path;
+use tempfile::tempdir;
 use test_case::test_case;
 
 #[test_case("oz_cairo_0"; "cairo_0_account")]
@@ -34,6 +39,7 @@ async fn test_happy_case(account: &str) {
 
     output.stdout_matches(indoc! {r"
         command: multicall run
+        deployed_contracts: [{contract_address: 0x[..], id: map_contract}]
         transaction_hash: 0x[..]
     "});
 }
@@ -62,6 +68,7 @@ async fn test_calldata_ids() {
 
     output.stdout_matches(indoc! {r"
         command: multicall run
+        deployed_contracts: [{contract_address: 0x[..], id: map_contract}, {contract_address: 0x[..], id: constructor-params}]
         transaction_hash: 0x[..]
     "});
 }
@@ -86,6 +93,31 @@ async fn test_invalid_path() {
     );
 }
 
+#[tokio::test]
+async fn test_unreachable_url() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user2"]);
+
+    args.append(&mut vec![
+        "multicall",
+        "run",
+        "--path",
+        "http://127.0.0.1:1/multicall.toml",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert!(output.as_stdout().is_empty());
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: multicall run
+        error: Failed to download multicall file from http://127.0.0.1:1/multicall.toml[..]
+        "},
+    );
+}
+
 #[tokio::test]
 async fn test_deploy_fail() {
     let mut args = default_cli_args();
@@ -160,3 +192,228 @@ async fn test_deploy_success_invoke_fails() {
         "},
     );
 }
+
+#[tokio::test]
+async fn test_non_atomic_with_per_call_max_fee() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user12"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_max_fee.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec![
+        "--wait",
+        "multicall",
+        "run",
+        "--path",
+        path_str,
+        "--non-atomic",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert();
+
+    let stderr_str = output.as_stderr();
+    assert!(
+        stderr_str.is_empty(),
+        "Multicall error, stderr: \n{stderr_str}",
+    );
+
+    output.stdout_matches(indoc! {r"
+        ...
+        command: multicall run
+        deployed_contracts: [{contract_address: 0x[..], id: map_contract}]
+        transaction_hashes: [0x[..], 0x[..]]
+    "});
+}
+
+#[tokio::test]
+async fn test_non_atomic_stream() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user12"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_max_fee.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec![
+        "--json",
+        "--wait",
+        "multicall",
+        "run",
+        "--path",
+        path_str,
+        "--non-atomic",
+        "--stream",
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let responses: Vec<serde_json::Value> = std::str::from_utf8(&output)
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let call_numbers: Vec<&serde_json::Value> = responses
+        .iter()
+        .filter_map(|response| response.get("call_number"))
+        .collect();
+    assert_eq!(call_numbers, ["1", "2"]);
+
+    let last_response = responses.last().unwrap();
+    assert_eq!(
+        last_response["transaction_hashes"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_per_call_max_fee_in_atomic_mode() {
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user12"]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke_max_fee.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec!["multicall", "run", "--path", path_str]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: multicall run
+        error: Per-call `max_fee` can only be used together with `--non-atomic` flag
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_deploy_with_salt() {
+    // A random salt makes sure the contract was not deployed by a previous run
+    let salt = FieldElement::from(rand::random::<u64>());
+    let class_hash = felt!("0x02a09379665a749e609b4a8459c86fe954566a6beeaddd0950e43f6c700ed321");
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("deploy_salt.toml");
+    fs::write(
+        &path,
+        formatdoc! {r#"
+            [[call]]
+            call_type = "deploy"
+            class_hash = "{class_hash:#x}"
+            inputs = []
+            id = "map_contract"
+            salt = "{salt:#x}"
+        "#},
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec!["--account", "user5"]);
+    args.append(&mut vec![
+        "multicall",
+        "run",
+        "--path",
+        path.to_str().unwrap(),
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert();
+
+    let stderr_str = output.as_stderr();
+    assert!(
+        stderr_str.is_empty(),
+        "Multicall error, stderr: \n{stderr_str}",
+    );
+
+    let expected_address =
+        get_udc_deployed_address(salt, class_hash, &UdcUniqueness::NotUnique, &[]);
+    output.stdout_matches(format!(
+        "command: multicall run\ndeployed_contracts: [{{contract_address: {expected_address:#x}, id: map_contract}}]\ntransaction_hash: 0x[..]\n"
+    ));
+}
+
+#[test_case(&[], "Calldata length = 8 exceeds the maximum = 5[..]"; "atomic")]
+#[test_case(&["--non-atomic"], "Call number 1 cannot be sent: Calldata length = 4 exceeds the maximum = 3[..]"; "non_atomic")]
+fn test_calldata_exceeds_max_calldata_len(run_args: &[&str], error: &str) {
+    let max_calldata_len = if run_args.is_empty() { "5" } else { "3" };
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--max-calldata-len",
+        max_calldata_len,
+    ]);
+
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke.toml");
+    let path_str = path.to_str().expect("failed converting path to str");
+
+    args.append(&mut vec!["multicall", "run", "--path", path_str]);
+    args.extend(run_args);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        formatdoc! {r"
+        command: multicall run
+        error: {error}
+        "},
+    );
+}
+
+#[test]
+fn test_strict_felt_parsing() {
+    let tempdir = tempdir().unwrap();
+    let path = tempdir.path().join("invoke_ambiguous_input.toml");
+    fs::write(
+        &path,
+        formatdoc! {r#"
+            [[call]]
+            call_type = "invoke"
+            contract_address = "0x123"
+            function = "put"
+            inputs = ["0x1", "ff"]
+        "#},
+    )
+    .unwrap();
+
+    let mut args = default_cli_args();
+    args.append(&mut vec![
+        "--account",
+        "user2",
+        "--strict-felt-parsing",
+        "multicall",
+        "run",
+        "--path",
+        path.to_str().unwrap(),
+    ]);
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: multicall run
+        error: Failed to parse input to FieldElement: Felt = ff is ambiguous, hex values have to be prefixed with `0x` and decimal values may only contain digits
+        "},
+    );
+}
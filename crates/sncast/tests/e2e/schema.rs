@@ -0,0 +1,29 @@
+use crate::helpers::runner::runner;
+use serde_json::Value;
+use shared::test_utils::output_assert::assert_stderr_contains;
+
+#[test]
+fn test_happy_case() {
+    let args = vec!["schema", "account", "deploy"];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let schema: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(schema["title"], "account deploy");
+    assert_eq!(schema["properties"]["command"]["const"], "account deploy");
+    assert!(schema["properties"]["transaction_hash"].is_object());
+}
+
+#[test]
+fn test_unknown_command() {
+    let args = vec!["schema", "nonexistent"];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Unknown command = nonexistent. Possible values: [..]",
+    );
+}
@@ -111,6 +111,82 @@ async fn test_fail_when_using_starknet_syscall() {
     );
 }
 
+#[tokio::test]
+async fn test_get_contract_from_scarb_toml() {
+    let script_dir =
+        copy_script_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/misc", Vec::<String>::new());
+
+    let script_name = "get_contract";
+    let args = vec!["--url", URL, "script", "run", &script_name];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        status: success
+    "});
+}
+
+#[tokio::test]
+async fn test_get_env() {
+    let script_dir =
+        copy_script_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/misc", Vec::<String>::new());
+
+    let script_name = "get_env";
+    let args = vec!["--url", URL, "script", "run", &script_name];
+
+    let snapbox = runner(&args)
+        .env("SNCAST_SCRIPT_TEST_SECRET", "abc")
+        .current_dir(script_dir.path());
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        status: success
+    "});
+}
+
+#[tokio::test]
+async fn test_get_env_required_missing() {
+    let script_dir =
+        copy_script_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/misc", Vec::<String>::new());
+
+    let script_name = "get_env";
+    let args = vec!["--url", URL, "script", "run", &script_name];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r#"
+        command: script run
+        message: 
+            "Environment variable = SNCAST_SCRIPT_TEST_SECRET required by the script is not set"
+        "#},
+    );
+}
+
+#[tokio::test]
+async fn test_get_env_not_allowed() {
+    let script_dir =
+        copy_script_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/misc", Vec::<String>::new());
+
+    let script_name = "get_env_not_allowed";
+    let args = vec!["--url", URL, "script", "run", &script_name];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r#"
+        command: script run
+        message: 
+            "Environment variable = HOME is not allowed in scripts, add it to `allowed-env-vars` in `[tool.sncast]` section of Scarb.toml"
+        "#},
+    );
+}
+
 #[tokio::test]
 async fn test_incompatible_sncast_std_version() {
     let script_dir = copy_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/old_sncast_std/scripts");
@@ -274,7 +350,7 @@ async fn test_nonexistent_account_address() {
         output,
         indoc! {r"
         command: script run
-        error: Account with address 0x1010101010011aaabbcc not found on network SN_SEPOLIA
+        error: Account with address 0x1010101010011aaabbcc not found on network SN_SEPOLIA. Make sure the account is deployed, e.g. with `sncast account deploy` command
         "},
     );
 }
@@ -545,3 +621,145 @@ async fn test_using_release_profile() {
         status: success
     "});
 }
+
+#[tokio::test]
+async fn test_plan() {
+    let contract_dir = duplicate_contract_directory_with_salt(
+        SCRIPTS_DIR.to_owned() + "/map_script/contracts/",
+        "dummy",
+        "plan",
+    );
+    let script_dir = copy_script_directory_to_tempdir(
+        SCRIPTS_DIR.to_owned() + "/declare/",
+        vec![contract_dir.as_ref()],
+    );
+    let accounts_json_path = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user4",
+        "--url",
+        URL,
+        "script",
+        "run",
+        "plan",
+        "--plan",
+        "plan.json",
+        "--no-state-file",
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        status: planned
+    "});
+
+    let plan: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(script_dir.path().join("plan.json")).unwrap(),
+    )
+    .unwrap();
+    let steps = plan["steps"].as_array().unwrap();
+    let step_types: Vec<&str> = steps
+        .iter()
+        .map(|step| step["type"].as_str().unwrap())
+        .collect();
+    assert_eq!(step_types, ["declare", "deploy", "invoke"]);
+    assert_eq!(steps[0]["contract_name"], "Mapa");
+    assert_eq!(steps[1]["class_hash"], steps[0]["class_hash"]);
+    assert_eq!(steps[1]["salt"], "0x3");
+    assert_eq!(steps[2]["contract_address"], steps[1]["contract_address"]);
+}
+
+#[tokio::test]
+async fn test_manifest_out() {
+    let contract_dir = duplicate_contract_directory_with_salt(
+        SCRIPTS_DIR.to_owned() + "/map_script/contracts/",
+        "dummy",
+        "manifest",
+    );
+    let script_dir = copy_script_directory_to_tempdir(
+        SCRIPTS_DIR.to_owned() + "/declare/",
+        vec![contract_dir.as_ref()],
+    );
+    let accounts_json_path = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user16",
+        "--url",
+        URL,
+        "script",
+        "run",
+        "plan",
+        "--manifest-out",
+        "deployments.json",
+        "--no-state-file",
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        status: success
+    "});
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(script_dir.path().join("deployments.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(manifest["network"], "alpha-sepolia");
+    assert!(manifest["timestamp"].as_u64().unwrap() > 0);
+    let declarations = manifest["declarations"].as_array().unwrap();
+    let deployments = manifest["deployments"].as_array().unwrap();
+    assert_eq!(declarations.len(), 1);
+    assert_eq!(deployments.len(), 1);
+    assert_eq!(declarations[0]["contract_name"], "Mapa");
+    assert_eq!(deployments[0]["contract_name"], "Mapa");
+    assert_eq!(deployments[0]["class_hash"], declarations[0]["class_hash"]);
+    assert!(deployments[0]["contract_address"].is_string());
+    assert!(deployments[0]["transaction_hash"].is_string());
+}
+
+#[tokio::test]
+async fn test_fee_report() {
+    let contract_dir = duplicate_contract_directory_with_salt(
+        SCRIPTS_DIR.to_owned() + "/map_script/contracts/",
+        "dummy",
+        "fee_report",
+    );
+    let script_dir = copy_script_directory_to_tempdir(
+        SCRIPTS_DIR.to_owned() + "/declare/",
+        vec![contract_dir.as_ref()],
+    );
+    let accounts_json_path = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user18",
+        "--url",
+        URL,
+        "script",
+        "run",
+        "plan",
+        "--fee-report",
+        "--no-state-file",
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        declare_fee: 0x[..]
+        deploy_fee: 0x[..]
+        invoke_fee: 0x[..]
+        status: success
+        total_fee: 0x[..]
+    "});
+}
@@ -8,7 +8,9 @@ use sncast::helpers::braavos::BraavosAccountFactory;
 use sncast::helpers::constants::{
     ARGENT_CLASS_HASH, BRAAVOS_BASE_ACCOUNT_CLASS_HASH, BRAAVOS_CLASS_HASH,
 };
+use sncast::helpers::keystore::KeystoreFormat;
 use sncast::helpers::scarb_utils::get_package_metadata;
+use sncast::helpers::traced_transport::TracedTransport;
 use sncast::state::state_file::{
     ScriptTransactionEntry, ScriptTransactionOutput, ScriptTransactionStatus,
 };
@@ -182,6 +184,8 @@ pub async fn invoke_contract(
         &Utf8PathBuf::from(ACCOUNT_FILE_PATH),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await
     .expect("Could not get the account");
@@ -295,9 +299,9 @@ pub async fn get_transaction_receipt(tx_hash: FieldElement) -> TransactionReceip
 }
 
 #[must_use]
-pub fn create_test_provider() -> JsonRpcClient<HttpTransport> {
+pub fn create_test_provider() -> JsonRpcClient<TracedTransport> {
     let parsed_url = Url::parse(URL).unwrap();
-    JsonRpcClient::new(HttpTransport::new(parsed_url))
+    JsonRpcClient::new(TracedTransport::new(HttpTransport::new(parsed_url)))
 }
 
 pub fn copy_file(src_path: impl AsRef<std::path::Path>, dest_path: impl AsRef<std::path::Path>) {
@@ -5,6 +5,7 @@ use crate::helpers::fixtures::create_test_provider;
 
 use camino::Utf8PathBuf;
 use shared::rpc::{get_rpc_version, is_expected_version};
+use sncast::helpers::keystore::KeystoreFormat;
 use sncast::{check_if_legacy_contract, get_account, get_provider};
 use std::fs;
 use url::ParseError;
@@ -39,6 +40,8 @@ async fn test_get_account() {
         &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await;
 
@@ -58,6 +61,8 @@ async fn test_get_account_no_file() {
         &Utf8PathBuf::from("tests/data/accounts/nonexistentfile.json"),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await;
     let err = account.unwrap_err();
@@ -74,6 +79,8 @@ async fn test_get_account_invalid_file() {
         &Utf8PathBuf::from("tests/data/accounts/invalid_format.json"),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await;
     let err = account.unwrap_err();
@@ -91,6 +98,8 @@ async fn test_get_account_no_account() {
         &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await;
     let err = account.unwrap_err();
@@ -107,6 +116,8 @@ async fn test_get_account_no_user_for_network() {
         &Utf8PathBuf::from("tests/data/accounts/accounts.json"),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await;
     let err = account.unwrap_err();
@@ -123,6 +134,8 @@ async fn test_get_account_failed_to_convert_field_elements() {
         &Utf8PathBuf::from("tests/data/accounts/faulty_accounts_invalid_felt.json"),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await;
     let err = account1.unwrap_err();
@@ -3,6 +3,7 @@ use crate::helpers::{
     fixtures::{create_test_provider, invoke_contract},
 };
 use sncast::helpers::constants::UDC_ADDRESS;
+use sncast::helpers::keystore::KeystoreFormat;
 
 use crate::helpers::constants::{
     CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA, MAP_CONTRACT_CLASS_HASH_SEPOLIA,
@@ -37,6 +38,8 @@ async fn test_rejected_transaction() {
         &Utf8PathBuf::from(ACCOUNT_FILE_PATH),
         &provider,
         None,
+        KeystoreFormat::Auto,
+        None,
     )
     .await
     .expect("Could not get the account");
@@ -282,6 +282,39 @@ pub fn get_nonce(block_tag: felt252) -> felt252 {
     *buf[0]
 }
 
+pub fn get_contract(name: ByteArray) -> Option<felt252> {
+    let mut inputs = array![];
+    name.serialize(ref inputs);
+
+    let mut buf = handle_cheatcode(cheatcode::<'get_contract'>(inputs.span()));
+
+    match Serde::<Option<felt252>>::deserialize(ref buf) {
+        Option::Some(result_data) => result_data,
+        Option::None => panic!("get_contract deserialize failed")
+    }
+}
+
+pub fn get_env(name: ByteArray) -> Option<ByteArray> {
+    read_env(name, false)
+}
+
+pub fn require_env(name: ByteArray) -> ByteArray {
+    read_env(name, true).unwrap()
+}
+
+fn read_env(name: ByteArray, required: bool) -> Option<ByteArray> {
+    let mut inputs = array![];
+    name.serialize(ref inputs);
+    required.serialize(ref inputs);
+
+    let mut buf = handle_cheatcode(cheatcode::<'get_env'>(inputs.span()));
+
+    match Serde::<Option<ByteArray>>::deserialize(ref buf) {
+        Option::Some(result_data) => result_data,
+        Option::None => panic!("get_env deserialize failed")
+    }
+}
+
 #[derive(Drop, Clone, Debug, Serde, PartialEq)]
 pub enum FinalityStatus {
     Received,